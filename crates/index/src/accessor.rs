@@ -16,6 +16,7 @@ use distance::Distance;
 use rabitq::byte::CodeMetadata;
 use simd::{Floating, f16};
 use std::marker::PhantomData;
+use vector::bvect::BVectOwned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
 use vector::vect::VectOwned;
@@ -26,6 +27,12 @@ pub struct L2S;
 #[derive(Debug, Clone, Copy)]
 pub struct Dot;
 
+#[derive(Debug, Clone, Copy)]
+pub struct Hamming;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Jaccard;
+
 pub trait Accessor2<E0, E1, M0, M1> {
     type Output;
     fn push(&mut self, input: &[E0], target: &[E1]);
@@ -125,6 +132,27 @@ where
     }
 }
 
+impl<E, M: Copy, A> Accessor1<E, M> for Vec<A>
+where
+    A: Accessor1<E, M>,
+{
+    type Output = Vec<A::Output>;
+
+    #[inline(always)]
+    fn push(&mut self, input: &[E]) {
+        for accessor in self.iter_mut() {
+            accessor.push(input);
+        }
+    }
+
+    #[inline(always)]
+    fn finish(self, input: M) -> Self::Output {
+        self.into_iter()
+            .map(|accessor| accessor.finish(input))
+            .collect()
+    }
+}
+
 pub struct FunctionalAccessor<T, P, F> {
     data: T,
     p: P,
@@ -284,6 +312,28 @@ where
     }
 }
 
+impl<E, M: Copy, A> TryAccessor1<E, M> for Vec<A>
+where
+    A: TryAccessor1<E, M>,
+{
+    type Output = Vec<A::Output>;
+
+    #[inline(always)]
+    fn push(&mut self, input: &[E]) -> Option<()> {
+        for accessor in self.iter_mut() {
+            accessor.push(input)?;
+        }
+        Some(())
+    }
+
+    #[inline(always)]
+    fn finish(self, input: M) -> Option<Self::Output> {
+        self.into_iter()
+            .map(|accessor| accessor.finish(input))
+            .collect()
+    }
+}
+
 pub struct LTryAccess<'a, E, M, A> {
     elements: &'a [E],
     metadata: M,
@@ -511,3 +561,46 @@ impl Accessor2<u8, u8, [f32; 4], [f32; 4]> for HalfbyteDistanceAccessor<Rabitq4O
         )
     }
 }
+
+#[derive(Debug)]
+pub struct BitDistanceAccessor<V, D>(u32, u32, PhantomData<fn(V) -> V>, PhantomData<fn(D) -> D>);
+
+impl<V, D> Default for BitDistanceAccessor<V, D> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self(0, 0, PhantomData, PhantomData)
+    }
+}
+
+impl Accessor2<u64, u64, (), ()> for BitDistanceAccessor<BVectOwned, Hamming> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u64], input: &[u64]) {
+        self.0 += simd::bit::reduce_sum_of_xor(target, input);
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        Distance::from_f32(self.0 as f32)
+    }
+}
+
+impl Accessor2<u64, u64, (), ()> for BitDistanceAccessor<BVectOwned, Jaccard> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u64], input: &[u64]) {
+        let (and, or) = simd::bit::reduce_sum_of_and_or(target, input);
+        self.0 += and;
+        self.1 += or;
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        if self.0 == 0 {
+            return Distance::from_f32(1.0);
+        }
+        Distance::from_f32(1.0 - (self.0 as f32 / self.1 as f32))
+    }
+}
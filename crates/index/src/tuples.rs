@@ -48,6 +48,27 @@ impl<'a> RefChecker<'a> {
         let bytes = &self.bytes[start..end];
         FromBytes::ref_from_bytes(bytes).expect("deserialization: bad bytes")
     }
+    #[inline]
+    pub fn try_prefix<T: FromBytes + IntoBytes + KnownLayout + Immutable + Sized>(
+        &self,
+        s: impl Into<usize> + Copy,
+    ) -> Option<&'a T> {
+        let start = Into::<usize>::into(s);
+        let end = Into::<usize>::into(s) + size_of::<T>();
+        let bytes = self.bytes.get(start..end)?;
+        FromBytes::ref_from_bytes(bytes).ok()
+    }
+    #[inline]
+    pub fn try_bytes<T: FromBytes + IntoBytes + KnownLayout + Immutable + ?Sized>(
+        &self,
+        s: impl Into<usize> + Copy,
+        e: impl Into<usize> + Copy,
+    ) -> Option<&'a T> {
+        let start = Into::<usize>::into(s);
+        let end = Into::<usize>::into(e);
+        let bytes = self.bytes.get(start..end)?;
+        FromBytes::ref_from_bytes(bytes).ok()
+    }
     /// # Safety
     ///
     /// * `FromBytes` could be implemented for `T`.
@@ -164,6 +164,13 @@ pub mod binary {
         qvector_sum: f32,
     }
 
+    impl BinaryLutMetadata {
+        #[inline(always)]
+        pub fn dis_v_2(&self) -> f32 {
+            self.dis_v_2
+        }
+    }
+
     pub type BinaryLut = (BinaryLutMetadata, [Vec<u64>; BITS]);
     pub type BinaryCode<'a> = ((f32, f32, f32, f32), &'a [u64]);
 
@@ -317,6 +324,13 @@ pub mod block {
         c: f32,
     }
 
+    impl BlockLutMetadata {
+        #[inline(always)]
+        pub fn dis_v_2(&self) -> f32 {
+            self.dis_v_2
+        }
+    }
+
     pub type BlockLut = (BlockLutMetadata, Vec<[u8; 16]>);
     pub type BlockCode<'a> = (
         &'a [f32; 32],
@@ -22,7 +22,8 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
     index: &R,
     check: impl Fn(),
     callback: impl Fn(NonZero<u64>) -> bool,
-) where
+) -> u64
+where
     R::Page: Page<Opaque = Opaque>,
 {
     let meta_guard = index.read(0);
@@ -32,8 +33,9 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
     let link = meta_guard.get_opaque().link;
     drop(meta_guard);
     let Some(_) = start.into_inner() else {
-        return;
+        return 0;
     };
+    let mut removed = 0_u64;
     let mut current = link;
     while current != u32::MAX {
         check();
@@ -59,6 +61,7 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
                     let p = tuple.payload();
                     if Some(true) == p.map(&callback) {
                         *p = None;
+                        removed += 1;
                     }
                 }
             }
@@ -67,4 +70,5 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
             current = read.get_opaque().next;
         }
     }
+    removed
 }
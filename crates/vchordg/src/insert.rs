@@ -238,7 +238,7 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
         m,
         &alpha,
         |(_, u)| *u,
-        O::DISTANCE == DistanceKind::L2S,
+        matches!(O::DISTANCE, DistanceKind::L2S | DistanceKind::Hamming),
     );
     let _ = update::<R, O>(
         (index, pointers_t.as_slice()),
@@ -290,7 +290,7 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
                 m,
                 &alpha,
                 |(_, u)| *u,
-                O::DISTANCE == DistanceKind::L2S,
+                matches!(O::DISTANCE, DistanceKind::L2S | DistanceKind::Hamming),
             );
             if update::<R, O>(
                 (index, pointers_u),
@@ -0,0 +1,194 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::operator::Operator;
+use crate::tuples::{MetaTuple, VertexTuple, WithReader};
+use crate::vectors::{copy_outs, read_without_accessor};
+use index::relation::{Page, RelationRead};
+use std::collections::{HashSet, VecDeque};
+
+pub struct Info {
+    pub dim: u32,
+    pub bits: u8,
+    pub m: u32,
+    pub alpha: Vec<f32>,
+    pub ef_construction: u32,
+    pub beam_construction: u32,
+    pub start: Option<(u32, u16)>,
+    pub number_of_vertex_pages: u64,
+    pub number_of_vector_pages: u64,
+}
+
+pub struct GraphStats {
+    pub number_of_vertices: u64,
+    pub number_of_deleted_vertices: u64,
+    pub number_of_dangling_pointers: u64,
+    pub number_of_unreachable_vertices: u64,
+    /// The `i`-th element is the number of vertices with `i` out-neighbours.
+    pub out_degrees: Vec<u64>,
+}
+
+pub fn info<R: RelationRead>(index: &R, check: impl Fn()) -> Info
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let bits = meta_tuple.bits();
+    let m = meta_tuple.m();
+    let alpha = meta_tuple.alpha().to_vec();
+    let ef_construction = meta_tuple.ef_construction();
+    let beam_construction = meta_tuple.beam_construction();
+    let start = meta_tuple.start().into_inner();
+    let link = meta_guard.get_opaque().link;
+    drop(meta_guard);
+    let mut number_of_vertex_pages = 0_u64;
+    let mut number_of_vector_pages = 0_u64;
+    let mut current = link;
+    while current != u32::MAX {
+        check();
+        let vertex_guard = index.read(current);
+        number_of_vertex_pages += 1;
+        let mut vector_current = vertex_guard.get_opaque().link;
+        current = vertex_guard.get_opaque().next;
+        drop(vertex_guard);
+        while vector_current != u32::MAX {
+            check();
+            number_of_vector_pages += 1;
+            vector_current = index.read(vector_current).get_opaque().next;
+        }
+    }
+    Info {
+        dim,
+        bits,
+        m,
+        alpha,
+        ef_construction,
+        beam_construction,
+        start,
+        number_of_vertex_pages,
+        number_of_vector_pages,
+    }
+}
+
+/// Walks the vertex tape, then the graph from the start vertex. A pointer is
+/// dangling if it refers to a vertex or a vector that does not exist, including
+/// one on a page beyond `number_of_pages`. Deleted vertices are not counted as
+/// unreachable.
+pub fn graph_stats<R: RelationRead, O: Operator>(
+    index: &R,
+    number_of_pages: u32,
+    check: impl Fn(),
+) -> GraphStats
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let m = meta_tuple.m();
+    let start = meta_tuple.start().into_inner();
+    let link = meta_guard.get_opaque().link;
+    drop(meta_guard);
+
+    let exists = |(id, i): (u32, u16)| id < number_of_pages && index.read(id).get(i).is_some();
+    let outs = |(id, i): (u32, u16)| {
+        if id >= number_of_pages {
+            return None;
+        }
+        let vertex_guard = index.read(id);
+        let vertex_bytes = vertex_guard.get(i)?;
+        let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
+        let pointers = vertex_tuple.pointers().to_vec();
+        drop(vertex_guard);
+        if pointers.is_empty()
+            || pointers
+                .iter()
+                .any(|pointer| pointer.into_inner().0 >= number_of_pages)
+        {
+            return None;
+        }
+        read_without_accessor::<R, O, _>((index, &pointers), copy_outs)
+            .ok()
+            .map(|(outs, ..)| outs)
+    };
+
+    let mut number_of_vertices = 0_u64;
+    let mut number_of_deleted_vertices = 0_u64;
+    let mut number_of_dangling_pointers = 0_u64;
+    let mut out_degrees = vec![0_u64; 1 + m as usize];
+    let mut alive = Vec::new();
+    let mut current = link;
+    while current != u32::MAX {
+        check();
+        if current >= number_of_pages {
+            number_of_dangling_pointers += 1;
+            break;
+        }
+        let vertex_guard = index.read(current);
+        let mut members = Vec::new();
+        for i in 1..=vertex_guard.len() {
+            if let Some(vertex_bytes) = vertex_guard.get(i) {
+                let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
+                members.push(((current, i), vertex_tuple.payload().is_some()));
+            }
+        }
+        current = vertex_guard.get_opaque().next;
+        drop(vertex_guard);
+        for (u, is_alive) in members {
+            number_of_vertices += 1;
+            if is_alive {
+                alive.push(u);
+            } else {
+                number_of_deleted_vertices += 1;
+            }
+            let Some(outs_u) = outs(u) else {
+                number_of_dangling_pointers += 1;
+                continue;
+            };
+            out_degrees[outs_u.len().min(m as usize)] += 1;
+            for v in outs_u {
+                if !exists(v) {
+                    number_of_dangling_pointers += 1;
+                }
+            }
+        }
+    }
+
+    let mut visited = HashSet::<(u32, u16)>::new();
+    if let Some(s) = start {
+        let mut queue = VecDeque::from([s]);
+        visited.insert(s);
+        while let Some(u) = queue.pop_front() {
+            check();
+            for v in outs(u).into_iter().flatten() {
+                if exists(v) && visited.insert(v) {
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+    let number_of_unreachable_vertices = alive.iter().filter(|u| !visited.contains(u)).count();
+
+    GraphStats {
+        number_of_vertices,
+        number_of_deleted_vertices,
+        number_of_dangling_pointers,
+        number_of_unreachable_vertices: number_of_unreachable_vertices as u64,
+        out_degrees,
+    }
+}
@@ -16,6 +16,7 @@ mod build;
 mod bulkdelete;
 mod candidates;
 mod insert;
+mod inspect;
 mod maintain;
 mod prewarm;
 mod prune;
@@ -23,6 +24,7 @@ mod results;
 mod search;
 mod tuples;
 mod vectors;
+mod verify;
 mod visited;
 
 pub mod operator;
@@ -31,9 +33,11 @@ pub mod types;
 pub use build::build;
 pub use bulkdelete::bulkdelete;
 pub use insert::insert;
-pub use maintain::maintain;
+pub use inspect::{GraphStats, Info, graph_stats, info};
+pub use maintain::{Maintain, maintain};
 pub use prewarm::prewarm;
 pub use search::search;
+pub use verify::{Problem, verify};
 
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
@@ -23,7 +23,14 @@ use index_accessor::DefaultWithDimension;
 use std::cmp::Reverse;
 use vector::VectorOwned;
 
-pub fn maintain<R: RelationRead + RelationWrite, O: Operator>(index: &R, check: impl Fn())
+pub struct Maintain {
+    pub number_of_tuples: u64,
+}
+
+pub fn maintain<R: RelationRead + RelationWrite, O: Operator>(
+    index: &R,
+    check: impl Fn(),
+) -> Maintain
 where
     R::Page: Page<Opaque = Opaque>,
 {
@@ -37,7 +44,9 @@ where
     let link = meta_guard.get_opaque().link;
     drop(meta_guard);
     let Some(s) = start.into_inner() else {
-        return;
+        return Maintain {
+            number_of_tuples: 0,
+        };
     };
     // do it's best to remove broken edges
     {
@@ -132,7 +141,7 @@ where
                         m,
                         &alpha,
                         |(_, u)| *u,
-                        O::DISTANCE == DistanceKind::L2S,
+                        matches!(O::DISTANCE, DistanceKind::L2S | DistanceKind::Hamming),
                     );
                     if update::<R, O>(
                         (index, pointers_u.as_slice()),
@@ -148,6 +157,7 @@ where
             current = next;
         }
     }
+    let mut number_of_tuples = 0_u64;
     // remove vertices and vectors
     {
         let mut current = link;
@@ -162,6 +172,7 @@ where
                     if p.is_none() && (current, i) != s {
                         vertex_guard.free(i);
                     } else {
+                        number_of_tuples += p.is_some() as u64;
                         let iter = tuple.pointers().iter().map(|pointer| pointer.into_inner());
                         reachable_set.extend(iter);
                     }
@@ -186,4 +197,5 @@ where
             current = vertex_guard.get_opaque().next;
         }
     }
+    Maintain { number_of_tuples }
 }
@@ -15,13 +15,14 @@
 use crate::types::DistanceKind;
 use distance::Distance;
 use index_accessor::{
-    Accessor1, Accessor2, ByteDistanceAccessor, DefaultWithDimension, DistanceAccessor, Dot,
-    HalfbyteDistanceAccessor, L2S,
+    Accessor1, Accessor2, BitDistanceAccessor, ByteDistanceAccessor, DefaultWithDimension,
+    DistanceAccessor, Dot, HalfbyteDistanceAccessor, Hamming, Jaccard, L2S,
 };
 use rabitq::bits::Bits;
 use simd::{Floating, f16};
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use vector::bvect::BVectOwned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
 use vector::vect::VectOwned;
@@ -249,6 +250,46 @@ impl Vector for Rabitq4Owned {
     }
 }
 
+impl Vector for BVectOwned {
+    type Metadata = ();
+
+    type Element = u64;
+
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.data(), ())
+    }
+
+    fn split(
+        vector: Self::Borrowed<'_>,
+        m: usize,
+    ) -> (Vec<&[Self::Element]>, (&[Self::Element], Self::Metadata)) {
+        let slice = vector.data();
+        let tailing = (size_of::<crate::tuples::OptionNeighbour>() * m)
+            .next_multiple_of(crate::tuples::ALIGN);
+        assert!(tailing <= 8000);
+        if slice.len() <= (8000 - tailing) / size_of::<u64>() {
+            return (vec![], (slice, ()));
+        }
+        let (l, r) = slice.split_at(slice.len() - (8000 - tailing) / size_of::<u64>());
+        (
+            l.chunks(8000 / size_of::<u64>()).collect::<Vec<_>>(),
+            (r, ()),
+        )
+    }
+
+    fn pack(dim: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
+        BVectOwned::new(dim, elements)
+    }
+
+    fn code(bits: Bits, vector: Self::Borrowed<'_>) -> rabitq::bits::Code {
+        rabitq::bits::code(bits, &vector.project())
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> rabitq::bits::binary::BinaryLut {
+        rabitq::bits::binary::preprocess(&vector.project())
+    }
+}
+
 pub trait Operator: 'static + Debug + Copy {
     const DISTANCE: DistanceKind;
 
@@ -550,6 +591,73 @@ impl Operator for Op<Rabitq4Owned, Dot> {
     }
 }
 
+impl Operator for Op<BVectOwned, Hamming> {
+    const DISTANCE: DistanceKind = DistanceKind::Hamming;
+
+    type Vector = BVectOwned;
+
+    type DistanceAccessor = BitDistanceAccessor<BVectOwned, Hamming>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (distance,) = rabitq::bits::binary::half_process_l2s(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        Distance::from_f32(distance)
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_hamming(rhs)
+    }
+}
+
+impl Operator for Op<BVectOwned, Jaccard> {
+    const DISTANCE: DistanceKind = DistanceKind::Jaccard;
+
+    type Vector = BVectOwned;
+
+    type DistanceAccessor = BitDistanceAccessor<BVectOwned, Jaccard>;
+
+    fn process(
+        bits: Bits,
+        dim: u32,
+        code: ([f32; 3], &[u64]),
+        lut: &rabitq::bits::binary::BinaryLut,
+    ) -> Distance {
+        use rabitq::bits::CodeMetadata;
+        let sum = rabitq::bits::binary::accumulate(bits, code.1, &lut.1);
+        let (hamming,) = rabitq::bits::binary::half_process_l2s(
+            bits,
+            dim,
+            sum,
+            CodeMetadata::from_array(code.0),
+            lut.0,
+        );
+        let s = code.0[0] + lut.0.dis_u_2;
+        Distance::from_f32(vector::bvect::jaccard_from_hamming(s, hamming))
+    }
+
+    fn distance(
+        lhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+        rhs: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> Distance {
+        lhs.operator_jaccard(rhs)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CloneAccessor<V: Vector>(u32, Vec<V::Element>);
 
@@ -39,6 +39,7 @@ pub fn search<'b, R: RelationRead, O: Operator>(
     bump: &'b impl Bump,
     mut prefetch_vertices: impl PrefetcherSequenceFamily<'b, R> + 'b,
     prefetch_vectors: impl PrefetcherSequenceFamily<'b, R> + 'b,
+    mut predicate: impl FnMut(NonZero<u64>) -> bool + 'b,
 ) -> Box<dyn Iterator<Item = (Distance, NonZero<u64>)> + 'b>
 where
     R::Page: Page<Opaque = Opaque>,
@@ -124,8 +125,11 @@ where
     });
     let mut results = Results::new(ef as _);
     let search = std::iter::from_fn(move || {
-        for element @ (Reverse(dis_c), _) in iter.by_ref() {
-            results.push(element);
+        for element @ (Reverse(dis_c), AlwaysEqual(payload_c)) in iter.by_ref() {
+            // vertices that do not match are still expanded, but not counted
+            if payload_c.is_none_or(&mut predicate) {
+                results.push(element);
+            }
             if results
                 .peek_ef_th()
                 .map(|dis_e| dis_e < dis_c)
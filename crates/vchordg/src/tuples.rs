@@ -23,6 +23,12 @@ pub type Tag = u64;
 const MAGIC: Tag = Tag::from_ne_bytes(*b"vchordg\0");
 const VERSION: u64 = 1001;
 
+#[inline(always)]
+fn try_tag(source: &[u8]) -> Option<Tag> {
+    let bytes = source.get(..size_of::<Tag>())?;
+    Some(Tag::from_ne_bytes(bytes.try_into().ok()?))
+}
+
 #[inline(always)]
 fn tag(source: &[u8]) -> Tag {
     assert!(source.len() >= size_of::<Tag>());
@@ -46,6 +52,11 @@ pub trait WithWriter: Tuple {
     fn deserialize_mut(source: &mut [u8]) -> Self::Writer<'_>;
 }
 
+/// Checks that `deserialize_ref` would succeed, without panicking.
+pub trait WithValidator: Tuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str>;
+}
+
 #[repr(C, align(8))]
 #[derive(Debug, Clone, FromBytes, IntoBytes, Immutable, KnownLayout)]
 struct MetaTupleHeader {
@@ -139,6 +150,26 @@ impl WithReader for MetaTuple {
     }
 }
 
+impl WithValidator for MetaTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        match try_tag(source) {
+            Some(MAGIC) => {
+                let checker = RefChecker::new(source);
+                if Some(&VERSION) != checker.try_prefix::<u64>(size_of::<Tag>()) {
+                    return Err("bad version number");
+                }
+                let header: &MetaTupleHeader =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[f32] = checker
+                    .try_bytes(header.alpha_s, header.alpha_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad magic number"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct MetaTupleReader<'a> {
     header: &'a MetaTupleHeader,
@@ -276,6 +307,20 @@ impl WithReader for VertexTuple {
     }
 }
 
+impl WithValidator for VertexTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        let header: &VertexTupleHeader = checker.try_prefix(0_u16).ok_or("bad bytes")?;
+        let _: &[u64] = checker
+            .try_bytes(header.elements_s, header.elements_e)
+            .ok_or("bad bytes")?;
+        let _: &[Pointer] = checker
+            .try_bytes(header.pointers_s, header.pointers_e)
+            .ok_or("bad bytes")?;
+        Ok(())
+    }
+}
+
 impl WithWriter for VertexTuple {
     type Writer<'a> = VertexTupleWriter<'a>;
 
@@ -514,6 +559,35 @@ impl<V: Vector> WithWriter for VectorTuple<V> {
     }
 }
 
+impl<V: Vector> WithValidator for VectorTuple<V> {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        match try_tag(source) {
+            Some(0) => {
+                let header: &VectorTupleHeader0 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[V::Element] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                let _: &V::Metadata = checker.try_prefix(header.metadata_s).ok_or("bad bytes")?;
+                let _: &[OptionNeighbour] = checker
+                    .try_bytes(header.neighbours_s, header.neighbours_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            Some(1) => {
+                let header: &VectorTupleHeader1 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[V::Element] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad bytes"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum VectorTupleReader<'a, V: Vector> {
     _0(VectorTupleReader0<'a, V>),
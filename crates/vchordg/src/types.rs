@@ -15,6 +15,7 @@
 use serde::{Deserialize, Serialize};
 use simd::f16;
 use validator::{Validate, ValidationError};
+use vector::bvect::{BVectBorrowed, BVectOwned};
 use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
 use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
 use vector::vect::{VectBorrowed, VectOwned};
@@ -87,6 +88,7 @@ pub enum OwnedVector {
     Vecf16(VectOwned<f16>),
     Rabitq8(Rabitq8Owned),
     Rabitq4(Rabitq4Owned),
+    Bit(BVectOwned),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -95,6 +97,7 @@ pub enum BorrowedVector<'a> {
     Vecf16(VectBorrowed<'a, f16>),
     Rabitq8(Rabitq8Borrowed<'a>),
     Rabitq4(Rabitq4Borrowed<'a>),
+    Bit(BVectBorrowed<'a>),
 }
 
 #[repr(u8)]
@@ -102,6 +105,8 @@ pub enum BorrowedVector<'a> {
 pub enum DistanceKind {
     L2S,
     Dot,
+    Hamming,
+    Jaccard,
 }
 
 #[repr(u8)]
@@ -111,6 +116,7 @@ pub enum VectorKind {
     Vecf16,
     Rabitq8,
     Rabitq4,
+    Bit,
 }
 
 #[derive(Debug, Clone, Validate)]
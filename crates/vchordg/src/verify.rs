@@ -0,0 +1,281 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::operator::Operator;
+use crate::tuples::*;
+use index::relation::{Page, RelationRead};
+use std::collections::{HashMap, HashSet};
+use std::num::NonZero;
+
+pub struct Problem {
+    pub page: Option<u32>,
+    pub message: String,
+}
+
+struct Verifier<'r, R, C> {
+    index: &'r R,
+    number_of_pages: u32,
+    check: C,
+    owners: HashMap<u32, &'static str>,
+    problems: Vec<Problem>,
+}
+
+impl<R: RelationRead, C: Fn()> Verifier<'_, R, C>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    fn report(&mut self, page: Option<u32>, message: String) {
+        self.problems.push(Problem { page, message });
+    }
+
+    /// Follows the `next` chain starting at `first`, which is referenced by the
+    /// page `from`, and returns the pages that belong to the chain. Pages out of
+    /// range are appended by concurrent insertions, so the chain stops there.
+    fn walk(&mut self, kind: &'static str, first: u32, from: u32) -> Vec<u32> {
+        let mut pages = Vec::new();
+        let mut visited = HashSet::new();
+        let mut previous = from;
+        let mut current = first;
+        while current != u32::MAX && current < self.number_of_pages {
+            (self.check)();
+            if !visited.insert(current) {
+                self.report(
+                    Some(previous),
+                    format!("the {kind} starting at page {first} does not terminate"),
+                );
+                break;
+            }
+            if let Some(owner) = self.owners.get(&current).copied() {
+                self.report(
+                    Some(current),
+                    format!("the page is reachable from both the {owner} and the {kind}"),
+                );
+                break;
+            }
+            pages.push(current);
+            previous = current;
+            current = self.index.read(current).get_opaque().next;
+        }
+        for &page in pages.iter() {
+            self.owners.insert(page, kind);
+        }
+        pages
+    }
+}
+
+/// Checks the structure of the index without panicking on corrupted data. The
+/// payloads of all live vertices are passed to `callback`.
+///
+/// Insertions may go on while the index is checked, so pages appended after
+/// the first `number_of_pages` pages are not checked.
+pub fn verify<R: RelationRead, O: Operator>(
+    index: &R,
+    number_of_pages: u32,
+    check: impl Fn(),
+    mut callback: impl FnMut(NonZero<u64>),
+) -> Vec<Problem>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut verifier = Verifier {
+        index,
+        number_of_pages,
+        check,
+        owners: HashMap::new(),
+        problems: Vec::new(),
+    };
+    if number_of_pages == 0 {
+        verifier.report(None, "the meta page does not exist".to_string());
+        return verifier.problems;
+    }
+    verifier.owners.insert(0, "meta page");
+
+    let meta_guard = index.read(0);
+    let Some(meta_bytes) = meta_guard.get(1) else {
+        verifier.report(Some(0), "the meta tuple does not exist".to_string());
+        return verifier.problems;
+    };
+    if let Err(e) = MetaTuple::validate(meta_bytes) {
+        verifier.report(Some(0), format!("the meta tuple is corrupted: {e}"));
+        return verifier.problems;
+    }
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let start = meta_tuple.start().into_inner();
+    let link = meta_guard.get_opaque().link;
+    drop(meta_guard);
+
+    // tapes are only extended at their last pages, which are remembered along
+    // with whether the first page of the tape is linked from them
+    let mut tails = Vec::new();
+    let mut vertex_pages = Vec::new();
+    let mut vector_pages = Vec::new();
+    let pages = verifier.walk("vertex tape", link, 0);
+    tails.push((
+        "vertex tape",
+        pages.last().map_or((0, true), |&x| (x, false)),
+    ));
+    for page in pages {
+        let vector_link = index.read(page).get_opaque().link;
+        let pages = verifier.walk("vector tape", vector_link, page);
+        tails.push((
+            "vector tape",
+            pages.last().map_or((page, true), |&x| (x, false)),
+        ));
+        vertex_pages.push(page);
+        vector_pages.extend(pages);
+    }
+
+    for page in vector_pages {
+        (verifier.check)();
+        let vector_guard = index.read(page);
+        for i in 1..=vector_guard.len() {
+            // vectors of deleted vertices are freed by maintenance
+            let Some(vector_bytes) = vector_guard.get(i) else {
+                continue;
+            };
+            if let Err(e) = VectorTuple::<O::Vector>::validate(vector_bytes) {
+                verifier.report(
+                    Some(page),
+                    format!("the vector tuple {i} is corrupted: {e}"),
+                );
+            }
+        }
+    }
+
+    let mut vertices = HashSet::new();
+    let mut pointers = Vec::new();
+    for page in vertex_pages {
+        (verifier.check)();
+        let vertex_guard = index.read(page);
+        for i in 1..=vertex_guard.len() {
+            // deleted vertices are freed by maintenance
+            let Some(vertex_bytes) = vertex_guard.get(i) else {
+                continue;
+            };
+            if let Err(e) = VertexTuple::validate(vertex_bytes) {
+                verifier.report(
+                    Some(page),
+                    format!("the vertex tuple {i} is corrupted: {e}"),
+                );
+                continue;
+            }
+            let vertex_tuple = VertexTuple::deserialize_ref(vertex_bytes);
+            let payload = vertex_tuple.payload();
+            if let Some(payload) = payload {
+                callback(payload);
+            }
+            if vertex_tuple.pointers().is_empty() {
+                verifier.report(
+                    Some(page),
+                    format!("the vertex tuple {i} references no vector tuples"),
+                );
+            }
+            pointers.push(((page, i), payload, vertex_tuple.pointers().to_vec()));
+            vertices.insert((page, i));
+        }
+    }
+
+    // a page is linked to a tape before any tuple is written to it, so pages
+    // holding the tuples seen above are reachable from the tails
+    let unreachable = (0..number_of_pages)
+        .filter(|page| !verifier.owners.contains_key(page))
+        .filter(|&page| {
+            (verifier.check)();
+            !index.read(page).is_empty()
+        })
+        .collect::<Vec<_>>();
+    for (kind, (tail, linked)) in tails {
+        let guard = index.read(tail);
+        let first = if linked {
+            guard.get_opaque().link
+        } else {
+            guard.get_opaque().next
+        };
+        drop(guard);
+        for page in verifier.walk(kind, first, tail) {
+            if kind == "vertex tape" {
+                let vector_link = index.read(page).get_opaque().link;
+                verifier.walk("vector tape", vector_link, page);
+            }
+        }
+    }
+    for page in unreachable {
+        if !verifier.owners.contains_key(&page) {
+            verifier.report(Some(page), "the page is not reachable".to_string());
+        }
+    }
+
+    // a vector is stored as vector tuples with indexes in order, followed by the
+    // vector tuple holding its metadata and neighbours
+    for ((page, i), payload, pointers) in pointers {
+        let m = pointers.len().saturating_sub(1);
+        for (j, pointer) in pointers.into_iter().enumerate() {
+            (verifier.check)();
+            let (x, y) = pointer.into_inner();
+            if x >= number_of_pages {
+                continue;
+            }
+            if verifier.owners.get(&x) != Some(&"vector tape") {
+                verifier.report(
+                    Some(page),
+                    format!("the vertex tuple {i} references page {x}, which is not a vector page"),
+                );
+                continue;
+            }
+            let vector_guard = index.read(x);
+            let Some(vector_bytes) = vector_guard.get(y) else {
+                verifier.report(
+                    Some(page),
+                    format!(
+                        "the vertex tuple {i} references the vector tuple ({x}, {y}), which does not exist"
+                    ),
+                );
+                continue;
+            };
+            if VectorTuple::<O::Vector>::validate(vector_bytes).is_err() {
+                continue;
+            }
+            let matched = match VectorTuple::<O::Vector>::deserialize_ref(vector_bytes) {
+                VectorTupleReader::_0(vector_tuple) => {
+                    j == m && (payload.is_none() || vector_tuple.payload() == payload)
+                }
+                VectorTupleReader::_1(vector_tuple) => {
+                    j < m
+                        && vector_tuple.index() as usize == j
+                        && (payload.is_none() || vector_tuple.payload() == payload)
+                }
+            };
+            if !matched {
+                verifier.report(
+                    Some(page),
+                    format!(
+                        "the vertex tuple {i} references the vector tuple ({x}, {y}), which belongs to another vector"
+                    ),
+                );
+            }
+        }
+    }
+
+    if let Some((x, y)) = start
+        && !vertices.contains(&(x, y))
+    {
+        verifier.report(
+            Some(0),
+            format!("the start vertex ({x}, {y}) does not exist"),
+        );
+    }
+
+    verifier.problems
+}
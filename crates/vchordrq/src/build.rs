@@ -39,6 +39,53 @@ pub fn build<R: RelationWrite, O: Operator>(
     let vectors = (0..vchordrq_options.degree_of_parallelism)
         .map(|_| TapeWriter::<_, VectorTuple<O::Vector>>::create(index, true).first())
         .collect::<Vec<_>>();
+    let (pointer_of_centroids, pointer_of_firsts) =
+        write_tree::<_, O>(index, dim, is_residual, &mut centroids, &structures);
+    meta.push(MetaTuple {
+        dim,
+        height_of_root: structures.len() as u32,
+        is_residual,
+        rerank_in_heap: vchordrq_options.rerank_in_table,
+        centroids_first: centroids.first(),
+        vectors_first: vectors,
+        centroid_prefetch: pointer_of_centroids
+            .last()
+            .expect("internal error: empty structure")[0]
+            .0
+            .clone(),
+        centroid_head: pointer_of_centroids
+            .last()
+            .expect("internal error: empty structure")[0]
+            .1,
+        centroid_norm: norm::<O::Vector>(
+            structures
+                .last()
+                .expect("internal error: empty structure")
+                .centroids[0]
+                .as_borrowed(),
+        ),
+        first: pointer_of_firsts
+            .last()
+            .expect("internal error: empty structure")[0],
+        freepages_first: freepages.first(),
+        cells: structures.iter().map(|s| s.len() as _).collect(),
+    });
+}
+
+/// Writes the centroids into `centroids` and creates the lists and h1 tapes
+/// of the tree. Returns the positions of centroids and the first pages of
+/// tapes, level by level.
+#[allow(clippy::type_complexity)]
+pub(crate) fn write_tree<R: RelationWrite, O: Operator>(
+    index: &R,
+    dim: u32,
+    is_residual: bool,
+    centroids: &mut TapeWriter<'_, R, CentroidTuple<O::Vector>>,
+    structures: &[Structure<O::Vector>],
+) -> (Vec<Vec<(Vec<u32>, u16)>>, Vec<Vec<u32>>)
+where
+    R::Page: Page<Opaque = Opaque>,
+{
     let mut pointer_of_centroids = Vec::<Vec<(Vec<u32>, u16)>>::new();
     for i in 0..structures.len() {
         let mut level = Vec::new();
@@ -114,35 +161,7 @@ pub fn build<R: RelationWrite, O: Operator>(
         }
         pointer_of_firsts.push(level);
     }
-    meta.push(MetaTuple {
-        dim,
-        height_of_root: structures.len() as u32,
-        is_residual,
-        rerank_in_heap: vchordrq_options.rerank_in_table,
-        centroids_first: centroids.first(),
-        vectors_first: vectors,
-        centroid_prefetch: pointer_of_centroids
-            .last()
-            .expect("internal error: empty structure")[0]
-            .0
-            .clone(),
-        centroid_head: pointer_of_centroids
-            .last()
-            .expect("internal error: empty structure")[0]
-            .1,
-        centroid_norm: norm::<O::Vector>(
-            structures
-                .last()
-                .expect("internal error: empty structure")
-                .centroids[0]
-                .as_borrowed(),
-        ),
-        first: pointer_of_firsts
-            .last()
-            .expect("internal error: empty structure")[0],
-        freepages_first: freepages.first(),
-        cells: structures.iter().map(|s| s.len() as _).collect(),
-    });
+    (pointer_of_centroids, pointer_of_firsts)
 }
 
 fn norm<V: Vector>(vector: V::Borrowed<'_>) -> f32 {
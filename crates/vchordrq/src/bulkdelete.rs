@@ -25,7 +25,8 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
     index: &R,
     check: impl Fn(),
     callback: impl Fn(NonZero<u64>) -> bool,
-) where
+) -> u64
+where
     R::Page: Page<Opaque = Opaque>,
 {
     let meta_guard = index.read(0);
@@ -52,6 +53,7 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
     for _ in (1..height_of_root).rev() {
         state = step(state);
     }
+    let mut removed = 0_u64;
     for first in state {
         let jump_guard = index.read(first);
         let jump_bytes = jump_guard.get(1).expect("data corruption");
@@ -88,6 +90,7 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
                             for p in tuple.payload().iter_mut() {
                                 if Some(true) == p.map(&callback) {
                                     *p = None;
+                                    removed += 1;
                                 }
                             }
                         }
@@ -121,6 +124,7 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
                         let p = tuple.payload();
                         if Some(true) == p.map(&callback) {
                             *p = None;
+                            removed += 1;
                         }
                     }
                     current = write.get_opaque().next;
@@ -130,6 +134,7 @@ pub fn bulkdelete<R: RelationRead + RelationWrite, O: Operator>(
             }
         }
     }
+    removed
 }
 
 pub fn bulkdelete_vectors<R: RelationRead + RelationWrite, O: Operator>(
@@ -23,7 +23,7 @@ use index::bump::Bump;
 use index::fetch::BorrowedIter;
 use index::prefetcher::{Prefetcher, PrefetcherHeapFamily};
 use index::relation::{Page, RelationRead, RelationWrite};
-use index_accessor::{DefaultWithDimension, FunctionalAccessor, LAccess};
+use index_accessor::FunctionalAccessor;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::num::NonZero;
@@ -93,14 +93,8 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
         let prefetch =
             BorrowedIter::from_slice(meta_tuple.centroid_prefetch(), |x| bump.alloc_slice(x));
         let head = meta_tuple.centroid_head();
-        let distance = centroids::read::<R, O, _>(
-            prefetch.map(|id| index.read(id)),
-            head,
-            LAccess::new(
-                O::Vector::unpack(vector),
-                O::DistanceAccessor::default_with_dimension(dim),
-            ),
-        );
+        let distance =
+            centroids::read::<R, O, _>(prefetch.map(|id| index.read(id)), head, O::access(vector));
         let norm = meta_tuple.centroid_norm();
         let first = meta_tuple.first();
         (Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))
@@ -142,14 +136,7 @@ pub fn insert<'b, R: RelationRead + RelationWrite, O: Operator>(
             while let Some(((Reverse(_), AlwaysEqual(&mut (first, norm, head, ..))), prefetch)) =
                 heap.next_if(|(d, _)| Some(*d) > cache.peek().map(|(d, ..)| *d))
             {
-                let distance = centroids::read::<R, O, _>(
-                    prefetch,
-                    head,
-                    LAccess::new(
-                        O::Vector::unpack(vector),
-                        O::DistanceAccessor::default_with_dimension(dim),
-                    ),
-                );
+                let distance = centroids::read::<R, O, _>(prefetch, head, O::access(vector));
                 cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
             }
             cache.pop()
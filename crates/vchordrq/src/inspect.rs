@@ -0,0 +1,218 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::closure_lifetime_binder::{id_0, id_1, id_2};
+use crate::tape::by_next;
+use crate::tuples::*;
+use crate::{Opaque, tape};
+use index::relation::{Page, RelationRead};
+use index_accessor::FunctionalAccessor;
+use std::num::NonZero;
+
+pub struct Info {
+    pub version: u64,
+    pub dim: u32,
+    pub height_of_root: u32,
+    pub cells: Vec<u32>,
+    pub is_residual: bool,
+    pub rerank_in_heap: bool,
+    pub number_of_centroid_pages: usize,
+    pub number_of_vector_pages: usize,
+    pub number_of_h1_pages: usize,
+    pub number_of_jump_pages: usize,
+    pub number_of_directory_pages: usize,
+    pub number_of_frozen_pages: usize,
+    pub number_of_appendable_pages: usize,
+    pub number_of_free_pages: usize,
+}
+
+pub struct List {
+    pub id: u32,
+    pub parent: Option<u32>,
+    pub number_of_live_tuples: u64,
+    pub number_of_dead_tuples: u64,
+    pub number_of_directory_pages: usize,
+    pub number_of_frozen_pages: usize,
+    pub number_of_appendable_pages: usize,
+}
+
+pub fn info<R: RelationRead>(index: &R, check: impl Fn()) -> Info
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let version = meta_tuple.version();
+    let dim = meta_tuple.dim();
+    let height_of_root = meta_tuple.height_of_root();
+    let cells = meta_tuple.cells().to_vec();
+    let is_residual = meta_tuple.is_residual();
+    let rerank_in_heap = meta_tuple.rerank_in_heap();
+    let centroids_first = meta_tuple.centroids_first();
+    let vectors_first = meta_tuple.vectors_first().to_vec();
+    let freepages_first = meta_tuple.freepages_first();
+    let first = meta_tuple.first();
+    drop(meta_guard);
+
+    let number_of_centroid_pages = by_next(index, centroids_first).inspect(|_| check()).count();
+    let number_of_vector_pages = vectors_first
+        .iter()
+        .map(|&first| by_next(index, first).inspect(|_| check()).count())
+        .sum();
+
+    let (number_of_h1_pages, lists) = traverse(index, first, height_of_root, &check);
+    let mut number_of_jump_pages = 0_usize;
+    let mut number_of_directory_pages = 0_usize;
+    let mut number_of_frozen_pages = 0_usize;
+    let mut number_of_appendable_pages = 0_usize;
+    for (first, _) in lists {
+        let jump_guard = index.read(first);
+        let jump_bytes = jump_guard.get(1).expect("data corruption");
+        let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+        let directory_first = jump_tuple.directory_first();
+        let frozen_first = jump_tuple.frozen_first();
+        let appendable_first = jump_tuple.appendable_first();
+        drop(jump_guard);
+        number_of_jump_pages += by_next(index, first).inspect(|_| check()).count();
+        number_of_directory_pages += by_next(index, directory_first).inspect(|_| check()).count();
+        number_of_frozen_pages += by_next(index, frozen_first).inspect(|_| check()).count();
+        number_of_appendable_pages += by_next(index, appendable_first)
+            .inspect(|_| check())
+            .count();
+    }
+
+    let mut number_of_free_pages = 0_usize;
+    let mut current = {
+        let freepages_guard = index.read(freepages_first);
+        let freepages_bytes = freepages_guard.get(1).expect("data corruption");
+        FreepagesTuple::deserialize_ref(freepages_bytes).first()
+    };
+    while current != u32::MAX {
+        check();
+        number_of_free_pages += 1;
+        current = index.read(current).get_opaque().next;
+    }
+
+    Info {
+        version,
+        dim,
+        height_of_root,
+        cells,
+        is_residual,
+        rerank_in_heap,
+        number_of_centroid_pages,
+        number_of_vector_pages,
+        number_of_h1_pages,
+        number_of_jump_pages,
+        number_of_directory_pages,
+        number_of_frozen_pages,
+        number_of_appendable_pages,
+        number_of_free_pages,
+    }
+}
+
+/// Returns the lists of the index. A tuple is counted as live if `is_live`
+/// returns true for its payload.
+pub fn lists<R: RelationRead>(
+    index: &R,
+    mut is_live: impl FnMut(NonZero<u64>) -> bool,
+    check: impl Fn(),
+) -> Vec<List>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let height_of_root = meta_tuple.height_of_root();
+    let first = meta_tuple.first();
+    drop(meta_guard);
+
+    let (_, lists) = traverse(index, first, height_of_root, &check);
+    let mut results = Vec::with_capacity(lists.len());
+    for (id, (first, parent)) in lists.into_iter().enumerate() {
+        let jump_guard = index.read(first);
+        let jump_bytes = jump_guard.get(1).expect("data corruption");
+        let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+        let directory_first = jump_tuple.directory_first();
+        let frozen_first = jump_tuple.frozen_first();
+        let appendable_first = jump_tuple.appendable_first();
+        drop(jump_guard);
+        let mut number_of_live_tuples = 0_u64;
+        let mut number_of_dead_tuples = 0_u64;
+        let mut count = |payload| {
+            if is_live(payload) {
+                number_of_live_tuples += 1;
+            } else {
+                number_of_dead_tuples += 1;
+            }
+        };
+        let mut number_of_frozen_pages = 0_usize;
+        let mut number_of_appendable_pages = 0_usize;
+        tape::read_frozen_tape::<R, _, _>(
+            by_next(index, frozen_first)
+                .inspect(|_| check())
+                .inspect(|_| number_of_frozen_pages += 1),
+            || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+            id_2(|_, _, payload, _| count(payload)),
+        );
+        tape::read_appendable_tape::<R, _>(
+            by_next(index, appendable_first)
+                .inspect(|_| check())
+                .inspect(|_| number_of_appendable_pages += 1),
+            |_, _, _| (),
+            id_2(|_, _, payload, _| count(payload)),
+        );
+        results.push(List {
+            id: id as u32,
+            parent,
+            number_of_live_tuples,
+            number_of_dead_tuples,
+            number_of_directory_pages: by_next(index, directory_first).inspect(|_| check()).count(),
+            number_of_frozen_pages,
+            number_of_appendable_pages,
+        });
+    }
+    results
+}
+
+/// Returns the number of pages of h1 tapes, and the first pages of jump tapes
+/// with the ids of their parents.
+fn traverse<R: RelationRead>(
+    index: &R,
+    first: u32,
+    height_of_root: u32,
+    check: &impl Fn(),
+) -> (usize, Vec<(u32, Option<u32>)>)
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut counter = 0_usize;
+    let mut state = vec![(first, None)];
+    for _ in (1..height_of_root).rev() {
+        let mut results = Vec::new();
+        for (parent, (first, _)) in state.into_iter().enumerate() {
+            tape::read_h1_tape::<R, _, _>(
+                by_next(index, first)
+                    .inspect(|_| check())
+                    .inspect(|_| counter += 1),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), _, _, first, _| results.push((first, Some(parent as u32))),
+            );
+        }
+        state = results;
+    }
+    (counter, state)
+}
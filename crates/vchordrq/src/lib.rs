@@ -22,15 +22,18 @@ mod cost;
 mod fast_heap;
 mod freepages;
 mod insert;
+mod inspect;
 mod linked_vec;
 mod maintain;
 mod prewarm;
+mod recluster;
 mod rerank;
 mod search;
 mod tape;
 mod tape_writer;
 mod tuples;
 mod vectors;
+mod verify;
 
 pub mod operator;
 pub mod types;
@@ -42,10 +45,13 @@ pub use consume::consume;
 pub use cost::cost;
 pub use fast_heap::FastHeap;
 pub use insert::{InsertChooser, insert, insert_vector};
-pub use maintain::{MaintainChooser, maintain};
+pub use inspect::{Info, List, info, lists};
+pub use maintain::{Maintain, MaintainChooser, maintain};
 pub use prewarm::prewarm;
-pub use rerank::{how, rerank_heap, rerank_index};
-pub use search::{default_search, maxsim_search};
+pub use recluster::{Recluster, recluster};
+pub use rerank::{how, rerank_heap, rerank_heap_multi, rerank_index, rerank_index_multi};
+pub use search::{AutoProbes, Probes, default_search, maxsim_search};
+pub use verify::{Problem, verify};
 
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 
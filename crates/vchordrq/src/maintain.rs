@@ -13,17 +13,20 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use crate::closure_lifetime_binder::{id_0, id_1, id_2, id_3};
-use crate::operator::{Operator, Vector};
-use crate::tape_writer::{DirectoryTapeWriter, FrozenTapeWriter};
+use crate::operator::{CloneAccessor, Operator, Vector, distance};
+use crate::tape::TapeWriter;
+use crate::tape_writer::{DirectoryTapeWriter, FrozenTapeWriter, H1TapeWriter};
 use crate::tuples::*;
-use crate::{Branch, Opaque, freepages, tape};
+use crate::{Branch, Opaque, centroids, freepages, tape, vectors};
 use index::prefetcher::PrefetcherSequenceFamily;
 use index::relation::{
     Page, PageGuard, Relation, RelationRead, RelationReadTypes, RelationWrite, RelationWriteTypes,
 };
-use index_accessor::FunctionalAccessor;
+use index_accessor::{DefaultWithDimension, FunctionalAccessor};
 use rabitq::packing::unpack;
 use std::cell::RefCell;
+use std::num::NonZero;
+use vector::VectorOwned;
 
 pub trait MaintainChooser {
     fn choose(&mut self, i: usize) -> bool;
@@ -33,12 +36,25 @@ pub struct Maintain {
     pub number_of_formerly_allocated_pages: usize,
     pub number_of_freshly_allocated_pages: usize,
     pub number_of_freed_pages: usize,
+    pub number_of_tuples: u64,
+    pub number_of_split_lists: usize,
+    /// Whether lists could be split. Lists of an index with a single level or
+    /// with vectors stored in the table are never split.
+    pub splittable: bool,
 }
 
+/// Rewrites the chosen lists. If `split_factor` is given, a list holding more
+/// than `split_factor` times the average number of tuples is split in two.
+/// A list is split only if `lock_split` succeeds, and `unlock_split` is
+/// called once the new list is attached, since parallel scans claim lists by
+/// their positions.
 pub fn maintain<'b, R: RelationRead + RelationWrite, O: Operator>(
     index: &'b R,
     mut prefetch_h0_tuples: impl PrefetcherSequenceFamily<'b, R>,
     chooser: &mut impl MaintainChooser,
+    split_factor: Option<f64>,
+    lock_split: impl Fn() -> bool,
+    unlock_split: impl Fn(),
     check: impl Fn(),
 ) -> Maintain
 where
@@ -49,29 +65,58 @@ where
     let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
     let dim = meta_tuple.dim();
     let height_of_root = meta_tuple.height_of_root();
+    let is_residual = meta_tuple.is_residual();
+    let rerank_in_heap = meta_tuple.rerank_in_heap();
+    let centroids_first = meta_tuple.centroids_first();
     let freepages_first = meta_tuple.freepages_first();
 
-    type State = Vec<u32>;
-    let mut state: State = vec![meta_tuple.first()];
+    type State = Vec<(u32, Vec<u32>, u16)>;
+    let mut state: State = vec![(
+        meta_tuple.first(),
+        meta_tuple.centroid_prefetch().to_vec(),
+        meta_tuple.centroid_head(),
+    )];
 
     drop(meta_guard);
 
-    let step = |state: State| {
+    let step = |state: &State| {
         let mut results = Vec::new();
-        for first in state {
+        let mut parents = Vec::new();
+        for (i, &(first, ..)) in state.iter().enumerate() {
             tape::read_h1_tape::<R, _, _>(
                 tape::by_next(index, first).inspect(|_| check()),
                 || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
-                |(), _, _, first, _| results.push(first),
+                |(), head, _, first, prefetch| {
+                    results.push((first, prefetch.to_vec(), head));
+                    parents.push(i);
+                },
             );
         }
-        results
+        (results, parents)
     };
 
+    let mut upper = State::new();
+    let mut parents = Vec::new();
     for _ in (1..height_of_root).rev() {
-        state = step(state);
+        let (results, indexes) = step(&state);
+        upper = std::mem::replace(&mut state, results);
+        parents = indexes;
     }
 
+    // a split list needs a parent to hold the new centroid and
+    // its vectors stored in the index
+    let splittable = height_of_root > 1 && !rerank_in_heap;
+    let threshold = split_factor.filter(|_| splittable).map(|split_factor| {
+        let mut sum = 0_u64;
+        for &(first, ..) in state.iter() {
+            let jump_guard = index.read(first);
+            let jump_bytes = jump_guard.get(1).expect("data corruption");
+            let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+            sum += jump_tuple.tuples();
+        }
+        split_factor * sum as f64 / state.len() as f64
+    });
+
     struct Buffers {
         pages: Vec<u32>,
         number_of_formerly_allocated_pages: usize,
@@ -84,12 +129,19 @@ where
         number_of_freshly_allocated_pages: 0,
     });
 
-    for (idx, first) in state.into_iter().enumerate() {
+    let mut number_of_tuples = 0_u64;
+    let mut number_of_split_lists = 0_usize;
+
+    for (idx, (first, centroid_prefetch, centroid_head)) in state.iter().enumerate() {
         if !chooser.choose(idx) {
+            let jump_guard = index.read(*first);
+            let jump_bytes = jump_guard.get(1).expect("data corruption");
+            let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+            number_of_tuples += jump_tuple.tuples();
             continue;
         }
 
-        let mut jump_guard = index.write(first, false);
+        let mut jump_guard = index.write(*first, false);
         let jump_bytes = jump_guard.get_mut(1).expect("data corruption");
         let mut jump_tuple = JumpTuple::deserialize_mut(jump_bytes);
 
@@ -118,15 +170,13 @@ where
             })
         });
 
-        let mut tape = FrozenTapeWriter::create(&hooked_index, O::Vector::count(dim) as _, false);
-
         let mut trace_directory = Vec::new();
         let mut trace_forzen = Vec::new();
         let mut trace_appendable = Vec::new();
 
-        let mut tuples = 0_u64;
+        let mut branches = Vec::new();
         let mut callback = id_2(|(code, delta): (_, _), head, payload, prefetch: &[_]| {
-            tape.push(Branch {
+            branches.push(Branch {
                 code,
                 delta,
                 prefetch: prefetch.to_vec(),
@@ -134,7 +184,6 @@ where
                 norm: 0.0,
                 extra: payload,
             });
-            tuples += 1;
         });
         let directory = tape::read_directory_tape::<R>(
             tape::by_next(index, *jump_tuple.directory_first())
@@ -200,41 +249,55 @@ where
             &mut callback,
         );
 
-        let (frozen_tape, branches) = tape.into_inner();
-
-        let mut appendable_tape = tape::TapeWriter::create(&hooked_index, false);
-
-        for branch in branches {
-            appendable_tape.push(AppendableTuple {
-                metadata: [
-                    branch.code.0.dis_u_2,
-                    branch.code.0.factor_cnt,
-                    branch.code.0.factor_ip,
-                    branch.code.0.factor_err,
-                ],
-                elements: rabitq::bit::binary::pack_code(&branch.code.1),
-                delta: branch.delta,
-                prefetch: branch.prefetch,
-                head: branch.head,
-                payload: Some(branch.extra),
-            });
-        }
-
-        let frozen_first = { frozen_tape }.first();
-
-        let directory = tape::by_next(index, frozen_first)
-            .inspect(|_| check())
-            .map(|guard| guard.id())
-            .collect::<Vec<_>>();
+        let locked =
+            threshold.is_some_and(|threshold| branches.len() as f64 > threshold) && lock_split();
+        let split = locked
+            .then(|| {
+                let centroid = centroids::read::<R, O, _>(
+                    centroid_prefetch.iter().map(|&id| index.read(id)),
+                    *centroid_head,
+                    CloneAccessor::default_with_dimension(dim),
+                );
+                split::<R, O>(index, dim, is_residual, centroid, &mut branches)
+            })
+            .flatten();
 
-        let mut directory_tape = DirectoryTapeWriter::create(&hooked_index, false);
-        directory_tape.push(directory.as_slice());
-        let directory_tape = directory_tape.into_inner();
+        let tuples = branches.len() as u64;
+        let (directory_first, frozen_first, appendable_first) =
+            write_list::<_, O>(&hooked_index, dim, branches, &check);
 
-        *jump_tuple.directory_first() = { directory_tape }.first();
+        *jump_tuple.directory_first() = directory_first;
         *jump_tuple.frozen_first() = frozen_first;
-        *jump_tuple.appendable_first() = { appendable_tape }.first();
+        *jump_tuple.appendable_first() = appendable_first;
         *jump_tuple.tuples() = tuples;
+        number_of_tuples += tuples;
+
+        if let Some((centroid, branches)) = split {
+            let (parent_first, parent_prefetch, parent_head) = &upper[parents[idx]];
+            let parent = is_residual.then(|| {
+                centroids::read::<R, O, _>(
+                    parent_prefetch.iter().map(|&id| index.read(id)),
+                    *parent_head,
+                    CloneAccessor::default_with_dimension(dim),
+                )
+            });
+            number_of_tuples += branches.len() as u64;
+            attach::<_, O>(
+                &hooked_index,
+                dim,
+                centroids_first,
+                *parent_first,
+                parent,
+                centroid,
+                branches,
+                &check,
+            );
+            number_of_split_lists += 1;
+        }
+
+        if locked {
+            unlock_split();
+        }
 
         drop(jump_guard);
 
@@ -253,7 +316,229 @@ where
         number_of_formerly_allocated_pages: buffers.number_of_formerly_allocated_pages,
         number_of_freshly_allocated_pages: buffers.number_of_freshly_allocated_pages,
         number_of_freed_pages: buffers.pages.len(),
+        number_of_tuples,
+        number_of_split_lists,
+        splittable,
+    }
+}
+
+fn write_list<R: RelationRead + RelationWrite, O: Operator>(
+    index: &R,
+    dim: u32,
+    branches: Vec<Branch<NonZero<u64>>>,
+    check: impl Fn(),
+) -> (u32, u32, u32)
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut tape = FrozenTapeWriter::create(index, O::Vector::count(dim) as _, false);
+    for branch in branches {
+        tape.push(branch);
+    }
+    let (frozen_tape, branches) = tape.into_inner();
+
+    let mut appendable_tape = TapeWriter::create(index, false);
+
+    for branch in branches {
+        appendable_tape.push(AppendableTuple {
+            metadata: [
+                branch.code.0.dis_u_2,
+                branch.code.0.factor_cnt,
+                branch.code.0.factor_ip,
+                branch.code.0.factor_err,
+            ],
+            elements: rabitq::bit::binary::pack_code(&branch.code.1),
+            delta: branch.delta,
+            prefetch: branch.prefetch,
+            head: branch.head,
+            payload: Some(branch.extra),
+        });
+    }
+
+    let frozen_first = { frozen_tape }.first();
+
+    let directory = tape::by_next(index, frozen_first)
+        .inspect(|_| check())
+        .map(|guard| guard.id())
+        .collect::<Vec<_>>();
+
+    let mut directory_tape = DirectoryTapeWriter::create(index, false);
+    directory_tape.push(directory.as_slice());
+    let directory_tape = directory_tape.into_inner();
+
+    (
+        { directory_tape }.first(),
+        frozen_first,
+        { appendable_tape }.first(),
+    )
+}
+
+/// Runs a 2-means on a sample of the members of a list, with the centroid of
+/// the list fixed. Returns the new centroid and the re-encoded members closer
+/// to it, which are removed from `branches`. Only the sample is held in memory,
+/// and the other members are read one by one when they are assigned.
+#[allow(clippy::type_complexity)]
+fn split<R: RelationRead, O: Operator>(
+    index: &R,
+    dim: u32,
+    is_residual: bool,
+    centroid: O::Vector,
+    branches: &mut Vec<Branch<NonZero<u64>>>,
+) -> Option<(O::Vector, Vec<Branch<NonZero<u64>>>)> {
+    const ITERATIONS: usize = 8;
+    const SAMPLES: usize = 4096;
+
+    let read = |branch: &Branch<NonZero<u64>>| {
+        vectors::read::<R, O, _>(
+            branch.prefetch.iter().map(|&id| index.read(id)),
+            branch.head,
+            branch.extra,
+            CloneAccessor::default_with_dimension(dim),
+        )
+    };
+
+    let step = branches.len().div_ceil(SAMPLES).max(1);
+    let samples = branches
+        .iter()
+        .step_by(step)
+        .filter_map(read)
+        .collect::<Vec<_>>();
+
+    let fixed = samples
+        .iter()
+        .map(|vector| distance::<O>(&centroid, vector))
+        .collect::<Vec<_>>();
+    let assign = |c: &O::Vector| {
+        std::iter::zip(samples.iter(), fixed.iter())
+            .map(|(vector, &d)| distance::<O>(c, vector) < d)
+            .collect::<Vec<_>>()
+    };
+    let is_trivial =
+        |assignment: &[bool]| assignment.iter().all(|&x| x) || assignment.iter().all(|&x| !x);
+
+    let farthest = (0..samples.len()).max_by_key(|&i| fixed[i])?;
+    let mut c = samples[farthest].clone();
+    let mut assignment = assign(&c);
+    for _ in 0..ITERATIONS {
+        if is_trivial(&assignment) {
+            return None;
+        }
+        let moved = std::iter::zip(samples.iter(), assignment.iter())
+            .filter(|(_, x)| **x)
+            .map(|(vector, _)| vector.as_borrowed())
+            .collect::<Vec<_>>();
+        c = O::Vector::mean(dim, &moved);
+        let next = assign(&c);
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+    }
+    if is_trivial(&assignment) {
+        return None;
+    }
+    drop(samples);
+
+    let mut moved = Vec::new();
+    let mut kept = Vec::new();
+    for branch in branches.drain(..) {
+        match read(&branch) {
+            Some(vector) if distance::<O>(&c, &vector) < distance::<O>(&centroid, &vector) => {
+                let (code, delta) = O::build(vector.as_borrowed(), is_residual.then(|| c.clone()));
+                moved.push(Branch {
+                    code,
+                    delta,
+                    ..branch
+                });
+            }
+            _ => kept.push(branch),
+        }
+    }
+    *branches = kept;
+    if moved.is_empty() {
+        return None;
+    }
+    Some((c, moved))
+}
+
+/// Creates a list for `centroid` and links it to the h1 tape of its parent.
+#[allow(clippy::too_many_arguments)]
+fn attach<R: RelationRead + RelationWrite, O: Operator>(
+    index: &R,
+    dim: u32,
+    centroids_first: u32,
+    parent_first: u32,
+    parent: Option<O::Vector>,
+    centroid: O::Vector,
+    branches: Vec<Branch<NonZero<u64>>>,
+    check: impl Fn(),
+) where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let (slices, metadata) = O::Vector::split(centroid.as_borrowed());
+    let mut chain = Ok(metadata);
+    let mut prefetch = Vec::new();
+    for i in (0..slices.len()).rev() {
+        let bytes = CentroidTuple::<O::Vector>::serialize(&match chain {
+            Ok(metadata) => CentroidTuple::_0 {
+                elements: slices[i].to_vec(),
+                metadata,
+            },
+            Err(head) => CentroidTuple::_1 {
+                elements: slices[i].to_vec(),
+                head,
+            },
+        });
+        let (id, head) = tape::append(index, centroids_first, &bytes, false, None);
+        chain = Err(head);
+        prefetch.push(id);
     }
+    prefetch.reverse();
+    let head = chain.expect_err("internal error: 0-dimensional vector");
+
+    let tuples = branches.len() as u64;
+    let (directory_first, frozen_first, appendable_first) =
+        write_list::<R, O>(index, dim, branches, &check);
+
+    let mut jump = TapeWriter::<_, JumpTuple>::create(index, false);
+    jump.push(JumpTuple {
+        directory_first,
+        frozen_first,
+        appendable_first,
+        centroid_prefetch: prefetch.clone(),
+        centroid_head: head,
+        tuples,
+    });
+    let jump_first = { jump }.first();
+
+    let (code, delta) = O::build(centroid.as_borrowed(), parent);
+    let mut tape = H1TapeWriter::create(index, O::Vector::count(dim) as _, false);
+    tape.push(Branch {
+        code,
+        delta,
+        prefetch,
+        head,
+        norm: O::Vector::squared_norm(centroid.as_borrowed()).sqrt(),
+        extra: jump_first,
+    });
+    let (mut tape, chunk) = tape.into_inner();
+    H1TapeWriter::flush(&mut tape, O::Vector::count(dim) as _, chunk);
+    let h1_first = { tape }.first();
+
+    let last = tape::by_next(index, parent_first)
+        .inspect(|_| check())
+        .map(|guard| guard.id())
+        .last()
+        .expect("data corruption");
+    let mut guard = index.write(last, false);
+    assert_eq!(guard.get_opaque().next, u32::MAX, "data corruption");
+    guard.get_opaque_mut().next = h1_first;
+    drop(guard);
+
+    let mut meta_guard = index.write(0, false);
+    let meta_bytes = meta_guard.get_mut(1).expect("data corruption");
+    let mut meta_tuple = MetaTuple::deserialize_mut(meta_bytes);
+    meta_tuple.cells()[0] += 1;
 }
 
 #[derive(Clone)]
@@ -14,8 +14,9 @@
 
 use distance::Distance;
 use index_accessor::{
-    Accessor1, Accessor2, ByteDistanceAccessor, DefaultWithDimension, DistanceAccessor, Dot,
-    HalfbyteDistanceAccessor, L2S, RAccess,
+    Accessor1, Accessor2, BitDistanceAccessor, ByteDistanceAccessor, DefaultWithDimension,
+    DistanceAccessor, Dot, HalfbyteDistanceAccessor, Hamming, Jaccard, L2S, LAccess, LTryAccess,
+    RAccess, TryAccessor1,
 };
 use rabitq::bit::CodeMetadata;
 use rabitq::bit::binary::BinaryLut;
@@ -23,8 +24,10 @@ use rabitq::bit::block::{BlockLut, STEP};
 use simd::{Floating, f16};
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use vector::bvect::{BVectBorrowed, BVectOwned};
 use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
 use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
+use vector::svect::{SVectBorrowed, SVectOwned};
 use vector::vect::{VectBorrowed, VectOwned};
 use vector::{VectorBorrowed, VectorOwned};
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
@@ -65,29 +68,233 @@ impl<F: Call<u32, CodeMetadata, f32>>
 }
 
 #[derive(Debug, Clone)]
-pub struct CloneAccessor<V: Vector>(Vec<V::Element>);
+pub struct CloneAccessor<V: Vector>(u32, Vec<V::Element>);
 
-impl<V: Vector> Default for CloneAccessor<V> {
+impl<V: Vector> DefaultWithDimension for CloneAccessor<V> {
     #[inline(always)]
-    fn default() -> Self {
-        Self(Vec::new())
+    fn default_with_dimension(dim: u32) -> Self {
+        Self(dim, Vec::new())
     }
 }
 
-impl<V: Vector> Accessor1<V::Element, (V::Metadata, u32)> for CloneAccessor<V> {
+impl<V: Vector> Accessor1<V::Element, V::Metadata> for CloneAccessor<V> {
     type Output = V;
 
     #[inline(always)]
     fn push(&mut self, input: &[V::Element]) {
-        self.0.extend(input);
+        self.1.extend(input);
     }
 
     #[inline(always)]
-    fn finish(self, (metadata, dim): (V::Metadata, u32)) -> Self::Output {
-        V::pack(dim, self.0, metadata)
+    fn finish(self, metadata: V::Metadata) -> Self::Output {
+        V::pack(self.0, self.1, metadata)
     }
 }
 
+impl<V: Vector> TryAccessor1<V::Element, V::Metadata> for CloneAccessor<V> {
+    type Output = V;
+
+    #[inline(always)]
+    fn push(&mut self, input: &[V::Element]) -> Option<()> {
+        self.1.extend(input);
+        Some(())
+    }
+
+    #[inline(always)]
+    fn finish(self, metadata: V::Metadata) -> Option<Self::Output> {
+        Some(V::pack(self.0, self.1, metadata))
+    }
+}
+
+pub(crate) fn distance<O: Operator>(lhs: &O::Vector, rhs: &O::Vector) -> Distance {
+    let (slices, metadata) = O::Vector::split(rhs.as_borrowed());
+    let mut accessor = O::access(lhs.as_borrowed());
+    for slice in slices {
+        accessor.push(slice);
+    }
+    accessor.finish(metadata)
+}
+
+#[derive(Debug)]
+pub struct SparseAccessor<D>(u32, Vec<u32>, Vec<u32>, PhantomData<fn(D) -> D>);
+
+impl<D> DefaultWithDimension for SparseAccessor<D> {
+    #[inline(always)]
+    fn default_with_dimension(dim: u32) -> Self {
+        Self(dim, Vec::new(), Vec::new(), PhantomData)
+    }
+}
+
+impl Accessor2<u32, u32, (), ()> for SparseAccessor<L2S> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u32], input: &[u32]) {
+        self.1.extend(target);
+        self.2.extend(input);
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        let target = SVectOwned::<f32>::pack(self.0, self.1, ());
+        let input = SVectOwned::<f32>::pack(self.0, self.2, ());
+        target.as_borrowed().operator_l2s(input.as_borrowed())
+    }
+}
+
+impl Accessor2<u32, u32, (), ()> for SparseAccessor<Dot> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, target: &[u32], input: &[u32]) {
+        self.1.extend(target);
+        self.2.extend(input);
+    }
+
+    #[inline(always)]
+    fn finish(self, (): (), (): ()) -> Self::Output {
+        let target = SVectOwned::<f32>::pack(self.0, self.1, ());
+        let input = SVectOwned::<f32>::pack(self.0, self.2, ());
+        target.as_borrowed().operator_dot(input.as_borrowed())
+    }
+}
+
+/// Sparse vectors are stored as two slices, so stored elements are collected
+/// and merged with the whole query at the end instead of position by position.
+#[derive(Debug)]
+pub struct SparseAccess<'a, D>(SVectBorrowed<'a, f32>, Vec<u32>, PhantomData<fn(D) -> D>);
+
+impl<'a, D> SparseAccess<'a, D> {
+    #[inline(always)]
+    pub fn new(vector: SVectBorrowed<'a, f32>) -> Self {
+        Self(vector, Vec::new(), PhantomData)
+    }
+}
+
+impl Accessor1<u32, ()> for SparseAccess<'_, L2S> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, input: &[u32]) {
+        self.1.extend(input);
+    }
+
+    #[inline(always)]
+    fn finish(self, (): ()) -> Self::Output {
+        let target = SVectOwned::<f32>::pack(self.0.dim(), self.1, ());
+        self.0.operator_l2s(target.as_borrowed())
+    }
+}
+
+impl Accessor1<u32, ()> for SparseAccess<'_, Dot> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, input: &[u32]) {
+        self.1.extend(input);
+    }
+
+    #[inline(always)]
+    fn finish(self, (): ()) -> Self::Output {
+        let target = SVectOwned::<f32>::pack(self.0.dim(), self.1, ());
+        self.0.operator_dot(target.as_borrowed())
+    }
+}
+
+impl TryAccessor1<u32, ()> for SparseAccess<'_, L2S> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, input: &[u32]) -> Option<()> {
+        self.1.extend(input);
+        Some(())
+    }
+
+    #[inline(always)]
+    fn finish(self, (): ()) -> Option<Self::Output> {
+        let target = sparse_unpack(self.0.dim(), self.1)?;
+        Some(self.0.operator_l2s(target.as_borrowed()))
+    }
+}
+
+impl TryAccessor1<u32, ()> for SparseAccess<'_, Dot> {
+    type Output = Distance;
+
+    #[inline(always)]
+    fn push(&mut self, input: &[u32]) -> Option<()> {
+        self.1.extend(input);
+        Some(())
+    }
+
+    #[inline(always)]
+    fn finish(self, (): ()) -> Option<Self::Output> {
+        let target = sparse_unpack(self.0.dim(), self.1)?;
+        Some(self.0.operator_dot(target.as_borrowed()))
+    }
+}
+
+fn sparse_unpack(dim: u32, mut elements: Vec<u32>) -> Option<SVectOwned<f32>> {
+    if elements.len() % 2 != 0 {
+        return None;
+    }
+    let n = elements.len() / 2;
+    let values = elements[n..].iter().copied().map(f32::from_bits).collect();
+    elements.truncate(n);
+    SVectOwned::new_checked(dim, elements, values)
+}
+
+fn sparse_project(vector: SVectBorrowed<'_, f32>) -> Vec<f32> {
+    let mut result = vec![0.0_f32; vector.dim() as usize];
+    for (&index, &value) in std::iter::zip(vector.indexes(), vector.values()) {
+        result[index as usize] = value;
+    }
+    rabitq::rotate::rotate_inplace(&mut result);
+    result
+}
+
+// Binary vectors are quantized as 0/1 vectors, so the estimated squared
+// Euclidean distance is the estimated Hamming distance.
+#[inline(always)]
+fn jaccard_from_hamming(s: f32, (rough, err): (f32, f32)) -> (f32, f32) {
+    let jaccard = vector::bvect::jaccard_from_hamming(s, rough);
+    (
+        jaccard,
+        jaccard - vector::bvect::jaccard_from_hamming(s, rough - err),
+    )
+}
+
+/// Keeps the largest non-zero elements of a sparse centroid that fit into a
+/// centroid tuple.
+pub fn sparse_centroid(dim: u32, elements: impl Iterator<Item = (u32, f32)>) -> SVectOwned<f32> {
+    const LIMIT: usize = 1920;
+    let mut elements = elements
+        .filter(|&(_, value)| value != 0.0)
+        .collect::<Vec<_>>();
+    if elements.len() > LIMIT {
+        elements.select_nth_unstable_by(LIMIT - 1, |(_, x), (_, y)| {
+            f32::total_cmp(&y.abs(), &x.abs())
+        });
+        elements.truncate(LIMIT);
+        elements.sort_unstable_by_key(|&(index, _)| index);
+    }
+    let (indexes, values) = elements.into_iter().unzip();
+    SVectOwned::new(dim, indexes, values)
+}
+
+/// Rounds each element of the mean of binary vectors to the nearest bit. A bit
+/// is set if it is set in more than half of the vectors, so the result is the
+/// binary vector with the least sum of Hamming distances to them.
+pub fn bit_centroid(mean: &[f32]) -> BVectOwned {
+    let dim = mean.len() as u32;
+    let mut data = vec![0_u64; dim.div_ceil(u64::BITS) as usize];
+    for (i, &x) in mean.iter().enumerate() {
+        if x > 0.5 {
+            data[i / u64::BITS as usize] |= 1 << (i % u64::BITS as usize);
+        }
+    }
+    BVectOwned::new(dim, data)
+}
+
 pub trait Vector: VectorOwned {
     type Element: Debug + Copy + FromBytes + IntoBytes + Immutable + KnownLayout;
 
@@ -97,8 +304,6 @@ pub trait Vector: VectorOwned {
 
     fn count(dim: u32) -> u32;
 
-    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata);
-
     fn pack(dim: u32, elements: Vec<Self::Element>, metadata: Self::Metadata) -> Self;
 
     fn block_preprocess(vector: Self::Borrowed<'_>) -> BlockLut;
@@ -108,6 +313,13 @@ pub trait Vector: VectorOwned {
     fn code(vector: Self::Borrowed<'_>) -> rabitq::bit::Code;
 
     fn squared_norm(vector: Self::Borrowed<'_>) -> f32;
+
+    fn mean(dim: u32, vectors: &[Self::Borrowed<'_>]) -> Self;
+}
+
+/// Vectors whose stored elements are a borrowed slice of the vector itself.
+pub trait Unpack: Vector {
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata);
 }
 
 impl Vector for VectOwned<f32> {
@@ -137,10 +349,6 @@ impl Vector for VectOwned<f32> {
         }
     }
 
-    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
-        (vector.slice(), ())
-    }
-
     fn pack(_: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
         VectOwned::new(elements)
     }
@@ -160,6 +368,21 @@ impl Vector for VectOwned<f32> {
     fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
         f32::reduce_sum_of_x2(vector.slice())
     }
+
+    fn mean(dim: u32, vectors: &[Self::Borrowed<'_>]) -> Self {
+        let mut result = vec![0.0_f32; dim as _];
+        for vector in vectors {
+            f32::vector_add_inplace(&mut result, vector.slice());
+        }
+        f32::vector_mul_scalar_inplace(&mut result, 1.0 / vectors.len() as f32);
+        VectOwned::new(result)
+    }
+}
+
+impl Unpack for VectOwned<f32> {
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.slice(), ())
+    }
 }
 
 impl Vector for VectOwned<f16> {
@@ -189,10 +412,6 @@ impl Vector for VectOwned<f16> {
         }
     }
 
-    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
-        (vector.slice(), ())
-    }
-
     fn pack(_: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
         VectOwned::new(elements)
     }
@@ -212,6 +431,21 @@ impl Vector for VectOwned<f16> {
     fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
         f16::reduce_sum_of_x2(vector.slice())
     }
+
+    fn mean(dim: u32, vectors: &[Self::Borrowed<'_>]) -> Self {
+        let mut result = vec![0.0_f32; dim as _];
+        for vector in vectors {
+            f32::vector_add_inplace(&mut result, &f16::vector_to_f32(vector.slice()));
+        }
+        f32::vector_mul_scalar_inplace(&mut result, 1.0 / vectors.len() as f32);
+        VectOwned::new(f16::vector_from_f32(&result))
+    }
+}
+
+impl Unpack for VectOwned<f16> {
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.slice(), ())
+    }
 }
 
 impl Vector for Rabitq8Owned {
@@ -245,18 +479,6 @@ impl Vector for Rabitq8Owned {
         }
     }
 
-    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
-        (
-            vector.packed_code(),
-            [
-                vector.sum_of_x2(),
-                vector.norm_of_lattice(),
-                vector.sum_of_code(),
-                vector.sum_of_abs_x(),
-            ],
-        )
-    }
-
     fn pack(dim: u32, elements: Vec<Self::Element>, [_0, _1, _2, _3]: Self::Metadata) -> Self {
         Rabitq8Owned::new(dim, _0, _1, _2, _3, elements)
     }
@@ -314,6 +536,42 @@ impl Vector for Rabitq8Owned {
     fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
         vector.sum_of_x2()
     }
+
+    fn mean(dim: u32, vectors: &[Self::Borrowed<'_>]) -> Self {
+        let mut result = vec![0.0_f32; dim as _];
+        for vector in vectors {
+            let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+            let base = -0.5 * ((1 << 8) - 1) as f32;
+            for (x, c) in std::iter::zip(result.iter_mut(), vector.unpacked_code()) {
+                *x += (base + c as f32) * scale;
+            }
+        }
+        f32::vector_mul_scalar_inplace(&mut result, 1.0 / vectors.len() as f32);
+        let (metadata, elements) = rabitq::byte::ugly_code(&result);
+        let elements = rabitq::byte::pack_code(&elements);
+        Rabitq8Owned::new(
+            dim,
+            metadata.dis_u_2,
+            metadata.norm_of_lattice,
+            metadata.sum_of_code,
+            f32::reduce_sum_of_abs_x(&result),
+            elements,
+        )
+    }
+}
+
+impl Unpack for Rabitq8Owned {
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (
+            vector.packed_code(),
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
 }
 
 impl Vector for Rabitq4Owned {
@@ -347,18 +605,6 @@ impl Vector for Rabitq4Owned {
         }
     }
 
-    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
-        (
-            vector.packed_code(),
-            [
-                vector.sum_of_x2(),
-                vector.norm_of_lattice(),
-                vector.sum_of_code(),
-                vector.sum_of_abs_x(),
-            ],
-        )
-    }
-
     fn pack(dim: u32, elements: Vec<Self::Element>, [_0, _1, _2, _3]: Self::Metadata) -> Self {
         Rabitq4Owned::new(dim, _0, _1, _2, _3, elements)
     }
@@ -416,6 +662,142 @@ impl Vector for Rabitq4Owned {
     fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
         vector.sum_of_x2()
     }
+
+    fn mean(dim: u32, vectors: &[Self::Borrowed<'_>]) -> Self {
+        let mut result = vec![0.0_f32; dim as _];
+        for vector in vectors {
+            let scale = vector.sum_of_x2().sqrt() / vector.norm_of_lattice();
+            let base = -0.5 * ((1 << 4) - 1) as f32;
+            for (x, c) in std::iter::zip(result.iter_mut(), vector.unpacked_code()) {
+                *x += (base + c as f32) * scale;
+            }
+        }
+        f32::vector_mul_scalar_inplace(&mut result, 1.0 / vectors.len() as f32);
+        let (metadata, elements) = rabitq::halfbyte::ugly_code(&result);
+        let elements = rabitq::halfbyte::pack_code(&elements);
+        Rabitq4Owned::new(
+            dim,
+            metadata.dis_u_2,
+            metadata.norm_of_lattice,
+            metadata.sum_of_code,
+            f32::reduce_sum_of_abs_x(&result),
+            elements,
+        )
+    }
+}
+
+impl Unpack for Rabitq4Owned {
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (
+            vector.packed_code(),
+            [
+                vector.sum_of_x2(),
+                vector.norm_of_lattice(),
+                vector.sum_of_code(),
+                vector.sum_of_abs_x(),
+            ],
+        )
+    }
+}
+
+impl Vector for SVectOwned<f32> {
+    type Metadata = ();
+
+    type Element = u32;
+
+    fn split(vector: Self::Borrowed<'_>) -> (Vec<&[u32]>, ()) {
+        let values = <[u32]>::ref_from_bytes(vector.values().as_bytes()).expect("unaligned");
+        (vec![vector.indexes(), values], ())
+    }
+
+    fn count(_: u32) -> u32 {
+        2
+    }
+
+    fn pack(dim: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
+        sparse_unpack(dim, elements).expect("data corruption")
+    }
+
+    fn block_preprocess(vector: Self::Borrowed<'_>) -> BlockLut {
+        rabitq::bit::block::preprocess(&sparse_project(vector))
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> (BlockLut, BinaryLut) {
+        rabitq::bit::preprocess(&sparse_project(vector))
+    }
+
+    fn code(vector: Self::Borrowed<'_>) -> rabitq::bit::Code {
+        rabitq::bit::code(&sparse_project(vector))
+    }
+
+    fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
+        f32::reduce_sum_of_x2(vector.values())
+    }
+
+    fn mean(dim: u32, vectors: &[Self::Borrowed<'_>]) -> Self {
+        let mut sum = std::collections::BTreeMap::<u32, f32>::new();
+        for vector in vectors {
+            for (&index, &value) in std::iter::zip(vector.indexes(), vector.values()) {
+                *sum.entry(index).or_default() += value;
+            }
+        }
+        let n = vectors.len() as f32;
+        sparse_centroid(
+            dim,
+            sum.into_iter().map(|(index, value)| (index, value / n)),
+        )
+    }
+}
+
+impl Vector for BVectOwned {
+    type Metadata = ();
+
+    type Element = u64;
+
+    fn split(vector: Self::Borrowed<'_>) -> (Vec<&[u64]>, ()) {
+        (vec![vector.data()], ())
+    }
+
+    fn count(_: u32) -> u32 {
+        1
+    }
+
+    fn pack(dim: u32, elements: Vec<Self::Element>, (): Self::Metadata) -> Self {
+        BVectOwned::new(dim, elements)
+    }
+
+    fn block_preprocess(vector: Self::Borrowed<'_>) -> BlockLut {
+        rabitq::bit::block::preprocess(&vector.project())
+    }
+
+    fn preprocess(vector: Self::Borrowed<'_>) -> (BlockLut, BinaryLut) {
+        rabitq::bit::preprocess(&vector.project())
+    }
+
+    fn code(vector: Self::Borrowed<'_>) -> rabitq::bit::Code {
+        rabitq::bit::code(&vector.project())
+    }
+
+    fn squared_norm(vector: Self::Borrowed<'_>) -> f32 {
+        simd::bit::reduce_sum_of_x(vector.data()) as f32
+    }
+
+    fn mean(dim: u32, vectors: &[Self::Borrowed<'_>]) -> Self {
+        let mut result = vec![0.0_f32; dim as _];
+        for vector in vectors {
+            for (x, bit) in std::iter::zip(result.iter_mut(), vector.iter()) {
+                *x += bit as u8 as f32;
+            }
+        }
+        f32::vector_mul_scalar_inplace(&mut result, 1.0 / vectors.len() as f32);
+        bit_centroid(&result)
+    }
+}
+
+impl Unpack for BVectOwned {
+    fn unpack(vector: Self::Borrowed<'_>) -> (&[Self::Element], Self::Metadata) {
+        (vector.data(), ())
+    }
 }
 
 pub trait Operator: 'static + Debug + Copy {
@@ -448,6 +830,44 @@ pub trait Operator: 'static + Debug + Copy {
         vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
         centroid: Option<Self::Vector>,
     ) -> (rabitq::bit::Code, f32);
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<
+        <Self::Vector as Vector>::Element,
+        <Self::Vector as Vector>::Metadata,
+        Output = Distance,
+    >;
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<
+        <Self::Vector as Vector>::Element,
+        <Self::Vector as Vector>::Metadata,
+        Output = Distance,
+    >;
+}
+
+fn unpack_access<O: Operator<Vector: Unpack>>(
+    vector: <O::Vector as VectorOwned>::Borrowed<'_>,
+) -> impl Accessor1<<O::Vector as Vector>::Element, <O::Vector as Vector>::Metadata, Output = Distance>
+{
+    let dim = vector.dim();
+    LAccess::new(
+        O::Vector::unpack(vector),
+        O::DistanceAccessor::default_with_dimension(dim),
+    )
+}
+
+fn unpack_try_access<O: Operator<Vector: Unpack>>(
+    vector: <O::Vector as VectorOwned>::Borrowed<'_>,
+) -> impl TryAccessor1<<O::Vector as Vector>::Element, <O::Vector as Vector>::Metadata, Output = Distance>
+{
+    let dim = vector.dim();
+    LTryAccess::new(
+        O::Vector::unpack(vector),
+        O::DistanceAccessor::default_with_dimension(dim),
+    )
 }
 
 #[derive(Debug)]
@@ -534,6 +954,18 @@ impl Operator for Op<VectOwned<f32>, L2S> {
             (code, delta)
         }
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<f32, (), Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<f32, (), Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 impl Operator for Op<VectOwned<f32>, Dot> {
@@ -611,6 +1043,18 @@ impl Operator for Op<VectOwned<f32>, Dot> {
             (code, delta)
         }
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<f32, (), Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<f32, (), Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 impl Operator for Op<VectOwned<f16>, L2S> {
@@ -687,6 +1131,18 @@ impl Operator for Op<VectOwned<f16>, L2S> {
             (code, delta)
         }
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<f16, (), Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<f16, (), Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 impl Operator for Op<VectOwned<f16>, Dot> {
@@ -765,6 +1221,18 @@ impl Operator for Op<VectOwned<f16>, Dot> {
             (code, delta)
         }
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<f16, (), Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<f16, (), Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 impl Operator for Op<Rabitq8Owned, L2S> {
@@ -816,6 +1284,18 @@ impl Operator for Op<Rabitq8Owned, L2S> {
         }
         (Self::Vector::code(vector), 0.0)
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u8, [f32; 4], Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u8, [f32; 4], Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 impl Operator for Op<Rabitq8Owned, Dot> {
@@ -867,6 +1347,18 @@ impl Operator for Op<Rabitq8Owned, Dot> {
         }
         (Self::Vector::code(vector), 0.0)
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u8, [f32; 4], Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u8, [f32; 4], Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 impl Operator for Op<Rabitq4Owned, L2S> {
@@ -918,6 +1410,18 @@ impl Operator for Op<Rabitq4Owned, L2S> {
         }
         (Self::Vector::code(vector), 0.0)
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u8, [f32; 4], Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u8, [f32; 4], Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 impl Operator for Op<Rabitq4Owned, Dot> {
@@ -969,6 +1473,272 @@ impl Operator for Op<Rabitq4Owned, Dot> {
         }
         (Self::Vector::code(vector), 0.0)
     }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u8, [f32; 4], Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u8, [f32; 4], Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
+}
+
+impl Operator for Op<SVectOwned<f32>, L2S> {
+    type Vector = SVectOwned<f32>;
+
+    type DistanceAccessor = SparseAccessor<L2S>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        assert!(!is_residual);
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code, _delta| {
+                rabitq::bit::block::half_process_l2s(value, code, lut.0)
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            rabitq::bit::binary::half_process_l2s(value, code, lut.0)
+        }
+    }
+
+    fn build(
+        vector: SVectBorrowed<'_, f32>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if centroid.is_some() {
+            unimplemented!();
+        }
+        (Self::Vector::code(vector), 0.0)
+    }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u32, (), Output = Distance> {
+        SparseAccess::<L2S>::new(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u32, (), Output = Distance> {
+        SparseAccess::<L2S>::new(vector)
+    }
+}
+
+impl Operator for Op<SVectOwned<f32>, Dot> {
+    type Vector = SVectOwned<f32>;
+
+    type DistanceAccessor = SparseAccessor<Dot>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        assert!(!is_residual);
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code, _delta| {
+                rabitq::bit::block::half_process_dot(value, code, lut.0)
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            rabitq::bit::binary::half_process_dot(value, code, lut.0)
+        }
+    }
+
+    fn build(
+        vector: SVectBorrowed<'_, f32>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if centroid.is_some() {
+            unimplemented!();
+        }
+        (Self::Vector::code(vector), 0.0)
+    }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u32, (), Output = Distance> {
+        SparseAccess::<Dot>::new(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u32, (), Output = Distance> {
+        SparseAccess::<Dot>::new(vector)
+    }
+}
+
+impl Operator for Op<BVectOwned, Hamming> {
+    type Vector = BVectOwned;
+
+    type DistanceAccessor = BitDistanceAccessor<BVectOwned, Hamming>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        assert!(!is_residual);
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code, _delta| {
+                rabitq::bit::block::half_process_l2s(value, code, lut.0)
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            rabitq::bit::binary::half_process_l2s(value, code, lut.0)
+        }
+    }
+
+    fn build(
+        vector: BVectBorrowed<'_>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if centroid.is_some() {
+            unimplemented!();
+        }
+        (Self::Vector::code(vector), 0.0)
+    }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u64, (), Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u64, (), Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
+}
+
+impl Operator for Op<BVectOwned, Jaccard> {
+    type Vector = BVectOwned;
+
+    type DistanceAccessor = BitDistanceAccessor<BVectOwned, Jaccard>;
+
+    fn block_access(
+        lut: &BlockLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl for<'x> Accessor1<[u8; 16], (&'x [[f32; 32]; 4], &'x [f32; 32]), Output = [(f32, f32); 32]>
+    {
+        assert!(!is_residual);
+        RAccess::new(
+            (&lut.1, ()),
+            BlockAccessor([0_u32; 32], move |value, code: CodeMetadata, _delta| {
+                let s = code.dis_u_2 + lut.0.dis_v_2();
+                jaccard_from_hamming(s, rabitq::bit::block::half_process_l2s(value, code, lut.0))
+            }),
+        )
+    }
+
+    fn binary_access(
+        lut: &BinaryLut,
+        is_residual: bool,
+        _dis_f: f32,
+        _norm: f32,
+    ) -> impl FnMut([f32; 4], &[u64], f32) -> (f32, f32) {
+        assert!(!is_residual);
+        move |metadata: [f32; 4], elements: &[u64], _delta: f32| {
+            let value = rabitq::bit::binary::accumulate(elements, &lut.1);
+            let code = CodeMetadata {
+                dis_u_2: metadata[0],
+                factor_cnt: metadata[1],
+                factor_ip: metadata[2],
+                factor_err: metadata[3],
+            };
+            let s = code.dis_u_2 + lut.0.dis_v_2();
+            jaccard_from_hamming(s, rabitq::bit::binary::half_process_l2s(value, code, lut.0))
+        }
+    }
+
+    fn build(
+        vector: BVectBorrowed<'_>,
+        centroid: Option<Self::Vector>,
+    ) -> (rabitq::bit::Code, f32) {
+        if centroid.is_some() {
+            unimplemented!();
+        }
+        (Self::Vector::code(vector), 0.0)
+    }
+
+    fn access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl Accessor1<u64, (), Output = Distance> {
+        unpack_access::<Self>(vector)
+    }
+
+    fn try_access(
+        vector: <Self::Vector as VectorOwned>::Borrowed<'_>,
+    ) -> impl TryAccessor1<u64, (), Output = Distance> {
+        unpack_try_access::<Self>(vector)
+    }
 }
 
 pub trait Call<A, B, C> {
@@ -985,3 +1755,43 @@ impl<A, B, C, F: Fn(A, B, C) -> R, R> Call<A, B, C> for F {
         (self)(a, b, c)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bit_mean() {
+        let vectors = [0b0111_u64, 0b0011, 0b1001, 0b0001].map(|x| BVectOwned::new(4, vec![x]));
+        let borrowed = vectors.iter().map(|x| x.as_borrowed()).collect::<Vec<_>>();
+        // bit 1 is set in exactly half of the vectors, so it is not set
+        let mean = BVectOwned::mean(4, &borrowed);
+        assert_eq!(mean.as_borrowed().data(), &[0b0001]);
+        // no binary vector is closer to the members in total
+        let total = |c: &BVectOwned| {
+            borrowed
+                .iter()
+                .map(|x| c.as_borrowed().operator_hamming(*x).to_f32())
+                .sum::<f32>()
+        };
+        for x in 0..16_u64 {
+            assert!(total(&mean) <= total(&BVectOwned::new(4, vec![x])));
+        }
+    }
+
+    #[test]
+    fn test_sparse_centroid() {
+        let elements = (0..4000_u32).map(|i| (i, if i % 2 == 0 { i as f32 } else { 0.0 }));
+        let centroid = sparse_centroid(4000, elements);
+        let centroid = centroid.as_borrowed();
+        assert_eq!(centroid.indexes().len(), 1920);
+        assert!(centroid.indexes().is_sorted());
+        assert_eq!(centroid.indexes()[0], 4000 - 2 * 1920);
+        assert!(
+            centroid
+                .values()
+                .iter()
+                .all(|&x| x >= (4000 - 2 * 1920) as f32)
+        );
+    }
+}
@@ -0,0 +1,291 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::build::write_tree;
+use crate::closure_lifetime_binder::{id_0, id_1};
+use crate::operator::{CloneAccessor, Operator, Vector, distance};
+use crate::tape::TapeWriter;
+use crate::tuples::*;
+use crate::types::Structure;
+use crate::{Opaque, freepages, tape, vectors};
+use index::relation::{Page, PageGuard, RelationRead, RelationWrite};
+use index_accessor::{DefaultWithDimension, FunctionalAccessor};
+use std::num::NonZero;
+use vector::VectorOwned;
+
+pub struct Recluster {
+    pub number_of_lists: usize,
+    pub number_of_tuples: u64,
+    pub number_of_freed_pages: usize,
+}
+
+/// Replaces the tree of centroids with `structures` and moves every vector into
+/// the list of its nearest new centroid.
+///
+/// The new tree is written aside and becomes visible once the meta tuple is
+/// switched, so searches always see a complete tree. Old lists are swept once
+/// while insertions go on, then `lock_writers` must block new insertions and
+/// wait for running ones, so that the last sweep drains the old lists before
+/// the switch; `unlock_writers` is called right after the switch. Until then
+/// the old tree stays intact, and an abort only leaks the pages of the new
+/// tree. `wait_for_readers` must return after all searches that may have seen
+/// the old tree have finished, since the old tree is freed after that.
+///
+/// Returns `None` without touching the index if vectors are not stored in the
+/// index, since they cannot be moved then.
+pub fn recluster<R: RelationRead + RelationWrite, O: Operator>(
+    index: &R,
+    structures: Vec<Structure<O::Vector>>,
+    lock_writers: impl FnOnce(),
+    unlock_writers: impl FnOnce(),
+    wait_for_readers: impl FnOnce(),
+    check: impl Fn(),
+) -> Option<Recluster>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let meta_guard = index.read(0);
+    let meta_bytes = meta_guard.get(1).expect("data corruption");
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let dim = meta_tuple.dim();
+    let height_of_root = meta_tuple.height_of_root();
+    let is_residual = meta_tuple.is_residual();
+    let rerank_in_heap = meta_tuple.rerank_in_heap();
+    let vectors_first = meta_tuple.vectors_first().to_vec();
+    let freepages_first = meta_tuple.freepages_first();
+    let old_centroids_first = meta_tuple.centroids_first();
+    let old_first = meta_tuple.first();
+    drop(meta_guard);
+
+    if rerank_in_heap {
+        return None;
+    }
+
+    // collect the old tree
+    let mut old_h1 = Vec::new();
+    let mut old_lists = vec![old_first];
+    for _ in (1..height_of_root).rev() {
+        let mut results = Vec::new();
+        for first in old_lists {
+            tape::read_h1_tape::<R, _, _>(
+                tape::by_next(index, first)
+                    .inspect(|_| check())
+                    .inspect(|guard| old_h1.push(guard.id())),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), _, _, first, _| results.push(first),
+            );
+        }
+        old_lists = results;
+    }
+
+    // write the new tree, which is unreachable until the meta tuple is switched
+    let mut centroids = TapeWriter::<_, CentroidTuple<O::Vector>>::create(index, false);
+    let (pointer_of_centroids, pointer_of_firsts) =
+        write_tree::<_, O>(index, dim, is_residual, &mut centroids, &structures);
+    let new_lists = pointer_of_firsts[0].clone();
+    let appendables = new_lists
+        .iter()
+        .map(|&first| {
+            let jump_guard = index.read(first);
+            let jump_bytes = jump_guard.get(1).expect("data corruption");
+            let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+            jump_tuple.appendable_first()
+        })
+        .collect::<Vec<_>>();
+
+    // insertions only append to the last page of an appendable tape, so a
+    // sweep resumes a list where the last sweep stopped
+    let mut cursors = vec![None; old_lists.len()];
+    let mut counts = vec![0_u64; new_lists.len()];
+    let mut migrate = |i: usize| {
+        let mut members = Vec::new();
+        cursors[i] = Some(read_list(
+            index,
+            old_lists[i],
+            cursors[i],
+            &check,
+            |payload, prefetch, head| members.push((payload, prefetch.to_vec(), head)),
+        ));
+        for (payload, prefetch, head) in members {
+            check();
+            let vector = vectors::read::<R, O, _>(
+                prefetch.iter().map(|&id| index.read(id)),
+                head,
+                payload,
+                CloneAccessor::default_with_dimension(dim),
+            );
+            let Some(vector) = vector else {
+                continue;
+            };
+            let mut j = 0_usize;
+            for i in (1..structures.len()).rev() {
+                j = structures[i].children[j]
+                    .iter()
+                    .map(|&child| child as usize)
+                    .min_by_key(|&child| {
+                        distance::<O>(&vector, &structures[i - 1].centroids[child])
+                    })
+                    .expect("invariant is violated: tree is not height-balanced");
+            }
+            let (code, delta) = O::build(
+                vector.as_borrowed(),
+                is_residual.then(|| structures[0].centroids[j].clone()),
+            );
+            let serialized = AppendableTuple::serialize(&AppendableTuple {
+                metadata: [
+                    code.0.dis_u_2,
+                    code.0.factor_cnt,
+                    code.0.factor_ip,
+                    code.0.factor_err,
+                ],
+                delta,
+                payload: Some(payload),
+                prefetch,
+                head,
+                elements: rabitq::bit::binary::pack_code(&code.1),
+            });
+            tape::append(
+                index,
+                appendables[j],
+                &serialized,
+                false,
+                Some(freepages_first),
+            );
+            counts[j] += 1;
+        }
+    };
+
+    for i in 0..old_lists.len() {
+        migrate(i);
+    }
+
+    // insertions append to old lists until the switch, so drain them with
+    // insertions blocked
+    lock_writers();
+    for i in 0..old_lists.len() {
+        migrate(i);
+    }
+
+    for (first, count) in std::iter::zip(new_lists.iter().copied(), counts.iter().copied()) {
+        let mut jump_guard = index.write(first, false);
+        let jump_bytes = jump_guard.get_mut(1).expect("data corruption");
+        let mut jump_tuple = JumpTuple::deserialize_mut(jump_bytes);
+        *jump_tuple.tuples() = count;
+    }
+
+    let root = structures.last().expect("internal error: empty structure");
+    let (centroid_prefetch, centroid_head) = pointer_of_centroids
+        .last()
+        .expect("internal error: empty structure")[0]
+        .clone();
+    let serialized = MetaTuple::serialize(&MetaTuple {
+        dim,
+        height_of_root: structures.len() as u32,
+        is_residual,
+        rerank_in_heap,
+        cells: structures.iter().map(|s| s.len() as _).collect(),
+        centroids_first: centroids.first(),
+        vectors_first,
+        freepages_first,
+        centroid_prefetch,
+        centroid_head,
+        centroid_norm: O::Vector::squared_norm(root.centroids[0].as_borrowed()).sqrt(),
+        first: pointer_of_firsts
+            .last()
+            .expect("internal error: empty structure")[0],
+    });
+    drop(centroids);
+    let mut meta_guard = index.write(0, false);
+    let opaque = *meta_guard.get_opaque();
+    meta_guard.clear(opaque);
+    if meta_guard.alloc(&serialized) != Some(1) {
+        panic!("implementation: a clear page cannot accommodate a single tuple");
+    }
+    drop(meta_guard);
+    unlock_writers();
+
+    // searches that have seen the old tree may still read it
+    wait_for_readers();
+    let mut pages = old_h1;
+    for first in old_lists.iter().copied() {
+        let jump_guard = index.read(first);
+        let jump_bytes = jump_guard.get(1).expect("data corruption");
+        let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+        let directory_first = jump_tuple.directory_first();
+        let frozen_first = jump_tuple.frozen_first();
+        let appendable_first = jump_tuple.appendable_first();
+        drop(jump_guard);
+        for first in [first, directory_first, frozen_first, appendable_first] {
+            pages.extend(tape::by_next(index, first).map(|guard| guard.id()));
+        }
+    }
+    pages.extend(tape::by_next(index, old_centroids_first).map(|guard| guard.id()));
+    for id in pages.iter().copied() {
+        freepages::free(index, freepages_first, id);
+    }
+
+    Some(Recluster {
+        number_of_lists: new_lists.len(),
+        number_of_tuples: counts.iter().sum(),
+        number_of_freed_pages: pages.len(),
+    })
+}
+
+/// Reads the members of a list after `cursor`, which is the last page of the
+/// appendable tape and the number of tuples read on it, and returns the new
+/// cursor. The frozen tape is only read if `cursor` is `None`.
+fn read_list<R: RelationRead>(
+    index: &R,
+    first: u32,
+    cursor: Option<(u32, u16)>,
+    check: &impl Fn(),
+    mut callback: impl FnMut(NonZero<u64>, &[u32], u16),
+) -> (u32, u16)
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let (mut current, mut skip) = match cursor {
+        Some(cursor) => cursor,
+        None => {
+            let jump_guard = index.read(first);
+            let jump_bytes = jump_guard.get(1).expect("data corruption");
+            let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
+            let frozen_first = jump_tuple.frozen_first();
+            let appendable_first = jump_tuple.appendable_first();
+            drop(jump_guard);
+            tape::read_frozen_tape::<R, _, _>(
+                tape::by_next(index, frozen_first).inspect(|_| check()),
+                || FunctionalAccessor::new((), id_0(|_, _| ()), id_1(|_, _| [(); _])),
+                |(), head, payload, prefetch| callback(payload, prefetch, head),
+            );
+            (appendable_first, 0)
+        }
+    };
+    let mut cursor = (current, skip);
+    while current != u32::MAX {
+        check();
+        let guard = index.read(current);
+        for i in skip + 1..=guard.len() {
+            let bytes = guard.get(i).expect("data corruption");
+            let tuple = AppendableTuple::deserialize_ref(bytes);
+            if let Some(payload) = tuple.payload() {
+                callback(payload, tuple.prefetch(), tuple.head());
+            }
+        }
+        cursor = (current, guard.len());
+        current = guard.get_opaque().next;
+        skip = 0;
+    }
+    cursor
+}
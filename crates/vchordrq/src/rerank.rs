@@ -22,12 +22,12 @@ use index::fetch::BorrowedIter;
 use index::packed::PackedRefMut;
 use index::prefetcher::Prefetcher;
 use index::relation::{Page, RelationRead};
-use index_accessor::{Accessor2, DefaultWithDimension, LTryAccess};
+use index_accessor::Accessor1;
 use std::cmp::Reverse;
 use std::collections::BinaryHeap;
 use std::marker::PhantomData;
 use std::num::NonZero;
-use vector::{VectorBorrowed, VectorOwned};
+use vector::VectorOwned;
 
 type Result = (Reverse<Distance>, AlwaysEqual<NonZero<u64>>);
 
@@ -89,7 +89,6 @@ pub fn rerank_index<
     vector: O::Vector,
     prefetcher: P,
 ) -> Reranker<T, impl FnMut(NonZero<u64>, P::Guards, u16) -> Option<Distance>, P, W> {
-    let dim = vector.as_borrowed().dim();
     Reranker {
         prefetcher,
         cache: BinaryHeap::new(),
@@ -98,10 +97,7 @@ pub fn rerank_index<
                 prefetch,
                 head,
                 payload,
-                LTryAccess::new(
-                    O::Vector::unpack(vector.as_borrowed()),
-                    O::DistanceAccessor::default_with_dimension(dim),
-                ),
+                O::try_access(vector.as_borrowed()),
             )
         }),
         _phantom: PhantomData,
@@ -117,21 +113,79 @@ pub fn rerank_heap<
 >(
     vector: O::Vector,
     prefetcher: P,
-    mut fetch: impl FnMut(NonZero<u64>) -> Option<O::Vector> + 'b,
+    mut fetch: impl FnMut(NonZero<u64>) -> Option<O::Vector>,
 ) -> Reranker<T, impl FnMut(NonZero<u64>, P::Guards, u16) -> Option<Distance>, P, W> {
-    let dim = vector.as_borrowed().dim();
     Reranker {
         prefetcher,
         cache: BinaryHeap::new(),
         f: id_4::<_, P, _, _, _>(move |payload, _, _| {
-            let unpack = O::Vector::unpack(vector.as_borrowed());
-            let vector = fetch(payload)?;
-            let vector = O::Vector::unpack(vector.as_borrowed());
-            let mut accessor = O::DistanceAccessor::default_with_dimension(dim);
-            accessor.push(unpack.0, vector.0);
-            let distance = accessor.finish(unpack.1, vector.1);
+            let target = fetch(payload)?;
+            let (slices, metadata) = O::Vector::split(target.as_borrowed());
+            let mut accessor = O::access(vector.as_borrowed());
+            for slice in slices {
+                accessor.push(slice);
+            }
+            let distance = accessor.finish(metadata);
             Some(distance)
         }),
         _phantom: PhantomData,
     }
 }
+
+pub fn rerank_index_multi<
+    'b,
+    O: Operator,
+    T,
+    P: Prefetcher<'b, Item = ((Reverse<Distance>, AlwaysEqual<T>), AlwaysEqual<W>)>,
+    W: 'b + PackedRefMut<T = (NonZero<u64>, u16, BorrowedIter<'b>)>,
+>(
+    vectors: Vec<O::Vector>,
+    mut combine: impl FnMut(Vec<Distance>) -> Distance,
+    prefetcher: P,
+) -> Reranker<T, impl FnMut(NonZero<u64>, P::Guards, u16) -> Option<Distance>, P, W> {
+    Reranker {
+        prefetcher,
+        cache: BinaryHeap::new(),
+        f: id_4::<_, P, _, _, _>(move |payload, prefetch, head| {
+            let accessors = vectors
+                .iter()
+                .map(|vector| O::try_access(vector.as_borrowed()))
+                .collect::<Vec<_>>();
+            let distances = vectors::read::<P::R, O, _>(prefetch, head, payload, accessors)?;
+            Some(combine(distances))
+        }),
+        _phantom: PhantomData,
+    }
+}
+
+pub fn rerank_heap_multi<
+    'b,
+    O: Operator,
+    T,
+    P: Prefetcher<'b, Item = ((Reverse<Distance>, AlwaysEqual<T>), AlwaysEqual<W>)>,
+    W: 'b + PackedRefMut<T = (NonZero<u64>, u16, BorrowedIter<'b>)>,
+>(
+    vectors: Vec<O::Vector>,
+    mut combine: impl FnMut(Vec<Distance>) -> Distance,
+    prefetcher: P,
+    mut fetch: impl FnMut(NonZero<u64>) -> Option<O::Vector> + 'b,
+) -> Reranker<T, impl FnMut(NonZero<u64>, P::Guards, u16) -> Option<Distance>, P, W> {
+    Reranker {
+        prefetcher,
+        cache: BinaryHeap::new(),
+        f: id_4::<_, P, _, _, _>(move |payload, _, _| {
+            let target = fetch(payload)?;
+            let (slices, metadata) = O::Vector::split(target.as_borrowed());
+            let mut accessors = vectors
+                .iter()
+                .map(|vector| O::access(vector.as_borrowed()))
+                .collect::<Vec<_>>();
+            for slice in slices {
+                Accessor1::push(&mut accessors, slice);
+            }
+            let distances = accessors.finish(metadata);
+            Some(combine(distances))
+        }),
+        _phantom: PhantomData,
+    }
+}
@@ -17,7 +17,7 @@ use crate::linked_vec::LinkedVec;
 use crate::operator::*;
 use crate::tape::{by_directory, by_next};
 use crate::tuples::*;
-use crate::{Opaque, centroids, tape};
+use crate::{Opaque, centroids, tape, vectors};
 use always_equal::AlwaysEqual;
 use distance::Distance;
 use index::bump::Bump;
@@ -25,19 +25,82 @@ use index::fetch::BorrowedIter;
 use index::packed::{PackedRefMut4, PackedRefMut8};
 use index::prefetcher::{Prefetcher, PrefetcherHeapFamily, PrefetcherSequenceFamily};
 use index::relation::{Page, RelationRead};
-use index_accessor::{DefaultWithDimension, FunctionalAccessor, LAccess};
+use index_accessor::FunctionalAccessor;
 use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashSet};
 use std::num::NonZero;
+use std::sync::atomic::{AtomicU32, Ordering};
 use vector::{VectorBorrowed, VectorOwned};
 
 type Extra1<'b> = &'b mut (u32, f32, u16, BorrowedIter<'b>);
 
+#[derive(Debug, Clone)]
+pub enum Probes {
+    Fixed(Vec<u32>),
+    Auto(AutoProbes),
+}
+
+/// Lists at the bottom level are probed in order of centroid distance, and
+/// probing stops early after a list whose members all have estimated lower
+/// bounds no smaller than the `k`-th smallest distance seen before it, since
+/// none of them can be among the nearest `k`. Distances are computed with the
+/// vectors stored in the index, or estimated by upper bounds if vectors are
+/// stored in the table. Without `k`, `max` lists are probed.
+#[derive(Debug, Clone, Copy)]
+pub struct AutoProbes {
+    pub min: u32,
+    pub max: u32,
+    pub k: Option<u32>,
+}
+
+impl Probes {
+    /// Returns the number of lists to probe at each level, from top to
+    /// bottom. In auto mode, upper levels are probed in the same proportion
+    /// as the maximum at the bottom level.
+    pub fn resolve(&self, cells: &[u32]) -> Vec<u32> {
+        match self {
+            Probes::Fixed(probes) => probes.clone(),
+            Probes::Auto(AutoProbes { max, .. }) => {
+                let height = cells.len();
+                let leaf = cells[0];
+                let max = (*max).clamp(1, leaf);
+                (1..height)
+                    .map(|i| {
+                        let n = cells[height - 1 - i];
+                        (n as u64 * max as u64)
+                            .div_ceil(leaf as u64)
+                            .clamp(1, n as u64) as u32
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[test]
+fn test_auto_probes_resolve() {
+    let auto = |max| {
+        Probes::Auto(AutoProbes {
+            min: 1,
+            max,
+            k: None,
+        })
+    };
+    assert_eq!(auto(16).resolve(&[64, 8, 1]), vec![2, 16]);
+    assert_eq!(auto(1).resolve(&[64, 8, 1]), vec![1, 1]);
+    assert_eq!(auto(65535).resolve(&[64, 8, 1]), vec![8, 64]);
+    assert_eq!(auto(16).resolve(&[1]), Vec::<u32>::new());
+}
+
+/// Lists whose first pages are in `probed` are skipped, and the lists that
+/// are probed are added to it.
 pub fn default_search<'b, R: RelationRead, O: Operator>(
     index: &'b R,
     vector: <O::Vector as VectorOwned>::Borrowed<'_>,
-    probes: Vec<u32>,
+    probes: Probes,
     epsilon: f32,
+    parallel: Option<&AtomicU32>,
+    probed: &mut HashSet<u32>,
     bump: &'b impl Bump,
     mut prefetch_h1_vectors: impl PrefetcherHeapFamily<'b, R>,
     mut prefetch_h0_tuples: impl PrefetcherSequenceFamily<'b, R>,
@@ -53,9 +116,17 @@ where
     let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
     let dim = meta_tuple.dim();
     let is_residual = meta_tuple.is_residual();
+    let rerank_in_heap = meta_tuple.rerank_in_heap();
     let height_of_root = meta_tuple.height_of_root();
     let cells = meta_tuple.cells().to_vec();
     assert_eq!(dim, vector.dim(), "unmatched dimensions");
+    // early termination is decided by each participant on its own, so it is
+    // disabled for parallel scans to keep claimed lists from being skipped
+    let auto = match probes {
+        Probes::Auto(auto) if parallel.is_none() => Some(auto),
+        _ => None,
+    };
+    let probes = probes.resolve(&cells);
     if height_of_root as usize != 1 + probes.len() {
         panic!(
             "usage: need {} probes, but {} probes provided",
@@ -70,14 +141,8 @@ where
         let prefetch =
             BorrowedIter::from_slice(meta_tuple.centroid_prefetch(), |x| bump.alloc_slice(x));
         let head = meta_tuple.centroid_head();
-        let distance = centroids::read::<R, O, _>(
-            prefetch.map(|id| index.read(id)),
-            head,
-            LAccess::new(
-                O::Vector::unpack(vector),
-                O::DistanceAccessor::default_with_dimension(dim),
-            ),
-        );
+        let distance =
+            centroids::read::<R, O, _>(prefetch.map(|id| index.read(id)), head, O::access(vector));
         let norm = meta_tuple.centroid_norm();
         let first = meta_tuple.first();
         vec![(Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))]
@@ -118,14 +183,7 @@ where
             while let Some(((Reverse(_), AlwaysEqual(&mut (first, norm, head, ..))), prefetch)) =
                 heap.next_if(|(d, _)| Some(*d) > cache.peek().map(|(d, ..)| *d))
             {
-                let distance = centroids::read::<R, O, _>(
-                    prefetch,
-                    head,
-                    LAccess::new(
-                        O::Vector::unpack(vector),
-                        O::DistanceAccessor::default_with_dimension(dim),
-                    ),
-                );
+                let distance = centroids::read::<R, O, _>(prefetch, head, O::access(vector));
                 cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
             }
             cache.pop()
@@ -134,7 +192,8 @@ where
 
     for i in 1..height_of_root {
         let partial_scan = probes[i as usize - 1] < cells[(height_of_root - 1 - i) as usize];
-        if partial_scan || is_residual {
+        let ordered = auto.is_some() && i == height_of_root - 1;
+        if partial_scan || is_residual || ordered {
             state = step(state).take(probes[i as usize - 1] as _).collect();
         } else {
             // fast path
@@ -156,13 +215,61 @@ where
         }
     }
 
+    // every participant of a parallel scan selects the same lists, and claims
+    // them one by one from the shared counter
+    let mut claim = {
+        let mut next = 0_u32;
+        move || match parallel {
+            Some(parallel) => parallel.fetch_add(1, Ordering::Relaxed),
+            None => {
+                next += 1;
+                next - 1
+            }
+        }
+    };
     let mut results = LinkedVec::<(_, AlwaysEqual<_>)>::new();
-    for (Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first)) in state {
+    // the `k` smallest distances seen so far
+    let mut kths = BinaryHeap::<Distance>::new();
+    let push = |kths: &mut BinaryHeap<Distance>, k: u32, distance: Distance| {
+        if kths.len() < k as usize {
+            kths.push(distance);
+        } else if kths.peek().is_some_and(|&kth| distance < kth) {
+            kths.pop();
+            kths.push(distance);
+        }
+    };
+    // members of this list that may be among the nearest `k`
+    let mut members = Vec::new();
+    let mut visited = 0_u32;
+    while let Some(&(Reverse(dis_f), AlwaysEqual(norm), AlwaysEqual(first))) =
+        state.get(claim() as usize)
+    {
+        // lists in `probed` were probed by an earlier search of the same scan
+        if !probed.insert(first) {
+            continue;
+        }
+        visited += 1;
+        // the `k`-th smallest distance before this list
+        let kth = match auto {
+            Some(AutoProbes { k: Some(k), .. }) if kths.len() >= k as usize => kths.peek().copied(),
+            _ => None,
+        };
+        // the smallest estimated lower bound in this list
+        let mut nearest = None::<Distance>;
         let jump_guard = index.read(first);
         let jump_bytes = jump_guard.get(1).expect("data corruption");
         let jump_tuple = JumpTuple::deserialize_ref(jump_bytes);
         let mut callback = id_2(|(rough, err), head, payload, prefetch| {
             let lowerbound = Distance::from_f32(rough - err * epsilon);
+            nearest = Some(nearest.map_or(lowerbound, |nearest| nearest.min(lowerbound)));
+            if let Some(AutoProbes { k: Some(k), .. }) = auto {
+                if rerank_in_heap {
+                    let upperbound = Distance::from_f32(rough + err * epsilon);
+                    push(&mut kths, k, upperbound);
+                } else if kth.is_none_or(|kth| lowerbound < kth) {
+                    members.push((lowerbound, payload, head, <[u32]>::to_vec(prefetch)));
+                }
+            }
             results.push((
                 (Reverse(lowerbound), AlwaysEqual(())),
                 AlwaysEqual(PackedRefMut4(bump.alloc((
@@ -192,6 +299,32 @@ where
             O::binary_access(&lut.1, is_residual, dis_f.to_f32(), norm),
             &mut callback,
         );
+        drop(jump_guard);
+        if let Some(AutoProbes { k: Some(k), .. }) = auto {
+            members.sort_unstable_by_key(|&(lowerbound, ..)| lowerbound);
+            for (lowerbound, payload, head, prefetch) in members.drain(..) {
+                if kths.len() >= k as usize && kths.peek().is_some_and(|&kth| lowerbound >= kth) {
+                    break;
+                }
+                let distance = vectors::read::<R, O, _>(
+                    prefetch.iter().map(|&id| index.read(id)),
+                    head,
+                    payload,
+                    O::try_access(vector),
+                );
+                if let Some(distance) = distance {
+                    push(&mut kths, k, distance);
+                }
+            }
+        }
+        if let Some(AutoProbes { min, .. }) = auto
+            && visited >= min
+            && nearest
+                .zip(kth)
+                .is_some_and(|(nearest, kth)| nearest >= kth)
+        {
+            break;
+        }
     }
     results.into_vec()
 }
@@ -237,14 +370,8 @@ where
         let prefetch =
             BorrowedIter::from_slice(meta_tuple.centroid_prefetch(), |x| bump.alloc_slice(x));
         let head = meta_tuple.centroid_head();
-        let distance = centroids::read::<R, O, _>(
-            prefetch.map(|id| index.read(id)),
-            head,
-            LAccess::new(
-                O::Vector::unpack(vector),
-                O::DistanceAccessor::default_with_dimension(dim),
-            ),
-        );
+        let distance =
+            centroids::read::<R, O, _>(prefetch.map(|id| index.read(id)), head, O::access(vector));
         let norm = meta_tuple.centroid_norm();
         let first = meta_tuple.first();
         vec![(Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first))]
@@ -285,14 +412,7 @@ where
             while let Some(((Reverse(_), AlwaysEqual(&mut (first, norm, head, ..))), prefetch)) =
                 heap.next_if(|(d, _)| Some(*d) > cache.peek().map(|(d, ..)| *d))
             {
-                let distance = centroids::read::<R, O, _>(
-                    prefetch,
-                    head,
-                    LAccess::new(
-                        O::Vector::unpack(vector),
-                        O::DistanceAccessor::default_with_dimension(dim),
-                    ),
-                );
+                let distance = centroids::read::<R, O, _>(prefetch, head, O::access(vector));
                 cache.push((Reverse(distance), AlwaysEqual(norm), AlwaysEqual(first)));
             }
             cache.pop()
@@ -22,6 +22,12 @@ pub type Tag = u64;
 const MAGIC: Tag = Tag::from_ne_bytes(*b"vchordrq");
 const VERSION: u64 = 1001;
 
+#[inline(always)]
+fn try_tag(source: &[u8]) -> Option<Tag> {
+    let bytes = source.get(..size_of::<Tag>())?;
+    Some(Tag::from_ne_bytes(bytes.try_into().ok()?))
+}
+
 #[inline(always)]
 fn tag(source: &[u8]) -> Tag {
     assert!(source.len() >= size_of::<Tag>());
@@ -45,6 +51,11 @@ pub trait WithWriter: Tuple {
     fn deserialize_mut(source: &mut [u8]) -> Self::Writer<'_>;
 }
 
+/// Checks that `deserialize_ref` would succeed, without panicking.
+pub trait WithValidator: Tuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str>;
+}
+
 #[repr(C, align(8))]
 #[derive(Debug, Clone, FromBytes, IntoBytes, Immutable, KnownLayout)]
 struct MetaTupleHeader {
@@ -186,6 +197,54 @@ impl WithReader for MetaTuple {
     }
 }
 
+impl WithValidator for MetaTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        match try_tag(source) {
+            Some(MAGIC) => {
+                let checker = RefChecker::new(source);
+                if Some(&VERSION) != checker.try_prefix::<u64>(size_of::<Tag>()) {
+                    return Err("bad version number");
+                }
+                let header: &MetaTupleHeader =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[u32] = checker
+                    .try_bytes(header.cells_s, header.cells_e)
+                    .ok_or("bad bytes")?;
+                let _: &[u32] = checker
+                    .try_bytes(header.vectors_first_s, header.vectors_first_e)
+                    .ok_or("bad bytes")?;
+                let _: &[u32] = checker
+                    .try_bytes(header.centroid_prefetch_s, header.centroid_prefetch_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad magic number"),
+        }
+    }
+}
+
+impl WithWriter for MetaTuple {
+    type Writer<'a> = MetaTupleWriter<'a>;
+    fn deserialize_mut(source: &mut [u8]) -> MetaTupleWriter<'_> {
+        let tag = tag(source);
+        match tag {
+            MAGIC => {
+                let mut checker = MutChecker::new(source);
+                let header: &mut MetaTupleHeader = checker.prefix(size_of::<Tag>());
+                if VERSION != header.version {
+                    panic!(
+                        "deserialization: bad version number; {}",
+                        "after upgrading VectorChord, please use REINDEX to rebuild the index."
+                    );
+                }
+                let cells = checker.bytes(header.cells_s, header.cells_e);
+                MetaTupleWriter { cells }
+            }
+            _ => panic!("deserialization: bad magic number"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct MetaTupleReader<'a> {
     header: &'a MetaTupleHeader,
@@ -195,6 +254,9 @@ pub struct MetaTupleReader<'a> {
 }
 
 impl<'a> MetaTupleReader<'a> {
+    pub fn version(self) -> u64 {
+        self.header.version
+    }
     pub fn dim(self) -> u32 {
         self.header.dim
     }
@@ -233,6 +295,17 @@ impl<'a> MetaTupleReader<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct MetaTupleWriter<'a> {
+    cells: &'a mut [u32],
+}
+
+impl MetaTupleWriter<'_> {
+    pub fn cells(&mut self) -> &mut [u32] {
+        self.cells
+    }
+}
+
 #[repr(C, align(8))]
 #[derive(Debug, Clone, FromBytes, IntoBytes, Immutable, KnownLayout)]
 struct FreepagesTupleHeader {
@@ -254,6 +327,35 @@ impl Tuple for FreepagesTuple {
     }
 }
 
+impl WithReader for FreepagesTuple {
+    type Reader<'a> = FreepagesTupleReader<'a>;
+
+    fn deserialize_ref(source: &[u8]) -> FreepagesTupleReader<'_> {
+        let checker = RefChecker::new(source);
+        let header = checker.prefix(0_u16);
+        FreepagesTupleReader { header }
+    }
+}
+
+impl WithValidator for FreepagesTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        let _: &FreepagesTupleHeader = checker.try_prefix(0_u16).ok_or("bad bytes")?;
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FreepagesTupleReader<'a> {
+    header: &'a FreepagesTupleHeader,
+}
+
+impl FreepagesTupleReader<'_> {
+    pub fn first(self) -> u32 {
+        self.header.first
+    }
+}
+
 impl WithWriter for FreepagesTuple {
     type Writer<'a> = FreepagesTupleWriter<'a>;
 
@@ -389,6 +491,32 @@ impl<V: Vector> WithReader for CentroidTuple<V> {
     }
 }
 
+impl<V: Vector> WithValidator for CentroidTuple<V> {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        match try_tag(source) {
+            Some(0) => {
+                let header: &CentroidTupleHeader0 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &V::Metadata = checker.try_prefix(header.metadata_s).ok_or("bad bytes")?;
+                let _: &[V::Element] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            Some(1) => {
+                let header: &CentroidTupleHeader1 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[V::Element] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad bytes"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct CentroidTupleReader0<'a, V: Vector> {
     #[allow(dead_code)]
@@ -559,6 +687,32 @@ impl<V: Vector> WithReader for VectorTuple<V> {
     }
 }
 
+impl<V: Vector> WithValidator for VectorTuple<V> {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        match try_tag(source) {
+            Some(0) => {
+                let header: &VectorTupleHeader0 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &V::Metadata = checker.try_prefix(header.metadata_s).ok_or("bad bytes")?;
+                let _: &[V::Element] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            Some(1) => {
+                let header: &VectorTupleHeader1 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[V::Element] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad bytes"),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct VectorTupleReader0<'a, V: Vector> {
     header: &'a VectorTupleHeader0,
@@ -722,6 +876,31 @@ impl WithReader for DirectoryTuple {
     }
 }
 
+impl WithValidator for DirectoryTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        match try_tag(source) {
+            Some(0) => {
+                let header: &DirectoryTupleHeader0 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[u32] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            Some(1) => {
+                let header: &DirectoryTupleHeader1 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[u32] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad bytes"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum DirectoryTupleReader<'a> {
     _0(DirectoryTupleReader0<'a>),
@@ -923,6 +1102,37 @@ impl WithReader for H1Tuple {
     }
 }
 
+impl WithValidator for H1Tuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        match try_tag(source) {
+            Some(0) => {
+                let header: &H1TupleHeader0 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[[u32; 32]] = checker
+                    .try_bytes(header.prefetch_s, header.prefetch_e)
+                    .ok_or("bad bytes")?;
+                let _: &[[u8; 16]] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                if header.len > 32 {
+                    return Err("bad bytes");
+                }
+                Ok(())
+            }
+            Some(1) => {
+                let header: &H1TupleHeader1 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[[u8; 16]] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad bytes"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum H1TupleReader<'a> {
     _0(H1TupleReader0<'a>),
@@ -1042,6 +1252,17 @@ impl WithReader for JumpTuple {
     }
 }
 
+impl WithValidator for JumpTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        let header: &JumpTupleHeader = checker.try_prefix(0_u16).ok_or("bad bytes")?;
+        let _: &[u32] = checker
+            .try_bytes(header.centroid_prefetch_s, header.centroid_prefetch_e)
+            .ok_or("bad bytes")?;
+        Ok(())
+    }
+}
+
 impl WithWriter for JumpTuple {
     type Writer<'a> = JumpTupleWriter<'a>;
     fn deserialize_mut(source: &mut [u8]) -> JumpTupleWriter<'_> {
@@ -1258,6 +1479,34 @@ impl WithReader for FrozenTuple {
     }
 }
 
+impl WithValidator for FrozenTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        match try_tag(source) {
+            Some(0) => {
+                let header: &FrozenTupleHeader0 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[[u32; 32]] = checker
+                    .try_bytes(header.prefetch_s, header.prefetch_e)
+                    .ok_or("bad bytes")?;
+                let _: &[[u8; 16]] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            Some(1) => {
+                let header: &FrozenTupleHeader1 =
+                    checker.try_prefix(size_of::<Tag>()).ok_or("bad bytes")?;
+                let _: &[[u8; 16]] = checker
+                    .try_bytes(header.elements_s, header.elements_e)
+                    .ok_or("bad bytes")?;
+                Ok(())
+            }
+            _ => Err("bad bytes"),
+        }
+    }
+}
+
 impl WithWriter for FrozenTuple {
     type Writer<'a> = FrozenTupleWriter<'a>;
 
@@ -1434,6 +1683,20 @@ impl WithReader for AppendableTuple {
     }
 }
 
+impl WithValidator for AppendableTuple {
+    fn validate(source: &[u8]) -> Result<(), &'static str> {
+        let checker = RefChecker::new(source);
+        let header: &AppendableTupleHeader = checker.try_prefix(0_u16).ok_or("bad bytes")?;
+        let _: &[u32] = checker
+            .try_bytes(header.prefetch_s, header.prefetch_e)
+            .ok_or("bad bytes")?;
+        let _: &[u64] = checker
+            .try_bytes(header.elements_s, header.elements_e)
+            .ok_or("bad bytes")?;
+        Ok(())
+    }
+}
+
 impl WithWriter for AppendableTuple {
     type Writer<'a> = AppendableTupleWriter<'a>;
 
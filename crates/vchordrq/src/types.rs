@@ -15,8 +15,10 @@
 use serde::{Deserialize, Serialize};
 use simd::f16;
 use validator::{Validate, ValidationError};
+use vector::bvect::{BVectBorrowed, BVectOwned};
 use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
 use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
+use vector::svect::{SVectBorrowed, SVectOwned};
 use vector::vect::{VectBorrowed, VectOwned};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -59,6 +61,8 @@ pub enum OwnedVector {
     Vecf16(VectOwned<f16>),
     Rabitq8(Rabitq8Owned),
     Rabitq4(Rabitq4Owned),
+    Svecf32(SVectOwned<f32>),
+    Bit(BVectOwned),
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -67,6 +71,8 @@ pub enum BorrowedVector<'a> {
     Vecf16(VectBorrowed<'a, f16>),
     Rabitq8(Rabitq8Borrowed<'a>),
     Rabitq4(Rabitq4Borrowed<'a>),
+    Svecf32(SVectBorrowed<'a, f32>),
+    Bit(BVectBorrowed<'a>),
 }
 
 #[repr(u8)]
@@ -74,6 +80,8 @@ pub enum BorrowedVector<'a> {
 pub enum DistanceKind {
     L2S,
     Dot,
+    Hamming,
+    Jaccard,
 }
 
 #[repr(u8)]
@@ -83,6 +91,8 @@ pub enum VectorKind {
     Vecf16,
     Rabitq8,
     Rabitq4,
+    Svecf32,
+    Bit,
 }
 
 impl VectorKind {
@@ -92,6 +102,8 @@ impl VectorKind {
             VectorKind::Vecf16 => 16,
             VectorKind::Rabitq8 => 8,
             VectorKind::Rabitq4 => 8,
+            VectorKind::Svecf32 => 32,
+            VectorKind::Bit => 1,
         }
     }
 }
@@ -0,0 +1,297 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::Opaque;
+use crate::operator::Operator;
+use crate::tuples::*;
+use index::relation::{Page, RelationRead};
+use std::collections::{HashMap, HashSet};
+use std::num::NonZero;
+
+pub struct Problem {
+    pub page: Option<u32>,
+    pub message: String,
+}
+
+struct Verifier<'r, R, C> {
+    index: &'r R,
+    number_of_pages: u32,
+    check: C,
+    owners: HashMap<u32, &'static str>,
+    problems: Vec<Problem>,
+}
+
+impl<R: RelationRead, C: Fn()> Verifier<'_, R, C>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    fn report(&mut self, page: Option<u32>, message: String) {
+        self.problems.push(Problem { page, message });
+    }
+
+    /// Follows the `next` chain starting at `first`, which is referenced by the
+    /// page `from`, and returns the pages that belong to the chain.
+    fn walk(&mut self, kind: &'static str, first: u32, from: u32) -> Vec<u32> {
+        let mut pages = Vec::new();
+        let mut visited = HashSet::new();
+        let mut previous = from;
+        let mut current = first;
+        while current != u32::MAX {
+            (self.check)();
+            if current >= self.number_of_pages {
+                self.report(
+                    Some(previous),
+                    format!("the {kind} references page {current}, which is out of range"),
+                );
+                break;
+            }
+            if !visited.insert(current) {
+                self.report(
+                    Some(previous),
+                    format!("the {kind} starting at page {first} does not terminate"),
+                );
+                break;
+            }
+            if let Some(owner) = self.owners.get(&current).copied() {
+                self.report(
+                    Some(current),
+                    format!("the page is reachable from both the {owner} and the {kind}"),
+                );
+                break;
+            }
+            pages.push(current);
+            previous = current;
+            current = self.index.read(current).get_opaque().next;
+        }
+        for &page in pages.iter() {
+            self.owners.insert(page, kind);
+        }
+        pages
+    }
+
+    /// Checks every tuple on the pages with `T::validate`, and passes the valid
+    /// ones to `f`.
+    fn tuples<T: WithValidator>(
+        &mut self,
+        kind: &'static str,
+        pages: &[u32],
+        f: impl FnMut(&[u8]),
+    ) {
+        self.validate::<T>(kind, pages, false, f);
+    }
+
+    /// Like `tuples`, but tuples may have been deleted.
+    fn live_tuples<T: WithValidator>(
+        &mut self,
+        kind: &'static str,
+        pages: &[u32],
+        f: impl FnMut(&[u8]),
+    ) {
+        self.validate::<T>(kind, pages, true, f);
+    }
+
+    fn validate<T: WithValidator>(
+        &mut self,
+        kind: &'static str,
+        pages: &[u32],
+        deletable: bool,
+        mut f: impl FnMut(&[u8]),
+    ) {
+        for &page in pages {
+            (self.check)();
+            let guard = self.index.read(page);
+            for i in 1..=guard.len() {
+                let Some(bytes) = guard.get(i) else {
+                    if !deletable {
+                        self.report(Some(page), format!("the {kind} tuple {i} does not exist"));
+                    }
+                    continue;
+                };
+                if let Err(e) = T::validate(bytes) {
+                    self.report(
+                        Some(page),
+                        format!("the {kind} tuple {i} is corrupted: {e}"),
+                    );
+                    continue;
+                }
+                f(bytes);
+            }
+        }
+    }
+}
+
+/// Checks the structure of the index without panicking on corrupted data. The
+/// payloads of all index entries are passed to `callback`.
+pub fn verify<R: RelationRead, O: Operator>(
+    index: &R,
+    number_of_pages: u32,
+    check: impl Fn(),
+    mut callback: impl FnMut(NonZero<u64>),
+) -> Vec<Problem>
+where
+    R::Page: Page<Opaque = Opaque>,
+{
+    let mut verifier = Verifier {
+        index,
+        number_of_pages,
+        check,
+        owners: HashMap::new(),
+        problems: Vec::new(),
+    };
+    if number_of_pages == 0 {
+        verifier.report(None, "the meta page does not exist".to_string());
+        return verifier.problems;
+    }
+    verifier.owners.insert(0, "meta page");
+
+    let meta_guard = index.read(0);
+    let Some(meta_bytes) = meta_guard.get(1) else {
+        verifier.report(Some(0), "the meta tuple does not exist".to_string());
+        return verifier.problems;
+    };
+    if let Err(e) = MetaTuple::validate(meta_bytes) {
+        verifier.report(Some(0), format!("the meta tuple is corrupted: {e}"));
+        return verifier.problems;
+    }
+    let meta_tuple = MetaTuple::deserialize_ref(meta_bytes);
+    let height_of_root = meta_tuple.height_of_root();
+    let centroids_first = meta_tuple.centroids_first();
+    let vectors_first = meta_tuple.vectors_first().to_vec();
+    let freepages_first = meta_tuple.freepages_first();
+    let first = meta_tuple.first();
+    drop(meta_guard);
+
+    let centroids = verifier.walk("centroid tape", centroids_first, 0);
+    verifier.tuples::<CentroidTuple<O::Vector>>("centroid", &centroids, |_| ());
+    for vectors_first in vectors_first {
+        let vectors = verifier.walk("vector tape", vectors_first, 0);
+        verifier.live_tuples::<VectorTuple<O::Vector>>("vector", &vectors, |_| ());
+    }
+    let freepages = verifier.walk("freepages tape", freepages_first, 0);
+    let mut free_first = None;
+    verifier.tuples::<FreepagesTuple>("freepages", &freepages[..freepages.len().min(1)], |bytes| {
+        free_first = Some(FreepagesTuple::deserialize_ref(bytes).first());
+    });
+
+    let mut state = vec![(first, 0_u32)];
+    for _ in (1..height_of_root).rev() {
+        let mut results = Vec::new();
+        for (first, from) in state {
+            let pages = verifier.walk("h1 tape", first, from);
+            for page in pages {
+                verifier.tuples::<H1Tuple>("h1", &[page], |bytes| {
+                    if let H1TupleReader::_0(tuple) = H1Tuple::deserialize_ref(bytes) {
+                        for j in 0..tuple.len() as usize {
+                            results.push((tuple.first()[j], page));
+                        }
+                    }
+                });
+            }
+        }
+        state = results;
+    }
+
+    for (first, from) in state {
+        let pages = verifier.walk("jump tape", first, from);
+        let Some(&head) = pages.first() else {
+            continue;
+        };
+        let jump_guard = index.read(head);
+        let jump = match jump_guard.get(1).map(JumpTuple::validate) {
+            Some(Ok(())) => {
+                let jump_tuple = JumpTuple::deserialize_ref(jump_guard.get(1).unwrap());
+                Some((
+                    jump_tuple.directory_first(),
+                    jump_tuple.frozen_first(),
+                    jump_tuple.appendable_first(),
+                ))
+            }
+            Some(Err(e)) => {
+                verifier.report(
+                    Some(from),
+                    format!(
+                        "the page {head} is referenced as a jump tuple, but it is corrupted: {e}"
+                    ),
+                );
+                None
+            }
+            None => {
+                verifier.report(
+                    Some(from),
+                    format!("the page {head} is referenced as a jump tuple, but it is empty"),
+                );
+                None
+            }
+        };
+        drop(jump_guard);
+        let Some((directory_first, frozen_first, appendable_first)) = jump else {
+            continue;
+        };
+        let directory = verifier.walk("directory tape", directory_first, head);
+        verifier.tuples::<DirectoryTuple>("directory", &directory, |_| ());
+        let frozen = verifier.walk("frozen tape", frozen_first, head);
+        verifier.tuples::<FrozenTuple>("frozen", &frozen, |bytes| {
+            if let FrozenTupleReader::_0(tuple) = FrozenTuple::deserialize_ref(bytes) {
+                tuple
+                    .payload()
+                    .iter()
+                    .flatten()
+                    .copied()
+                    .for_each(&mut callback);
+            }
+        });
+        let appendable = verifier.walk("appendable tape", appendable_first, head);
+        verifier.tuples::<AppendableTuple>("appendable", &appendable, |bytes| {
+            if let Some(payload) = AppendableTuple::deserialize_ref(bytes).payload() {
+                callback(payload);
+            }
+        });
+    }
+
+    // free pages are checked last, so any page that is still in use is reported
+    if let Some(free_first) = free_first {
+        let mut previous = freepages[0];
+        let mut visited = HashSet::new();
+        let mut current = free_first;
+        while current != u32::MAX {
+            (verifier.check)();
+            if current >= number_of_pages {
+                verifier.report(
+                    Some(previous),
+                    format!("the free page list references page {current}, which is out of range"),
+                );
+                break;
+            }
+            if !visited.insert(current) {
+                verifier.report(
+                    Some(previous),
+                    "the free page list does not terminate".to_string(),
+                );
+                break;
+            }
+            if let Some(owner) = verifier.owners.get(&current).copied() {
+                verifier.report(
+                    Some(current),
+                    format!(
+                        "the page is in the free page list, but it is referenced by the {owner}"
+                    ),
+                );
+            }
+            previous = current;
+            current = index.read(current).get_opaque().next;
+        }
+    }
+
+    verifier.problems
+}
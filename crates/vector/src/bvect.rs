@@ -137,6 +137,31 @@ impl<'a> BVectBorrowed<'a> {
             }
         })
     }
+
+    /// Maps the bits to a 0/1 vector and rotates it, so that it can be
+    /// quantized by RaBitQ.
+    #[inline(always)]
+    pub fn project(self) -> Vec<f32> {
+        let mut result = self
+            .iter()
+            .map(|x| if x { 1.0_f32 } else { 0.0_f32 })
+            .collect::<Vec<_>>();
+        rabitq::rotate::rotate_inplace(&mut result);
+        result
+    }
+}
+
+// The squared Euclidean distance between 0/1 vectors is the Hamming distance `h`,
+// and `s` is the sum of the popcounts of both vectors. The Jaccard distance is
+// `2h / (s + h)`, which is monotonic in `h`.
+#[inline(always)]
+pub fn jaccard_from_hamming(s: f32, h: f32) -> f32 {
+    if s > 0.0 {
+        let h = h.clamp(0.0, s);
+        2.0 * h / (s + h)
+    } else {
+        1.0
+    }
 }
 
 impl VectorBorrowed for BVectBorrowed<'_> {
@@ -182,6 +207,9 @@ impl VectorBorrowed for BVectBorrowed<'_> {
     #[inline(always)]
     fn operator_jaccard(self, rhs: Self) -> Distance {
         let (and, or) = simd::bit::reduce_sum_of_and_or(self.data, rhs.data);
+        if and == 0 {
+            return Distance::from(1.0_f32);
+        }
         Distance::from(1.0 - (and as f32 / or as f32))
     }
 
@@ -254,3 +282,28 @@ impl PartialOrd for BVectBorrowed<'_> {
         Some(Ordering::Equal)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn jaccard() {
+        let zero = BVectOwned::new(70, vec![0, 0]);
+        let a = BVectOwned::new(70, vec![0b1011, 1 << 5]);
+        let b = BVectOwned::new(70, vec![0b0110, 1 << 5]);
+        let distance = |l: &BVectOwned, r: &BVectOwned| {
+            l.as_borrowed().operator_jaccard(r.as_borrowed()).to_f32()
+        };
+        assert_eq!(distance(&zero, &zero), 1.0);
+        assert_eq!(distance(&zero, &a), 1.0);
+        assert_eq!(distance(&a, &a), 0.0);
+        assert_eq!(distance(&a, &b), 1.0 - 2.0 / 5.0);
+        for (l, r) in [(&zero, &zero), (&zero, &a), (&a, &a), (&a, &b)] {
+            let s = (l.as_borrowed().iter().filter(|&x| x).count()
+                + r.as_borrowed().iter().filter(|&x| x).count()) as f32;
+            let h = l.as_borrowed().operator_hamming(r.as_borrowed()).to_f32();
+            assert!((jaccard_from_hamming(s, h) - distance(l, r)).abs() < 1e-6);
+        }
+    }
+}
@@ -0,0 +1,29 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use pgrx::pg_sys::Datum;
+use std::num::NonZero;
+
+/// Reads an attribute of a composite datum without checking its type.
+///
+/// `PgHeapTuple::get_by_index` rejects arrays of the vector types, since they
+/// do not report a type oid, so array attributes are read with this instead.
+pub unsafe fn get_by_index(composite: Datum, attno: NonZero<usize>) -> Option<Datum> {
+    unsafe {
+        let header = pgrx::pg_sys::pg_detoast_datum(composite.cast_mut_ptr()).cast();
+        let mut is_null = true;
+        let datum = pgrx::pg_sys::GetAttributeByNum(header, attno.get() as _, &mut is_null);
+        (!is_null).then_some(datum)
+    }
+}
@@ -0,0 +1,254 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use pgrx::datum::{FromDatum, IntoDatum};
+use pgrx::pg_sys::{Datum, Oid};
+use pgrx::pgrx_sql_entity_graph::metadata::*;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use vector::bvect::{BVECTOR_WIDTH, BVectBorrowed, BVectOwned};
+use vector::{VectorBorrowed, VectorOwned};
+
+// PostgreSQL stores bits from the most significant bit of the first byte,
+// while binary vectors store bits from the least significant bit of the first word.
+#[repr(C)]
+struct BitHeader {
+    varlena: u32,
+    len: u32,
+    elements: [u8; 0],
+}
+
+impl BitHeader {
+    fn size_of(len: usize) -> usize {
+        size_of::<Self>() + len.div_ceil(8)
+    }
+    unsafe fn to_owned(this: NonNull<Self>) -> BVectOwned {
+        unsafe {
+            let this = this.as_ptr();
+            let dim = (&raw const (*this).len).read();
+            let bytes = std::slice::from_raw_parts(
+                (&raw const (*this).elements).cast::<u8>(),
+                (dim as usize).div_ceil(8),
+            );
+            if !(1..=65535).contains(&dim) {
+                pgrx::error!("bit string with length {dim} is not supported");
+            }
+            let mut data = vec![0_u64; dim.div_ceil(BVECTOR_WIDTH) as usize];
+            for (i, &byte) in bytes.iter().enumerate() {
+                data[i / 8] |= (byte.reverse_bits() as u64) << (i % 8 * 8);
+            }
+            BVectOwned::new(dim, data)
+        }
+    }
+}
+
+pub struct BitInput<'a>(BVectOwned, PhantomData<&'a ()>);
+
+impl BitInput<'_> {
+    unsafe fn from_ptr(p: NonNull<BitHeader>) -> Self {
+        let q = unsafe {
+            NonNull::new(pgrx::pg_sys::pg_detoast_datum(p.as_ptr().cast()).cast()).unwrap()
+        };
+        let vector = unsafe {
+            let varlena = q.cast::<u32>().read();
+            #[cfg(target_endian = "big")]
+            let size = varlena as usize;
+            #[cfg(target_endian = "little")]
+            let size = varlena as usize >> 2;
+            let len = q.byte_add(4).cast::<u32>().read();
+            assert_eq!(BitHeader::size_of(len as _), size);
+            BitHeader::to_owned(q)
+        };
+        if p != q {
+            unsafe {
+                pgrx::pg_sys::pfree(q.as_ptr().cast());
+            }
+        }
+        BitInput(vector, PhantomData)
+    }
+    pub fn as_borrowed(&self) -> BVectBorrowed<'_> {
+        self.0.as_borrowed()
+    }
+}
+
+pub struct BitOutput(BVectOwned);
+
+impl BitOutput {
+    unsafe fn from_ptr(p: NonNull<BitHeader>) -> Self {
+        let q = unsafe {
+            NonNull::new(pgrx::pg_sys::pg_detoast_datum(p.as_ptr().cast()).cast()).unwrap()
+        };
+        let vector = unsafe {
+            let varlena = q.cast::<u32>().read();
+            #[cfg(target_endian = "big")]
+            let size = varlena as usize;
+            #[cfg(target_endian = "little")]
+            let size = varlena as usize >> 2;
+            let len = q.byte_add(4).cast::<u32>().read();
+            assert_eq!(BitHeader::size_of(len as _), size);
+            BitHeader::to_owned(q)
+        };
+        if p != q {
+            unsafe {
+                pgrx::pg_sys::pfree(q.as_ptr().cast());
+            }
+        }
+        Self(vector)
+    }
+    pub fn new(vector: BVectBorrowed<'_>) -> Self {
+        Self(vector.own())
+    }
+    pub fn as_borrowed(&self) -> BVectBorrowed<'_> {
+        self.0.as_borrowed()
+    }
+    fn into_raw(self) -> *mut BitHeader {
+        let vector = self.0.as_borrowed();
+        let dim = vector.dim();
+        let data = vector.data();
+        unsafe {
+            let size = BitHeader::size_of(dim as _);
+
+            let ptr = pgrx::pg_sys::palloc0(size) as *mut BitHeader;
+            // SET_VARSIZE_4B
+            #[cfg(target_endian = "big")]
+            (&raw mut (*ptr).varlena).write((size as u32) & 0x3FFFFFFF);
+            #[cfg(target_endian = "little")]
+            (&raw mut (*ptr).varlena).write((size << 2) as u32);
+            (&raw mut (*ptr).len).write(dim);
+            let elements = (&raw mut (*ptr).elements).cast::<u8>();
+            for i in 0..(dim as usize).div_ceil(8) {
+                let byte = (data[i / 8] >> (i % 8 * 8)) as u8;
+                elements.add(i).write(byte.reverse_bits());
+            }
+            ptr
+        }
+    }
+}
+
+// FromDatum
+
+impl FromDatum for BitInput<'_> {
+    unsafe fn from_polymorphic_datum(datum: Datum, is_null: bool, _typoid: Oid) -> Option<Self> {
+        if is_null {
+            None
+        } else {
+            let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+            unsafe { Some(Self::from_ptr(ptr)) }
+        }
+    }
+}
+
+impl FromDatum for BitOutput {
+    unsafe fn from_polymorphic_datum(datum: Datum, is_null: bool, _typoid: Oid) -> Option<Self> {
+        if is_null {
+            None
+        } else {
+            let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+            unsafe { Some(Self::from_ptr(ptr)) }
+        }
+    }
+}
+
+// IntoDatum
+
+impl IntoDatum for BitOutput {
+    fn into_datum(self) -> Option<Datum> {
+        Some(Datum::from(self.into_raw()))
+    }
+
+    fn type_oid() -> Oid {
+        Oid::INVALID
+    }
+
+    fn is_compatible_with(_: Oid) -> bool {
+        true
+    }
+}
+
+// UnboxDatum
+
+unsafe impl<'a> pgrx::datum::UnboxDatum for BitInput<'a> {
+    type As<'src>
+        = BitInput<'src>
+    where
+        'a: 'src;
+    #[inline]
+    unsafe fn unbox<'src>(datum: pgrx::datum::Datum<'src>) -> Self::As<'src>
+    where
+        Self: 'src,
+    {
+        let datum = datum.sans_lifetime();
+        let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+        unsafe { Self::from_ptr(ptr) }
+    }
+}
+
+unsafe impl pgrx::datum::UnboxDatum for BitOutput {
+    type As<'src> = BitOutput;
+    #[inline]
+    unsafe fn unbox<'src>(datum: pgrx::datum::Datum<'src>) -> Self::As<'src>
+    where
+        Self: 'src,
+    {
+        let datum = datum.sans_lifetime();
+        let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+        unsafe { Self::from_ptr(ptr) }
+    }
+}
+
+// SqlTranslatable
+
+unsafe impl SqlTranslatable for BitInput<'_> {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::As(String::from("bit")))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::As(String::from("bit"))))
+    }
+}
+
+unsafe impl SqlTranslatable for BitOutput {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::As(String::from("bit")))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::As(String::from("bit"))))
+    }
+}
+
+// ArgAbi
+
+unsafe impl<'fcx> pgrx::callconv::ArgAbi<'fcx> for BitInput<'fcx> {
+    unsafe fn unbox_arg_unchecked(arg: pgrx::callconv::Arg<'_, 'fcx>) -> Self {
+        let index = arg.index();
+        unsafe {
+            arg.unbox_arg_using_from_datum()
+                .unwrap_or_else(|| panic!("argument {index} must not be null"))
+        }
+    }
+}
+
+// BoxRet
+
+unsafe impl pgrx::callconv::BoxRet for BitOutput {
+    unsafe fn box_into<'fcx>(
+        self,
+        fcinfo: &mut pgrx::callconv::FcInfo<'fcx>,
+    ) -> pgrx::datum::Datum<'fcx> {
+        match self.into_datum() {
+            Some(datum) => unsafe { fcinfo.return_raw_datum(datum) },
+            None => fcinfo.return_null(),
+        }
+    }
+}
@@ -0,0 +1,268 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use pgrx::datum::{FromDatum, IntoDatum};
+use pgrx::pg_sys::{Datum, Oid};
+use pgrx::pgrx_sql_entity_graph::metadata::*;
+use std::marker::PhantomData;
+use std::ptr::NonNull;
+use vector::VectorBorrowed;
+use vector::svect::SVectBorrowed;
+
+#[repr(C)]
+struct SparsevecHeader {
+    varlena: u32,
+    dim: u32,
+    len: u32,
+    unused: u32,
+    elements: [u32; 0],
+}
+
+impl SparsevecHeader {
+    fn size_of(len: usize) -> usize {
+        if len > 1_048_576 {
+            panic!("vector is too large");
+        }
+        size_of::<Self>() + (size_of::<u32>() + size_of::<f32>()) * len
+    }
+    unsafe fn as_borrowed<'a>(this: NonNull<Self>) -> SVectBorrowed<'a, f32> {
+        unsafe {
+            let this = this.as_ptr();
+            let dim = (&raw const (*this).dim).read();
+            let len = (&raw const (*this).len).read() as usize;
+            let indexes = (&raw const (*this).elements).cast::<u32>();
+            let values = indexes.add(len).cast::<f32>();
+            SVectBorrowed::new(
+                dim,
+                std::slice::from_raw_parts(indexes, len),
+                std::slice::from_raw_parts(values, len),
+            )
+        }
+    }
+}
+
+pub struct SparsevecInput<'a>(NonNull<SparsevecHeader>, PhantomData<&'a ()>, bool);
+
+impl SparsevecInput<'_> {
+    unsafe fn from_ptr(p: NonNull<SparsevecHeader>) -> Self {
+        let q = unsafe {
+            NonNull::new(pgrx::pg_sys::pg_detoast_datum(p.as_ptr().cast()).cast()).unwrap()
+        };
+        unsafe {
+            let varlena = q.cast::<u32>().read();
+            #[cfg(target_endian = "big")]
+            let size = varlena as usize;
+            #[cfg(target_endian = "little")]
+            let size = varlena as usize >> 2;
+            let len = q.byte_add(8).cast::<u32>().read();
+            assert_eq!(SparsevecHeader::size_of(len as _), size);
+            let unused = q.byte_add(12).cast::<u32>().read();
+            assert_eq!(unused, 0);
+        }
+        SparsevecInput(q, PhantomData, p != q)
+    }
+    pub fn as_borrowed(&self) -> SVectBorrowed<'_, f32> {
+        unsafe { SparsevecHeader::as_borrowed(self.0) }
+    }
+}
+
+impl Drop for SparsevecInput<'_> {
+    fn drop(&mut self) {
+        if self.2 {
+            unsafe {
+                pgrx::pg_sys::pfree(self.0.as_ptr().cast());
+            }
+        }
+    }
+}
+
+pub struct SparsevecOutput(NonNull<SparsevecHeader>);
+
+impl SparsevecOutput {
+    unsafe fn from_ptr(p: NonNull<SparsevecHeader>) -> Self {
+        let q = unsafe {
+            NonNull::new(pgrx::pg_sys::pg_detoast_datum_copy(p.as_ptr().cast()).cast()).unwrap()
+        };
+        unsafe {
+            let varlena = q.cast::<u32>().read();
+            #[cfg(target_endian = "big")]
+            let size = varlena as usize;
+            #[cfg(target_endian = "little")]
+            let size = varlena as usize >> 2;
+            let len = q.byte_add(8).cast::<u32>().read();
+            assert_eq!(SparsevecHeader::size_of(len as _), size);
+            let unused = q.byte_add(12).cast::<u32>().read();
+            assert_eq!(unused, 0);
+        }
+        Self(q)
+    }
+    pub fn new(vector: SVectBorrowed<'_, f32>) -> Self {
+        unsafe {
+            let indexes = vector.indexes();
+            let values = vector.values();
+            let size = SparsevecHeader::size_of(indexes.len());
+
+            let ptr = pgrx::pg_sys::palloc0(size) as *mut SparsevecHeader;
+            // SET_VARSIZE_4B
+            #[cfg(target_endian = "big")]
+            (&raw mut (*ptr).varlena).write((size as u32) & 0x3FFFFFFF);
+            #[cfg(target_endian = "little")]
+            (&raw mut (*ptr).varlena).write((size << 2) as u32);
+            (&raw mut (*ptr).dim).write(vector.dim());
+            (&raw mut (*ptr).len).write(indexes.len() as _);
+            (&raw mut (*ptr).unused).write(0);
+            let elements = (&raw mut (*ptr).elements).cast::<u32>();
+            std::ptr::copy_nonoverlapping(indexes.as_ptr(), elements, indexes.len());
+            std::ptr::copy_nonoverlapping(
+                values.as_ptr(),
+                elements.add(indexes.len()).cast::<f32>(),
+                values.len(),
+            );
+            Self(NonNull::new(ptr).unwrap())
+        }
+    }
+    pub fn as_borrowed(&self) -> SVectBorrowed<'_, f32> {
+        unsafe { SparsevecHeader::as_borrowed(self.0) }
+    }
+    fn into_raw(self) -> *mut SparsevecHeader {
+        let result = self.0.as_ptr();
+        std::mem::forget(self);
+        result
+    }
+}
+
+impl Drop for SparsevecOutput {
+    fn drop(&mut self) {
+        unsafe {
+            pgrx::pg_sys::pfree(self.0.as_ptr().cast());
+        }
+    }
+}
+
+// FromDatum
+
+impl FromDatum for SparsevecInput<'_> {
+    unsafe fn from_polymorphic_datum(datum: Datum, is_null: bool, _typoid: Oid) -> Option<Self> {
+        if is_null {
+            None
+        } else {
+            let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+            unsafe { Some(Self::from_ptr(ptr)) }
+        }
+    }
+}
+
+impl FromDatum for SparsevecOutput {
+    unsafe fn from_polymorphic_datum(datum: Datum, is_null: bool, _typoid: Oid) -> Option<Self> {
+        if is_null {
+            None
+        } else {
+            let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+            unsafe { Some(Self::from_ptr(ptr)) }
+        }
+    }
+}
+
+// IntoDatum
+
+impl IntoDatum for SparsevecOutput {
+    fn into_datum(self) -> Option<Datum> {
+        Some(Datum::from(self.into_raw()))
+    }
+
+    fn type_oid() -> Oid {
+        Oid::INVALID
+    }
+
+    fn is_compatible_with(_: Oid) -> bool {
+        true
+    }
+}
+
+// UnboxDatum
+
+unsafe impl<'a> pgrx::datum::UnboxDatum for SparsevecInput<'a> {
+    type As<'src>
+        = SparsevecInput<'src>
+    where
+        'a: 'src;
+    #[inline]
+    unsafe fn unbox<'src>(datum: pgrx::datum::Datum<'src>) -> Self::As<'src>
+    where
+        Self: 'src,
+    {
+        let datum = datum.sans_lifetime();
+        let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+        unsafe { Self::from_ptr(ptr) }
+    }
+}
+
+unsafe impl pgrx::datum::UnboxDatum for SparsevecOutput {
+    type As<'src> = SparsevecOutput;
+    #[inline]
+    unsafe fn unbox<'src>(datum: pgrx::datum::Datum<'src>) -> Self::As<'src>
+    where
+        Self: 'src,
+    {
+        let datum = datum.sans_lifetime();
+        let ptr = NonNull::new(datum.cast_mut_ptr()).unwrap();
+        unsafe { Self::from_ptr(ptr) }
+    }
+}
+
+// SqlTranslatable
+
+unsafe impl SqlTranslatable for SparsevecInput<'_> {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::As(String::from("sparsevec")))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::As(String::from("sparsevec"))))
+    }
+}
+
+unsafe impl SqlTranslatable for SparsevecOutput {
+    fn argument_sql() -> Result<SqlMapping, ArgumentError> {
+        Ok(SqlMapping::As(String::from("sparsevec")))
+    }
+    fn return_sql() -> Result<Returns, ReturnsError> {
+        Ok(Returns::One(SqlMapping::As(String::from("sparsevec"))))
+    }
+}
+
+// ArgAbi
+
+unsafe impl<'fcx> pgrx::callconv::ArgAbi<'fcx> for SparsevecInput<'fcx> {
+    unsafe fn unbox_arg_unchecked(arg: pgrx::callconv::Arg<'_, 'fcx>) -> Self {
+        let index = arg.index();
+        unsafe {
+            arg.unbox_arg_using_from_datum()
+                .unwrap_or_else(|| panic!("argument {index} must not be null"))
+        }
+    }
+}
+
+// BoxRet
+
+unsafe impl pgrx::callconv::BoxRet for SparsevecOutput {
+    unsafe fn box_into<'fcx>(
+        self,
+        fcinfo: &mut pgrx::callconv::FcInfo<'fcx>,
+    ) -> pgrx::datum::Datum<'fcx> {
+        match self.into_datum() {
+            Some(datum) => unsafe { fcinfo.return_raw_datum(datum) },
+            None => fcinfo.return_null(),
+        }
+    }
+}
@@ -14,15 +14,20 @@
 
 mod binary_rabitq4;
 mod binary_rabitq8;
+pub mod composite;
 mod functions_rabitq4;
 mod functions_rabitq8;
+pub mod memory_bit;
 pub mod memory_halfvec;
 pub mod memory_rabitq4;
 pub mod memory_rabitq8;
+pub mod memory_sparsevec;
 pub mod memory_vector;
+mod operators_bit;
 mod operators_halfvec;
 mod operators_rabitq4;
 mod operators_rabitq8;
+mod operators_sparsevec;
 mod operators_vector;
 mod text_rabitq4;
 mod text_rabitq8;
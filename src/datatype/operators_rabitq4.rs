@@ -12,8 +12,9 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use crate::datatype::composite;
 use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
-use pgrx::datum::Array;
+use pgrx::datum::{Array, FromDatum};
 use std::num::NonZero;
 use vector::VectorBorrowed;
 use vector::rabitq4::Rabitq4Borrowed;
@@ -137,3 +138,21 @@ fn _vchord_rabitq4_operator_maxsim(
     }
     maxsim
 }
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchord_rabitq4_maxsim_threshold_in(
+    lhs: Array<'_, Rabitq4Input<'_>>,
+    rhs: pgrx::composite_type!("maxsim_threshold_rabitq4"),
+) -> bool {
+    let threshold: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty threshold at maxsim_threshold"),
+        Err(_) => unreachable!(),
+    };
+    let rhs = rhs.into_composite_datum().unwrap();
+    let query = match unsafe { composite::get_by_index(rhs, NonZero::new(1).unwrap()) } {
+        Some(s) => unsafe { Array::<Rabitq4Input>::from_datum(s, false).unwrap() },
+        None => pgrx::error!("Bad input: empty query at maxsim_threshold"),
+    };
+    _vchord_rabitq4_operator_maxsim(lhs, query) < -threshold
+}
@@ -0,0 +1,90 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::datatype::memory_sparsevec::{SparsevecInput, SparsevecOutput};
+use std::num::NonZero;
+use vector::VectorBorrowed;
+use vector::svect::SVectBorrowed;
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_sparsevec_sphere_l2_in(
+    lhs: SparsevecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_sparsevec"),
+) -> bool {
+    let center: SparsevecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = SVectBorrowed::operator_l2s(lhs, center).to_f32().sqrt();
+    d < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_sparsevec_sphere_ip_in(
+    lhs: SparsevecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_sparsevec"),
+) -> bool {
+    let center: SparsevecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = SVectBorrowed::operator_dot(lhs, center).to_f32();
+    d < radius
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_sparsevec_sphere_cosine_in(
+    lhs: SparsevecInput<'_>,
+    rhs: pgrx::composite_type!("sphere_sparsevec"),
+) -> bool {
+    let center: SparsevecOutput = match rhs.get_by_index(NonZero::new(1).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty center at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let radius: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty radius at sphere"),
+        Err(_) => unreachable!(),
+    };
+    let lhs = lhs.as_borrowed();
+    let center = center.as_borrowed();
+    if lhs.dim() != center.dim() {
+        pgrx::error!("dimension is not matched");
+    }
+    let d = SVectBorrowed::operator_cos(lhs, center).to_f32();
+    d < radius
+}
@@ -12,8 +12,10 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use crate::datatype::composite;
 use crate::datatype::memory_vector::{VectorInput, VectorOutput};
-use pgrx::datum::Array;
+use pgrx::datum::{Array, FromDatum};
+use pgrx::pg_sys::Datum;
 use std::num::NonZero;
 use vector::VectorBorrowed;
 use vector::vect::VectBorrowed;
@@ -107,3 +109,82 @@ fn _vchord_vector_operator_maxsim(
     }
     maxsim
 }
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_vector_maxsim_threshold_in(
+    lhs: Array<'_, VectorInput<'_>>,
+    rhs: pgrx::composite_type!("maxsim_threshold_vector"),
+) -> bool {
+    let threshold: f32 = match rhs.get_by_index(NonZero::new(2).unwrap()) {
+        Ok(Some(s)) => s,
+        Ok(None) => pgrx::error!("Bad input: empty threshold at maxsim_threshold"),
+        Err(_) => unreachable!(),
+    };
+    let rhs = rhs.into_composite_datum().unwrap();
+    let query = match unsafe { composite::get_by_index(rhs, NonZero::new(1).unwrap()) } {
+        Some(s) => unsafe { Array::<VectorInput>::from_datum(s, false).unwrap() },
+        None => pgrx::error!("Bad input: empty query at maxsim_threshold"),
+    };
+    _vchord_vector_operator_maxsim(lhs, query) < -threshold
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_vector_multi_query_l2(
+    lhs: VectorInput<'_>,
+    rhs: pgrx::composite_type!("multi_query_vector"),
+) -> f32 {
+    let lhs = lhs.as_borrowed();
+    multi_query(rhs.into_composite_datum().unwrap(), |query| {
+        if lhs.dim() != query.dim() {
+            pgrx::error!("dimension is not matched");
+        }
+        VectBorrowed::operator_l2s(lhs, query).to_f32().sqrt()
+    })
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_vector_multi_query_ip(
+    lhs: VectorInput<'_>,
+    rhs: pgrx::composite_type!("multi_query_vector"),
+) -> f32 {
+    let lhs = lhs.as_borrowed();
+    multi_query(rhs.into_composite_datum().unwrap(), |query| {
+        if lhs.dim() != query.dim() {
+            pgrx::error!("dimension is not matched");
+        }
+        VectBorrowed::operator_dot(lhs, query).to_f32()
+    })
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchord_vector_multi_query_cosine(
+    lhs: VectorInput<'_>,
+    rhs: pgrx::composite_type!("multi_query_vector"),
+) -> f32 {
+    let lhs = lhs.as_borrowed();
+    multi_query(rhs.into_composite_datum().unwrap(), |query| {
+        if lhs.dim() != query.dim() {
+            pgrx::error!("dimension is not matched");
+        }
+        VectBorrowed::operator_cos(lhs, query).to_f32()
+    })
+}
+
+fn multi_query(rhs: Datum, mut f: impl FnMut(VectBorrowed<'_, f32>) -> f32) -> f32 {
+    let queries = match unsafe { composite::get_by_index(rhs, NonZero::new(1).unwrap()) } {
+        Some(s) => unsafe { Array::<VectorInput>::from_datum(s, false).unwrap() },
+        None => pgrx::error!("Bad input: empty queries at multi_query"),
+    };
+    let weights = match unsafe { composite::get_by_index(rhs, NonZero::new(2).unwrap()) } {
+        Some(s) => unsafe { Array::<f32>::from_datum(s, false).unwrap() },
+        None => pgrx::error!("Bad input: empty weights at multi_query"),
+    };
+    if queries.len() != weights.len() {
+        pgrx::error!("Bad input: queries and weights are not matched at multi_query");
+    }
+    let mut distance = 0.0f32;
+    for (query, weight) in queries.iter_deny_null().zip(weights.iter_deny_null()) {
+        distance += weight * f(query.as_borrowed());
+    }
+    distance
+}
@@ -32,6 +32,9 @@ pub trait Fetcher {
         Self: 'a;
 
     fn fetch(&mut self, key: [u16; 3]) -> Option<Self::Tuple<'_>>;
+
+    /// Hints that the tuple of `key` is going to be fetched soon.
+    fn prefetch(&mut self, _key: [u16; 3]) {}
 }
 
 impl<T: Fetcher, F: FnOnce() -> T> Fetcher for LazyCell<T, F> {
@@ -43,6 +46,10 @@ impl<T: Fetcher, F: FnOnce() -> T> Fetcher for LazyCell<T, F> {
     fn fetch(&mut self, key: [u16; 3]) -> Option<Self::Tuple<'_>> {
         self.deref_mut().fetch(key)
     }
+
+    fn prefetch(&mut self, key: [u16; 3]) {
+        self.deref_mut().prefetch(key)
+    }
 }
 
 pub struct HeapFetcher {
@@ -56,6 +63,7 @@ pub struct HeapFetcher {
     values: [Datum; 32],
     is_nulls: [bool; 32],
     hack: *mut pgrx::pg_sys::IndexScanState,
+    owned: bool,
 }
 
 impl HeapFetcher {
@@ -67,6 +75,27 @@ impl HeapFetcher {
         hack: *mut pgrx::pg_sys::IndexScanState,
     ) -> Self {
         unsafe {
+            use pgrx::pg_sys::ffi::pg_guard_ffi_boundary;
+            // bitmap scans do not open the heap relation for index access methods
+            let owned = heap_relation.is_null();
+            let (heap_relation, heapfetch) = if owned {
+                let heap_relation = pgrx::pg_sys::relation_open(
+                    pgrx::pg_sys::IndexGetRelation((*index_relation).rd_id, false),
+                    pgrx::pg_sys::NoLock as _,
+                );
+                let table_am = (*heap_relation).rd_tableam;
+                if table_am.is_null() {
+                    panic!("unknown heap access method");
+                }
+                let index_fetch_begin = (*table_am)
+                    .index_fetch_begin
+                    .expect("unsupported heap access method");
+                #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+                let heapfetch = pg_guard_ffi_boundary(|| index_fetch_begin(heap_relation));
+                (heap_relation, heapfetch)
+            } else {
+                (heap_relation, heapfetch)
+            };
             let index_info = pgrx::pg_sys::BuildIndexInfo(index_relation);
             let estate = pgrx::pg_sys::CreateExecutorState();
             let econtext = pgrx::pg_sys::MakePerTupleExprContext(estate);
@@ -81,6 +110,7 @@ impl HeapFetcher {
                 values: [Datum::null(); 32],
                 is_nulls: [true; 32],
                 hack,
+                owned,
             }
         }
     }
@@ -93,6 +123,16 @@ impl Drop for HeapFetcher {
             // free common resources
             pgrx::pg_sys::ExecDropSingleTupleTableSlot(self.slot);
             pgrx::pg_sys::FreeExecutorState(self.estate);
+            // free resources of bitmap scans
+            if self.owned {
+                use pgrx::pg_sys::ffi::pg_guard_ffi_boundary;
+                let table_am = (*self.heap_relation).rd_tableam;
+                if let Some(index_fetch_end) = (*table_am).index_fetch_end {
+                    #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+                    pg_guard_ffi_boundary(|| index_fetch_end(self.heapfetch));
+                }
+                pgrx::pg_sys::relation_close(self.heap_relation, pgrx::pg_sys::NoLock as _);
+            }
         }
     }
 }
@@ -153,6 +193,18 @@ impl Fetcher for HeapFetcher {
             }
         }
     }
+
+    fn prefetch(&mut self, key: [u16; 3]) {
+        let ctid = key_to_ctid(key);
+        let block = ((ctid.ip_blkid.bi_hi as u32) << 16) | ctid.ip_blkid.bi_lo as u32;
+        unsafe {
+            pgrx::pg_sys::PrefetchBuffer(
+                self.heap_relation,
+                pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+                block,
+            );
+        }
+    }
 }
 
 pub struct HeapTuple<'a> {
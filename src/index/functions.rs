@@ -12,11 +12,14 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use crate::index::fetcher::{Fetcher, HeapFetcher, pointer_to_kv};
 use crate::index::storage::PostgresRelation;
 use crate::recorder::dump;
-use pgrx::iter::SetOfIterator;
+use pgrx::iter::{SetOfIterator, TableIterator};
+use pgrx::name;
 use pgrx::pg_sys::Oid;
 use pgrx_catalog::{PgAm, PgClass, PgClassRelkind};
+use std::ffi::CStr;
 
 #[pgrx::pg_extern(sql = "")]
 fn _vchordg_prewarm(indexrelid: Oid) -> String {
@@ -40,6 +43,106 @@ fn _vchordg_prewarm(indexrelid: Oid) -> String {
     crate::index::vchordg::dispatch::prewarm(opfamily, &index)
 }
 
+#[pgrx::pg_extern(sql = "")]
+fn _vchordg_index_info(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        name!(dim, i32),
+        name!(bits, i32),
+        name!(m, i32),
+        name!(alpha, Vec<f32>),
+        name!(ef_construction, i32),
+        name!(beam_construction, i32),
+        name!(start_page, Option<i64>),
+        name!(start_offset, Option<i32>),
+        name!(vertex_pages, i64),
+        name!(vector_pages, i64),
+    ),
+> {
+    let pg_am = PgAm::search_amname(c"vchordg").unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!("the index {:?} is not a vchordg index", pg_class.relname());
+    }
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let info = vchordg::info(&index, || {
+        pgrx::check_for_interrupts!();
+    });
+    TableIterator::once((
+        info.dim as _,
+        info.bits as _,
+        info.m as _,
+        info.alpha,
+        info.ef_construction as _,
+        info.beam_construction as _,
+        info.start.map(|(id, _)| id as _),
+        info.start.map(|(_, i)| i as _),
+        info.number_of_vertex_pages as _,
+        info.number_of_vector_pages as _,
+    ))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordg_graph_stats(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        name!(vertices, i64),
+        name!(deleted_vertices, i64),
+        name!(dangling_pointers, i64),
+        name!(unreachable_vertices, i64),
+        name!(out_degree_histogram, Vec<i64>),
+    ),
+> {
+    let pg_am = PgAm::search_amname(c"vchordg").unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!("the index {:?} is not a vchordg index", pg_class.relname());
+    }
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let opfamily = unsafe { crate::index::vchordg::opclass::opfamily(relation.raw()) };
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let number_of_pages = unsafe {
+        pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            relation.raw(),
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        )
+    };
+    let stats =
+        crate::index::vchordg::dispatch::graph_stats(opfamily, &index, number_of_pages, || {
+            pgrx::check_for_interrupts!();
+        });
+    TableIterator::once((
+        stats.number_of_vertices as _,
+        stats.number_of_deleted_vertices as _,
+        stats.number_of_dangling_pointers as _,
+        stats.number_of_unreachable_vertices as _,
+        stats.out_degrees.into_iter().map(|x| x as _).collect(),
+    ))
+}
+
 #[pgrx::pg_extern(sql = "")]
 fn _vchordrq_prewarm(indexrelid: Oid, height: i32) -> String {
     let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
@@ -62,6 +165,257 @@ fn _vchordrq_prewarm(indexrelid: Oid, height: i32) -> String {
     crate::index::vchordrq::dispatch::prewarm(opfamily, &index, height)
 }
 
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_index_info(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        name!(version, i64),
+        name!(dim, i32),
+        name!(height_of_root, i32),
+        name!(cells, Vec<i32>),
+        name!(is_residual, bool),
+        name!(rerank_in_heap, bool),
+        name!(centroid_pages, i64),
+        name!(vector_pages, i64),
+        name!(h1_pages, i64),
+        name!(jump_pages, i64),
+        name!(directory_pages, i64),
+        name!(frozen_pages, i64),
+        name!(appendable_pages, i64),
+        name!(free_pages, i64),
+    ),
+> {
+    let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!("the index {:?} is not a vchordrq index", pg_class.relname());
+    }
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    let info = vchordrq::info(&index, || {
+        pgrx::check_for_interrupts!();
+    });
+    TableIterator::once((
+        info.version as _,
+        info.dim as _,
+        info.height_of_root as _,
+        info.cells.into_iter().map(|x| x as _).collect(),
+        info.is_residual,
+        info.rerank_in_heap,
+        info.number_of_centroid_pages as _,
+        info.number_of_vector_pages as _,
+        info.number_of_h1_pages as _,
+        info.number_of_jump_pages as _,
+        info.number_of_directory_pages as _,
+        info.number_of_frozen_pages as _,
+        info.number_of_appendable_pages as _,
+        info.number_of_free_pages as _,
+    ))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_lists(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        name!(id, i32),
+        name!(parent, Option<i32>),
+        name!(live_tuples, i64),
+        name!(dead_tuples, i64),
+        name!(directory_pages, i64),
+        name!(frozen_pages, i64),
+        name!(appendable_pages, i64),
+    ),
+> {
+    let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!("the index {:?} is not a vchordrq index", pg_class.relname());
+    }
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    let _heap = Table::open(heaprelid, pgrx::pg_sys::AccessShareLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let index = unsafe { PostgresRelation::new(relation.raw()) };
+    // tuples whose heap tuples are not visible are dead until `VACUUM` removes them
+    let snapshot =
+        unsafe { pgrx::pg_sys::RegisterSnapshot(pgrx::pg_sys::GetTransactionSnapshot()) };
+    let mut fetcher = unsafe {
+        HeapFetcher::new(
+            relation.raw(),
+            std::ptr::null_mut(),
+            snapshot,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    let lists = vchordrq::lists(
+        &index,
+        |payload| fetcher.fetch(pointer_to_kv(payload).0).is_some(),
+        || {
+            pgrx::check_for_interrupts!();
+        },
+    );
+    drop(fetcher);
+    unsafe {
+        pgrx::pg_sys::UnregisterSnapshot(snapshot);
+    }
+    TableIterator::new(lists.into_iter().map(|list| {
+        (
+            list.id as _,
+            list.parent.map(|x| x as _),
+            list.number_of_live_tuples as _,
+            list.number_of_dead_tuples as _,
+            list.number_of_directory_pages as _,
+            list.number_of_frozen_pages as _,
+            list.number_of_appendable_pages as _,
+        )
+    }))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_recluster(indexrelid: Oid, options: String) {
+    let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!("the index {:?} is not a vchordrq index", pg_class.relname());
+    }
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    if !is_owner(heaprelid) {
+        pgrx::error!(
+            "must be owner of the table of the index {:?}",
+            pg_class.relname()
+        );
+    }
+    // the same lock as `VACUUM`, so that no tuple is deleted while vectors are moved
+    let heap = Table::open(heaprelid, pgrx::pg_sys::ShareUpdateExclusiveLock as _);
+    let relation = Index::open(indexrelid, pgrx::pg_sys::ShareUpdateExclusiveLock as _);
+    unsafe {
+        crate::index::vchordrq::am::recluster(heap.raw(), relation.raw(), &options);
+    }
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_verify(
+    indexrelid: Oid,
+    heapallindexed: bool,
+) -> TableIterator<'static, (name!(page, Option<i64>), name!(problem, String))> {
+    // the same lock as `CREATE INDEX`, since insertions may reuse free pages
+    verify(
+        indexrelid,
+        heapallindexed,
+        c"vchordrq",
+        pgrx::pg_sys::ShareLock as _,
+        crate::index::vchordrq::am::verify,
+    )
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordg_verify(
+    indexrelid: Oid,
+    heapallindexed: bool,
+) -> TableIterator<'static, (name!(page, Option<i64>), name!(problem, String))> {
+    // the same lock as `VACUUM`, so that insertions go on but no tuple is freed
+    verify(
+        indexrelid,
+        heapallindexed,
+        c"vchordg",
+        pgrx::pg_sys::ShareUpdateExclusiveLock as _,
+        crate::index::vchordg::am::verify,
+    )
+}
+
+fn verify(
+    indexrelid: Oid,
+    heapallindexed: bool,
+    amname: &CStr,
+    lockmode: pgrx::pg_sys::LOCKMODE,
+    verify: unsafe fn(
+        pgrx::pg_sys::Relation,
+        pgrx::pg_sys::Relation,
+        bool,
+    ) -> Vec<crate::index::verify::Problem>,
+) -> TableIterator<'static, (name!(page, Option<i64>), name!(problem, String))> {
+    let pg_am = PgAm::search_amname(amname).unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!(
+            "the index {:?} is not a {} index",
+            pg_class.relname(),
+            amname.to_string_lossy()
+        );
+    }
+    let heaprelid = unsafe { pgrx::pg_sys::IndexGetRelation(indexrelid, false) };
+    if !is_owner(heaprelid) {
+        pgrx::error!(
+            "must be owner of the table of the index {:?}",
+            pg_class.relname()
+        );
+    }
+    let heap = Table::open(heaprelid, lockmode);
+    let relation = Index::open(indexrelid, lockmode);
+    let problems = unsafe { verify(heap.raw(), relation.raw(), heapallindexed) };
+    TableIterator::new(
+        problems
+            .into_iter()
+            .map(|problem| (problem.page.map(|x| x as _), problem.message)),
+    )
+}
+
+#[cfg(any(feature = "pg14", feature = "pg15"))]
+fn is_owner(relid: Oid) -> bool {
+    unsafe { pgrx::pg_sys::pg_class_ownercheck(relid, pgrx::pg_sys::GetUserId()) }
+}
+
+#[cfg(any(feature = "pg16", feature = "pg17", feature = "pg18"))]
+fn is_owner(relid: Oid) -> bool {
+    unsafe {
+        pgrx::pg_sys::object_ownercheck(
+            pgrx::pg_sys::RelationRelationId,
+            relid,
+            pgrx::pg_sys::GetUserId(),
+        )
+    }
+}
+
 struct Index {
     raw: *mut pgrx::pg_sys::RelationData,
     lockmode: pgrx::pg_sys::LOCKMODE,
@@ -87,6 +441,31 @@ impl Drop for Index {
     }
 }
 
+struct Table {
+    raw: *mut pgrx::pg_sys::RelationData,
+    lockmode: pgrx::pg_sys::LOCKMODE,
+}
+
+impl Table {
+    fn open(relid: Oid, lockmode: pgrx::pg_sys::LOCKMASK) -> Self {
+        Self {
+            raw: unsafe { pgrx::pg_sys::table_open(relid, lockmode) },
+            lockmode,
+        }
+    }
+    fn raw(&self) -> *mut pgrx::pg_sys::RelationData {
+        self.raw
+    }
+}
+
+impl Drop for Table {
+    fn drop(&mut self) {
+        unsafe {
+            pgrx::pg_sys::table_close(self.raw, self.lockmode);
+        }
+    }
+}
+
 #[pgrx::pg_extern(sql = "")]
 fn _vchordrq_sampled_values(indexrelid: Oid) -> SetOfIterator<'static, String> {
     let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
@@ -106,5 +485,82 @@ fn _vchordrq_sampled_values(indexrelid: Oid) -> SetOfIterator<'static, String> {
     // The user must have access to the index, if not, raise an error from Postgres.
     let _relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
     let queries = dump(indexrelid.to_u32());
-    SetOfIterator::new(queries)
+    SetOfIterator::new(queries.into_iter().map(|record| record.sample))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_sampled_records(
+    indexrelid: Oid,
+) -> TableIterator<
+    'static,
+    (
+        name!(value, String),
+        name!(probes, Option<String>),
+        name!(epsilon, Option<f32>),
+        name!(max_scan_tuples, Option<i32>),
+        name!(prefilter, Option<bool>),
+        name!(limit_count, Option<i64>),
+        name!(returned_rows, i64),
+        name!(elapsed_ms, f64),
+    ),
+> {
+    let pg_am = PgAm::search_amname(c"vchordrq").unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!("the index {:?} is not a vchordrq index", pg_class.relname());
+    }
+    // The user must have access to the index, if not, raise an error from Postgres.
+    let _relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let records = dump(indexrelid.to_u32());
+    TableIterator::new(records.into_iter().map(|record| {
+        (
+            record.sample,
+            record.probes,
+            record.epsilon,
+            record.max_scan_tuples.map(|x| x as i32),
+            record.prefilter,
+            record.limit,
+            record.returned_rows as i64,
+            record.elapsed * 1000.0,
+        )
+    }))
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordrq_debug_scanned_tuples() -> i64 {
+    if !crate::index::gucs::vchordrq_debug_scanned_tuples() {
+        pgrx::error!("`vchordrq.debug_scanned_tuples` is not enabled");
+    }
+    crate::index::vchordrq::scanned_tuples() as i64
+}
+
+#[pgrx::pg_extern(sql = "")]
+fn _vchordg_sampled_values(indexrelid: Oid) -> SetOfIterator<'static, String> {
+    let pg_am = PgAm::search_amname(c"vchordg").unwrap();
+    let Some(pg_am) = pg_am.get() else {
+        pgrx::error!("vchord is not installed");
+    };
+    let pg_class = PgClass::search_reloid(indexrelid).unwrap();
+    let Some(pg_class) = pg_class.get() else {
+        pgrx::error!("the relation does not exist");
+    };
+    if pg_class.relkind() != PgClassRelkind::Index {
+        pgrx::error!("the relation {:?} is not an index", pg_class.relname());
+    }
+    if pg_class.relam() != pg_am.oid() {
+        pgrx::error!("the index {:?} is not a vchordg index", pg_class.relname());
+    }
+    // The user must have access to the index, if not, raise an error from Postgres.
+    let _relation = Index::open(indexrelid, pgrx::pg_sys::AccessShareLock as _);
+    let queries = dump(indexrelid.to_u32());
+    SetOfIterator::new(queries.into_iter().map(|record| record.sample))
 }
@@ -15,6 +15,7 @@
 use crate::index::scanners::Io;
 use pgrx::guc::{GucContext, GucFlags, GucRegistry, GucSetting, PostgresGucEnum};
 use std::ffi::{CStr, CString};
+use vchordrq::{AutoProbes, Probes};
 
 #[derive(Debug, Clone, Copy, PostgresGucEnum)]
 pub enum PostgresIo {
@@ -33,6 +34,12 @@ static VCHORDRQ_QUERY_SAMPLING_MAX_RECORDS: GucSetting<i32> = GucSetting::<i32>:
 
 static VCHORDRQ_QUERY_SAMPLING_RATE: GucSetting<f64> = GucSetting::<f64>::new(0.0);
 
+static VCHORDG_QUERY_SAMPLING_ENABLE: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+static VCHORDG_QUERY_SAMPLING_MAX_RECORDS: GucSetting<i32> = GucSetting::<i32>::new(0);
+
+static VCHORDG_QUERY_SAMPLING_RATE: GucSetting<f64> = GucSetting::<f64>::new(0.0);
+
 static VCHORDG_ENABLE_SCAN: GucSetting<bool> = GucSetting::<bool>::new(true);
 
 static VCHORDG_EF_SEARCH: GucSetting<i32> = GucSetting::<i32>::new(64);
@@ -43,6 +50,10 @@ static VCHORDG_BEAM_SEARCH: GucSetting<i32> = GucSetting::<i32>::new(1);
 
 static VCHORDG_MAX_SCAN_TUPLES: GucSetting<i32> = GucSetting::<i32>::new(-1);
 
+static VCHORDG_PREFILTER: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+static VCHORDG_ORDERBY_PROJECTION: GucSetting<bool> = GucSetting::<bool>::new(false);
+
 static VCHORDG_IO_SEARCH: GucSetting<PostgresIo> = GucSetting::<PostgresIo>::new(
     #[cfg(any(feature = "pg14", feature = "pg15", feature = "pg16"))]
     PostgresIo::PrefetchBuffer,
@@ -63,6 +74,10 @@ static VCHORDRQ_PROBES: GucSetting<Option<CString>> = GucSetting::<Option<CStrin
 
 static mut VCHORDRQ_PROBES_CONFIG: *mut pgrx::pg_sys::config_generic = core::ptr::null_mut();
 
+static VCHORDRQ_AUTO_PROBES_MIN: GucSetting<i32> = GucSetting::<i32>::new(1);
+
+static VCHORDRQ_AUTO_PROBES_MAX: GucSetting<i32> = GucSetting::<i32>::new(65535);
+
 static VCHORDRQ_EPSILON: GucSetting<f64> = GucSetting::<f64>::new(1.9);
 
 static mut VCHORDRQ_EPSILON_CONFIG: *mut pgrx::pg_sys::config_generic = core::ptr::null_mut();
@@ -78,8 +93,16 @@ static VCHORDRQ_MAXSIM_THRESHOLD: GucSetting<i32> = GucSetting::<i32>::new(0);
 static mut VCHORDRQ_MAXSIM_THRESHOLD_CONFIG: *mut pgrx::pg_sys::config_generic =
     core::ptr::null_mut();
 
+static VCHORDRQ_MAINTAIN_SPLIT_FACTOR: GucSetting<f64> = GucSetting::<f64>::new(0.0);
+
 static VCHORDRQ_PREFILTER: GucSetting<bool> = GucSetting::<bool>::new(false);
 
+static VCHORDRQ_ITERATIVE_SCAN: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+static VCHORDRQ_ORDERBY_PROJECTION: GucSetting<bool> = GucSetting::<bool>::new(false);
+
+static VCHORDRQ_DEBUG_SCANNED_TUPLES: GucSetting<bool> = GucSetting::<bool>::new(false);
+
 static VCHORDRQ_IO_SEARCH: GucSetting<PostgresIo> = GucSetting::<PostgresIo>::new(
     #[cfg(any(feature = "pg14", feature = "pg15", feature = "pg16"))]
     PostgresIo::PrefetchBuffer,
@@ -111,6 +134,26 @@ pub fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_int_guc(
+        c"vchordrq.auto_probes_min",
+        c"`auto_probes_min` argument of vchordrq.",
+        c"`auto_probes_min` argument of vchordrq.",
+        &VCHORDRQ_AUTO_PROBES_MIN,
+        1,
+        65535,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        c"vchordrq.auto_probes_max",
+        c"`auto_probes_max` argument of vchordrq.",
+        c"`auto_probes_max` argument of vchordrq. It is the number of lists probed if probing cannot stop early, that is, without a `LIMIT`, with a `WHERE` clause, with prefilter or in parallel scans.",
+        &VCHORDRQ_AUTO_PROBES_MAX,
+        1,
+        65535,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
     GucRegistry::define_float_guc(
         c"vchordrq.epsilon",
         c"`epsilon` argument of vchordrq.",
@@ -159,6 +202,40 @@ pub fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_bool_guc(
+        c"vchordrq.iterative_scan",
+        c"`iterative_scan` argument of vchordrq.",
+        c"If enabled, more lists are probed whenever rows are still wanted after all the candidates are returned, until all lists are probed or `vchordrq.max_scan_tuples` tuples are scanned. Rows found in lists probed later are returned after the rows before them, so they may be returned out of order.",
+        &VCHORDRQ_ITERATIVE_SCAN,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_bool_guc(
+        c"vchordrq.orderby_projection",
+        c"`orderby_projection` argument of vchordrq.",
+        c"`orderby_projection` argument of vchordrq.",
+        &VCHORDRQ_ORDERBY_PROJECTION,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_bool_guc(
+        c"vchordrq.debug_scanned_tuples",
+        c"`debug_scanned_tuples` argument of vchordrq.",
+        c"`debug_scanned_tuples` argument of vchordrq.",
+        &VCHORDRQ_DEBUG_SCANNED_TUPLES,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_float_guc(
+        c"vchordrq.maintain_split_factor",
+        c"`maintain_split_factor` argument of vchordrq.",
+        c"`maintain_split_factor` argument of vchordrq.",
+        &VCHORDRQ_MAINTAIN_SPLIT_FACTOR,
+        0.0,
+        1000.0,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
     GucRegistry::define_enum_guc(
         c"vchordrq.io_search",
         c"`io_search` argument of vchordrq.",
@@ -247,6 +324,22 @@ pub fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_bool_guc(
+        c"vchordg.prefilter",
+        c"`prefilter` argument of vchordg.",
+        c"`prefilter` argument of vchordg.",
+        &VCHORDG_PREFILTER,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_bool_guc(
+        c"vchordg.orderby_projection",
+        c"`orderby_projection` argument of vchordg.",
+        c"`orderby_projection` argument of vchordg.",
+        &VCHORDG_ORDERBY_PROJECTION,
+        GucContext::Userset,
+        GucFlags::default(),
+    );
     GucRegistry::define_enum_guc(
         c"vchordg.io_search",
         c"`io_search` argument of vchordg.",
@@ -263,6 +356,34 @@ pub fn init() {
         GucContext::Userset,
         GucFlags::default(),
     );
+    GucRegistry::define_bool_guc(
+        c"vchordg.query_sampling_enable",
+        c"`query_sampling_enable` argument of vchordg.",
+        c"`query_sampling_enable` argument of vchordg.",
+        &VCHORDG_QUERY_SAMPLING_ENABLE,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_int_guc(
+        c"vchordg.query_sampling_max_records",
+        c"`query_sampling_max_records` argument of vchordg.",
+        c"`query_sampling_max_records` argument of vchordg.",
+        &VCHORDG_QUERY_SAMPLING_MAX_RECORDS,
+        0,
+        10000,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
+    GucRegistry::define_float_guc(
+        c"vchordg.query_sampling_rate",
+        c"`query_sampling_rate` argument of vchordg.",
+        c"`query_sampling_rate` argument of vchordg.",
+        &VCHORDG_QUERY_SAMPLING_RATE,
+        0.0,
+        1.0,
+        GucContext::Suset,
+        GucFlags::default(),
+    );
     unsafe {
         #[cfg(feature = "pg14")]
         pgrx::pg_sys::EmitWarningsOnPlaceholders(c"vchordg".as_ptr());
@@ -359,6 +480,14 @@ pub fn vchordg_max_scan_tuples() -> Option<u32> {
     if x < 0 { None } else { Some(x as u32) }
 }
 
+pub fn vchordg_prefilter() -> bool {
+    VCHORDG_PREFILTER.get()
+}
+
+pub fn vchordg_orderby_projection() -> bool {
+    VCHORDG_ORDERBY_PROJECTION.get()
+}
+
 pub fn vchordg_io_search() -> Io {
     match VCHORDG_IO_SEARCH.get() {
         PostgresIo::ReadBuffer => Io::Plain,
@@ -377,12 +506,31 @@ pub fn vchordg_io_rerank() -> Io {
     }
 }
 
+pub fn vchordg_query_sampling_enable() -> bool {
+    VCHORDG_QUERY_SAMPLING_ENABLE.get()
+}
+
+pub fn vchordg_query_sampling_max_records() -> u32 {
+    VCHORDG_QUERY_SAMPLING_MAX_RECORDS.get() as u32
+}
+
+pub fn vchordg_query_sampling_rate() -> f64 {
+    VCHORDG_QUERY_SAMPLING_RATE.get()
+}
+
 pub fn vchordrq_enable_scan() -> bool {
     VCHORDRQ_ENABLE_SCAN.get()
 }
 
-pub unsafe fn vchordrq_probes(index: pgrx::pg_sys::Relation) -> Vec<u32> {
-    fn parse(value: &CStr) -> Vec<u32> {
+pub unsafe fn vchordrq_probes(index: pgrx::pg_sys::Relation) -> Probes {
+    fn parse(value: &CStr) -> Probes {
+        if value.to_bytes().trim_ascii() == b"auto" {
+            return Probes::Auto(AutoProbes {
+                min: VCHORDRQ_AUTO_PROBES_MIN.get() as u32,
+                max: VCHORDRQ_AUTO_PROBES_MAX.get() as u32,
+                k: None,
+            });
+        }
         let mut result = Vec::new();
         let mut current = None;
         for &c in value.to_bytes() {
@@ -402,7 +550,7 @@ pub unsafe fn vchordrq_probes(index: pgrx::pg_sys::Relation) -> Vec<u32> {
         if let Some(current) = current {
             result.push(current);
         }
-        result
+        Probes::Fixed(result)
     }
     assert!(crate::is_main());
     const DEFAULT: &CStr = c"";
@@ -476,6 +624,23 @@ pub fn vchordrq_prefilter() -> bool {
     VCHORDRQ_PREFILTER.get()
 }
 
+pub fn vchordrq_iterative_scan() -> bool {
+    VCHORDRQ_ITERATIVE_SCAN.get()
+}
+
+pub fn vchordrq_orderby_projection() -> bool {
+    VCHORDRQ_ORDERBY_PROJECTION.get()
+}
+
+pub fn vchordrq_debug_scanned_tuples() -> bool {
+    VCHORDRQ_DEBUG_SCANNED_TUPLES.get()
+}
+
+pub fn vchordrq_maintain_split_factor() -> Option<f64> {
+    let x = VCHORDRQ_MAINTAIN_SPLIT_FACTOR.get();
+    if x > 0.0 { Some(x) } else { None }
+}
+
 pub fn vchordrq_io_search() -> Io {
     match VCHORDRQ_IO_SEARCH.get() {
         PostgresIo::ReadBuffer => Io::Plain,
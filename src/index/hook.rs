@@ -12,52 +12,55 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use std::ptr::NonNull;
+
+unsafe fn dirty_check_vchordg(index_relation: *mut pgrx::pg_sys::RelationData) -> Option<bool> {
+    type FnPtr = unsafe extern "C-unwind" fn(
+        *mut pgrx::pg_sys::RelationData,
+        i32,
+        i32,
+    ) -> *mut pgrx::pg_sys::IndexScanDescData;
+    unsafe {
+        let index_relation = index_relation.as_ref()?;
+        let indam = index_relation.rd_indam.as_ref()?;
+        let ambeginscan = indam.ambeginscan.as_ref()?;
+        Some(core::ptr::fn_addr_eq::<FnPtr, FnPtr>(
+            *ambeginscan,
+            crate::index::vchordg::am::ambeginscan,
+        ))
+    }
+}
+
+unsafe fn dirty_check_vchordrq(index_relation: *mut pgrx::pg_sys::RelationData) -> Option<bool> {
+    type FnPtr = unsafe extern "C-unwind" fn(
+        *mut pgrx::pg_sys::RelationData,
+        i32,
+        i32,
+    ) -> *mut pgrx::pg_sys::IndexScanDescData;
+    unsafe {
+        let index_relation = index_relation.as_ref()?;
+        let indam = index_relation.rd_indam.as_ref()?;
+        let ambeginscan = indam.ambeginscan.as_ref()?;
+        Some(core::ptr::fn_addr_eq::<FnPtr, FnPtr>(
+            *ambeginscan,
+            crate::index::vchordrq::am::ambeginscan,
+        ))
+    }
+}
+
 #[pgrx::pg_guard]
 unsafe extern "C-unwind" fn rewrite_plan_state(
     node: *mut pgrx::pg_sys::PlanState,
     context: *mut core::ffi::c_void,
 ) -> bool {
-    unsafe fn dirty_check_vchordg(index_relation: *mut pgrx::pg_sys::RelationData) -> Option<bool> {
-        type FnPtr = unsafe extern "C-unwind" fn(
-            *mut pgrx::pg_sys::RelationData,
-            i32,
-            i32,
-        ) -> *mut pgrx::pg_sys::IndexScanDescData;
-        unsafe {
-            let index_relation = index_relation.as_ref()?;
-            let indam = index_relation.rd_indam.as_ref()?;
-            let ambeginscan = indam.ambeginscan.as_ref()?;
-            Some(core::ptr::fn_addr_eq::<FnPtr, FnPtr>(
-                *ambeginscan,
-                crate::index::vchordg::am::ambeginscan,
-            ))
-        }
-    }
-
-    unsafe fn dirty_check_vchordrq(
-        index_relation: *mut pgrx::pg_sys::RelationData,
-    ) -> Option<bool> {
-        type FnPtr = unsafe extern "C-unwind" fn(
-            *mut pgrx::pg_sys::RelationData,
-            i32,
-            i32,
-        ) -> *mut pgrx::pg_sys::IndexScanDescData;
-        unsafe {
-            let index_relation = index_relation.as_ref()?;
-            let indam = index_relation.rd_indam.as_ref()?;
-            let ambeginscan = indam.ambeginscan.as_ref()?;
-            Some(core::ptr::fn_addr_eq::<FnPtr, FnPtr>(
-                *ambeginscan,
-                crate::index::vchordrq::am::ambeginscan,
-            ))
-        }
-    }
-
     unsafe {
         if (*node).type_ == pgrx::pg_sys::NodeTag::T_IndexScanState {
             let node = node as *mut pgrx::pg_sys::IndexScanState;
             let index_relation = (*node).iss_RelationDesc;
-            if (*node).iss_ScanDesc.is_null() {
+            // parallel scans are begun by PostgreSQL after the executor starts,
+            // so they are not told about the plan state, and prefilter is a
+            // no-op for them since the fetcher has no qual to evaluate
+            if (*node).iss_ScanDesc.is_null() && !(*(*node).ss.ps.plan).parallel_aware {
                 if Some(true) == dirty_check_vchordg(index_relation) {
                     use crate::index::vchordg::am::Scanner;
 
@@ -73,6 +76,8 @@ unsafe extern "C-unwind" fn rewrite_plan_state(
 
                     let scanner = &mut *((*(*node).iss_ScanDesc).opaque as *mut Scanner);
                     scanner.hack = std::ptr::NonNull::new(node);
+                    scanner.limit = std::ptr::NonNull::new(context.cast());
+                    scanner.projection = projection(node);
 
                     if (*node).iss_NumRuntimeKeys == 0 || (*node).iss_RuntimeKeysReady {
                         pgrx::pg_sys::index_rescan(
@@ -99,6 +104,8 @@ unsafe extern "C-unwind" fn rewrite_plan_state(
 
                     let scanner = &mut *((*(*node).iss_ScanDesc).opaque as *mut Scanner);
                     scanner.hack = std::ptr::NonNull::new(node);
+                    scanner.limit = std::ptr::NonNull::new(context.cast());
+                    scanner.projection = projection(node);
 
                     if (*node).iss_NumRuntimeKeys == 0 || (*node).iss_RuntimeKeysReady {
                         pgrx::pg_sys::index_rescan(
@@ -112,10 +119,195 @@ unsafe extern "C-unwind" fn rewrite_plan_state(
                 }
             }
         }
+        // only the direct child of a `Limit` node is told about the limit
+        let context = if (*node).type_ == pgrx::pg_sys::NodeTag::T_LimitState {
+            node.cast()
+        } else {
+            std::ptr::null_mut()
+        };
         pgrx::pg_sys::planstate_tree_walker(node, Some(rewrite_plan_state), context)
     }
 }
 
+/// Returns the number of rows that the `Limit` node reads from its child, that
+/// is the row count of the `LIMIT` clause plus the `OFFSET`, once the `Limit`
+/// node has evaluated them.
+pub unsafe fn limit_count(node: NonNull<pgrx::pg_sys::LimitState>) -> Option<i64> {
+    let node = unsafe { node.as_ref() };
+    if node.lstate == pgrx::pg_sys::LimitStateCond::LIMIT_INITIAL || node.noCount {
+        return None;
+    }
+    Some(node.count.saturating_add(node.offset))
+}
+
+/// Returns the parameter that stands for the `ORDER BY` expression in the
+/// projection of the index scan, if the plan was rewritten by `rewrite_plan`.
+unsafe fn projection(
+    node: *mut pgrx::pg_sys::IndexScanState,
+) -> Option<NonNull<pgrx::pg_sys::ParamExecData>> {
+    use pgrx::PgList;
+    use pgrx::pg_sys::{IndexScan, Node, NodeTag, Param, ParamKind};
+    unsafe {
+        let plan = (*node).ss.ps.plan as *mut IndexScan;
+        let orderbys = PgList::<Node>::from_pg((*plan).indexorderbyorig);
+        let orderby = orderbys.get_ptr(0)?;
+        if (*orderby).type_ != NodeTag::T_Param {
+            return None;
+        }
+        let param = orderby as *mut Param;
+        if (*param).paramkind != ParamKind::PARAM_EXEC {
+            return None;
+        }
+        let estate = (*node).ss.ps.state;
+        NonNull::new((*estate).es_param_exec_vals.add((*param).paramid as usize))
+    }
+}
+
+/// Converts a distance to a datum of the return type of the ordering operator.
+pub fn orderby_datum(rettype: pgrx::pg_sys::Oid, distance: f32) -> pgrx::pg_sys::Datum {
+    use pgrx::datum::IntoDatum;
+    let datum = match rettype {
+        pgrx::pg_sys::FLOAT4OID => distance.into_datum(),
+        pgrx::pg_sys::FLOAT8OID => (distance as f64).into_datum(),
+        _ => pgrx::error!("unsupported return type of the ordering operator"),
+    };
+    datum.expect("null datum")
+}
+
+/// Replaces the `ORDER BY` expression in the target list of a vector index
+/// scan with a parameter, so the projection takes the distance returned by
+/// the index instead of recomputing it. The parameter also takes the place of
+/// `indexorderbyorig`, which is only evaluated if the index asks for
+/// rechecking the order.
+unsafe fn rewrite_index_scan(
+    stmt: *mut pgrx::pg_sys::PlannedStmt,
+    plan: *mut pgrx::pg_sys::IndexScan,
+) {
+    use pgrx::pg_sys::{Node, NodeTag, Param, ParamKind, TargetEntry};
+    use pgrx::{PgBox, PgList};
+    unsafe {
+        // parallel scans are begun by PostgreSQL, so the parameter is never set
+        if (*plan).scan.plan.parallel_aware {
+            return;
+        }
+        let orderbys = PgList::<Node>::from_pg((*plan).indexorderbyorig);
+        let (Some(orderby), 1) = (orderbys.get_ptr(0), orderbys.len()) else {
+            return;
+        };
+        if (*orderby).type_ == NodeTag::T_Param {
+            return;
+        }
+        let enabled = {
+            let index_relation = pgrx::pg_sys::RelationIdGetRelation((*plan).indexid);
+            let enabled = (Some(true) == dirty_check_vchordg(index_relation)
+                && crate::index::gucs::vchordg_orderby_projection())
+                || (Some(true) == dirty_check_vchordrq(index_relation)
+                    && crate::index::gucs::vchordrq_orderby_projection());
+            if !index_relation.is_null() {
+                pgrx::pg_sys::RelationClose(index_relation);
+            }
+            enabled
+        };
+        if !enabled {
+            return;
+        }
+        let mut param = std::ptr::null_mut::<Param>();
+        let targetlist = PgList::<TargetEntry>::from_pg((*plan).scan.plan.targetlist);
+        for entry in targetlist.iter_ptr() {
+            if !pgrx::pg_sys::equal((*entry).expr as *const _, orderby as *const _) {
+                continue;
+            }
+            if param.is_null() {
+                let mut node = PgBox::<Param>::alloc_node(NodeTag::T_Param);
+                node.paramkind = ParamKind::PARAM_EXEC;
+                node.paramid = PgList::<()>::from_pg((*stmt).paramExecTypes).len() as _;
+                node.paramtype = pgrx::pg_sys::exprType(orderby);
+                node.paramtypmod = pgrx::pg_sys::exprTypmod(orderby);
+                node.paramcollid = pgrx::pg_sys::exprCollation(orderby);
+                node.location = -1;
+                param = node.into_pg();
+                (*stmt).paramExecTypes =
+                    pgrx::pg_sys::lappend_oid((*stmt).paramExecTypes, (*param).paramtype);
+            }
+            (*entry).expr = param.cast();
+        }
+        if param.is_null() {
+            return;
+        }
+        let mut orderbys = PgList::<Param>::new();
+        orderbys.push(param);
+        (*plan).indexorderbyorig = orderbys.into_pg();
+    }
+}
+
+unsafe fn rewrite_plan(stmt: *mut pgrx::pg_sys::PlannedStmt, plan: *mut pgrx::pg_sys::Plan) {
+    use pgrx::PgList;
+    use pgrx::pg_sys::{Append, CustomScan, MergeAppend, NodeTag, Plan, SubqueryScan};
+    unsafe {
+        if plan.is_null() {
+            return;
+        }
+        match (*plan).type_ {
+            NodeTag::T_IndexScan => rewrite_index_scan(stmt, plan.cast()),
+            NodeTag::T_Append => {
+                let plans = PgList::<Plan>::from_pg((*(plan as *mut Append)).appendplans);
+                for child in plans.iter_ptr() {
+                    rewrite_plan(stmt, child);
+                }
+            }
+            NodeTag::T_MergeAppend => {
+                let plans = PgList::<Plan>::from_pg((*(plan as *mut MergeAppend)).mergeplans);
+                for child in plans.iter_ptr() {
+                    rewrite_plan(stmt, child);
+                }
+            }
+            NodeTag::T_CustomScan => {
+                let plans = PgList::<Plan>::from_pg((*(plan as *mut CustomScan)).custom_plans);
+                for child in plans.iter_ptr() {
+                    rewrite_plan(stmt, child);
+                }
+            }
+            NodeTag::T_SubqueryScan => rewrite_plan(stmt, (*(plan as *mut SubqueryScan)).subplan),
+            _ => (),
+        }
+        rewrite_plan(stmt, (*plan).lefttree);
+        rewrite_plan(stmt, (*plan).righttree);
+    }
+}
+
+static mut PREV_PLANNER: pgrx::pg_sys::planner_hook_type = None;
+
+#[pgrx::pg_guard]
+unsafe extern "C-unwind" fn planner(
+    parse: *mut pgrx::pg_sys::Query,
+    query_string: *const core::ffi::c_char,
+    cursor_options: core::ffi::c_int,
+    bound_params: pgrx::pg_sys::ParamListInfo,
+) -> *mut pgrx::pg_sys::PlannedStmt {
+    unsafe {
+        use pgrx::PgList;
+        use pgrx::pg_sys::submodules::ffi::pg_guard_ffi_boundary;
+        let stmt = if let Some(prev_planner) = PREV_PLANNER {
+            #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+            pg_guard_ffi_boundary(|| {
+                prev_planner(parse, query_string, cursor_options, bound_params)
+            })
+        } else {
+            pgrx::pg_sys::standard_planner(parse, query_string, cursor_options, bound_params)
+        };
+        // rows rechecked by `EvalPlanQual` are not returned by the index, so
+        // the parameter would be stale
+        if (*stmt).commandType == pgrx::pg_sys::CmdType::CMD_SELECT && (*stmt).rowMarks.is_null() {
+            rewrite_plan(stmt, (*stmt).planTree);
+            let subplans = PgList::<pgrx::pg_sys::Plan>::from_pg((*stmt).subplans);
+            for subplan in subplans.iter_ptr() {
+                rewrite_plan(stmt, subplan);
+            }
+        }
+        stmt
+    }
+}
+
 static mut PREV_EXECUTOR_START: pgrx::pg_sys::ExecutorStart_hook_type = None;
 
 #[pgrx::pg_guard]
@@ -136,10 +328,34 @@ unsafe extern "C-unwind" fn executor_start(
     }
 }
 
+static mut PREV_SET_REL_PATHLIST: pgrx::pg_sys::set_rel_pathlist_hook_type = None;
+
+#[pgrx::pg_guard]
+unsafe extern "C-unwind" fn set_rel_pathlist(
+    root: *mut pgrx::pg_sys::PlannerInfo,
+    rel: *mut pgrx::pg_sys::RelOptInfo,
+    rti: pgrx::pg_sys::Index,
+    rte: *mut pgrx::pg_sys::RangeTblEntry,
+) {
+    unsafe {
+        use pgrx::pg_sys::submodules::ffi::pg_guard_ffi_boundary;
+        if let Some(prev_set_rel_pathlist) = PREV_SET_REL_PATHLIST {
+            #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+            pg_guard_ffi_boundary(|| prev_set_rel_pathlist(root, rel, rti, rte))
+        }
+        crate::index::vchordrq::am::am_pushdown::set_rel_pathlist(root, rel, rte);
+        crate::index::vchordrq::am::am_parallel::set_rel_pathlist(rel);
+    }
+}
+
 pub fn init() {
     assert!(crate::is_main());
     unsafe {
+        PREV_PLANNER = pgrx::pg_sys::planner_hook;
+        pgrx::pg_sys::planner_hook = Some(planner);
         PREV_EXECUTOR_START = pgrx::pg_sys::ExecutorStart_hook;
         pgrx::pg_sys::ExecutorStart_hook = Some(executor_start);
+        PREV_SET_REL_PATHLIST = pgrx::pg_sys::set_rel_pathlist_hook;
+        pgrx::pg_sys::set_rel_pathlist_hook = Some(set_rel_pathlist);
     }
 }
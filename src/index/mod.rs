@@ -23,6 +23,7 @@ mod storage;
 mod traverse;
 mod vchordg;
 mod vchordrq;
+mod verify;
 
 pub fn init() {
     gucs::init();
@@ -72,6 +72,36 @@ fn _vchordg_support_rabitq4_ip_ops() -> String {
     "vchordg_rabitq4_ip_ops".to_string()
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_bit_hamming_ops() -> String {
+    "vchordg_bit_hamming_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_bit_jaccard_ops() -> String {
+    "vchordg_bit_jaccard_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_vector_maxsim_ops() -> String {
+    "vchordg_vector_maxsim_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_halfvec_maxsim_ops() -> String {
+    "vchordg_halfvec_maxsim_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq8_maxsim_ops() -> String {
+    "vchordg_rabitq8_maxsim_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordg_support_rabitq4_maxsim_ops() -> String {
+    "vchordg_rabitq4_maxsim_ops".to_string()
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchordrq_support_vector_l2_ops() -> String {
     "vchordrq_vector_l2_ops".to_string()
@@ -132,6 +162,31 @@ fn _vchordrq_support_rabitq4_cosine_ops() -> String {
     "vchordrq_rabitq4_cosine_ops".to_string()
 }
 
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_sparsevec_l2_ops() -> String {
+    "vchordrq_sparsevec_l2_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_sparsevec_ip_ops() -> String {
+    "vchordrq_sparsevec_ip_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_sparsevec_cosine_ops() -> String {
+    "vchordrq_sparsevec_cosine_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_bit_hamming_ops() -> String {
+    "vchordrq_bit_hamming_ops".to_string()
+}
+
+#[pgrx::pg_extern(immutable, strict, parallel_safe)]
+fn _vchordrq_support_bit_jaccard_ops() -> String {
+    "vchordrq_bit_jaccard_ops".to_string()
+}
+
 #[pgrx::pg_extern(immutable, strict, parallel_safe)]
 fn _vchordrq_support_vector_maxsim_ops() -> String {
     "vchordrq_vector_maxsim_ops".to_string()
@@ -156,3 +211,258 @@ pub struct Sphere<T> {
     pub center: T,
     pub radius: f32,
 }
+
+/// The catalog layout an operator class of this extension is expected to have.
+pub struct Shape {
+    /// Name of the indexed type.
+    pub input: &'static str,
+    /// Strategy number of the ordering operator.
+    pub order: i16,
+    /// Name of the sphere type, if the operator class has a sphere operator
+    /// as strategy 2.
+    pub sphere: Option<&'static str>,
+    /// Name of the multi-query type, if the operator class has a multi-query
+    /// ordering operator as strategy 4.
+    pub multi_query: Option<&'static str>,
+}
+
+pub unsafe fn support(proc: pgrx::pg_sys::Oid) -> Option<String> {
+    use pgrx::datum::FromDatum;
+
+    let mut flinfo = pgrx::pg_sys::FmgrInfo::default();
+
+    unsafe {
+        pgrx::pg_sys::fmgr_info(proc, &mut flinfo);
+    }
+
+    let fn_addr = flinfo.fn_addr.expect("null function pointer");
+
+    let mut fcinfo = unsafe { std::mem::zeroed::<pgrx::pg_sys::FunctionCallInfoBaseData>() };
+    fcinfo.flinfo = &mut flinfo;
+    fcinfo.fncollation = pgrx::pg_sys::DEFAULT_COLLATION_OID;
+    fcinfo.context = std::ptr::null_mut();
+    fcinfo.resultinfo = std::ptr::null_mut();
+    fcinfo.isnull = true;
+    fcinfo.nargs = 0;
+
+    let result_datum = unsafe {
+        #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+        pgrx::pg_sys::ffi::pg_guard_ffi_boundary(|| fn_addr(&mut fcinfo))
+    };
+
+    let result_option = unsafe { String::from_datum(result_datum, fcinfo.isnull) };
+
+    if !fcinfo.isnull {
+        unsafe {
+            pgrx::pg_sys::pfree(result_datum.cast_mut_ptr());
+        }
+    }
+
+    result_option
+}
+
+pub unsafe fn validate(opclass: pgrx::pg_sys::Oid, shape: impl Fn(&str) -> Option<Shape>) -> bool {
+    use pgrx::PgSqlErrorCode::ERRCODE_INVALID_OBJECT_DEFINITION;
+    use pgrx::pg_sys::{AMOP_ORDER, AMOP_SEARCH, BOOLOID, FLOAT4OID, FLOAT8OID, Oid, TEXTOID};
+    use pgrx::pg_sys::{FormData_pg_amop, FormData_pg_amproc, FormData_pg_opclass};
+    use pgrx::pg_sys::{FormData_pg_opfamily, FormData_pg_type, SysCacheIdentifier};
+    use std::ffi::CStr;
+
+    unsafe fn string(s: *const std::ffi::c_char) -> String {
+        unsafe { CStr::from_ptr(s).to_string_lossy().into_owned() }
+    }
+
+    unsafe fn typname(oid: Oid) -> Option<String> {
+        unsafe {
+            let tuple = pgrx::pg_sys::SearchSysCache1(SysCacheIdentifier::TYPEOID as _, oid.into());
+            if tuple.is_null() {
+                return None;
+            }
+            let form = pgrx::pg_sys::heap_tuple_get_struct::<FormData_pg_type>(tuple);
+            let result = string((*form).typname.data.as_ptr());
+            pgrx::pg_sys::ReleaseSysCache(tuple);
+            Some(result)
+        }
+    }
+
+    let mut result = true;
+
+    unsafe {
+        let tuple = pgrx::pg_sys::SearchSysCache1(SysCacheIdentifier::CLAOID as _, opclass.into());
+        if tuple.is_null() {
+            pgrx::error!("cache lookup failed for operator class {opclass}");
+        }
+        let form = pgrx::pg_sys::heap_tuple_get_struct::<FormData_pg_opclass>(tuple);
+        let opfamily = (*form).opcfamily;
+        let opcintype = (*form).opcintype;
+        let opclassname = string((*form).opcname.data.as_ptr());
+        let amname = string(pgrx::pg_sys::get_am_name((*form).opcmethod));
+        pgrx::pg_sys::ReleaseSysCache(tuple);
+
+        let tuple =
+            pgrx::pg_sys::SearchSysCache1(SysCacheIdentifier::OPFAMILYOID as _, opfamily.into());
+        if tuple.is_null() {
+            pgrx::error!("cache lookup failed for operator family {opfamily}");
+        }
+        let form = pgrx::pg_sys::heap_tuple_get_struct::<FormData_pg_opfamily>(tuple);
+        let opfamilyname = string((*form).opfname.data.as_ptr());
+        pgrx::pg_sys::ReleaseSysCache(tuple);
+
+        let mut report = |message: String| {
+            pgrx::ereport!(INFO, ERRCODE_INVALID_OBJECT_DEFINITION, message);
+            result = false;
+        };
+
+        let mut name = None;
+        let list = pgrx::pg_sys::SearchSysCacheList(
+            SysCacheIdentifier::AMPROCNUM as _,
+            1,
+            opfamily.into(),
+            pgrx::pg_sys::Datum::null(),
+            pgrx::pg_sys::Datum::null(),
+        );
+        for &member in (*list).members.as_slice((*list).n_members as _) {
+            let form =
+                pgrx::pg_sys::heap_tuple_get_struct::<FormData_pg_amproc>(&raw mut (*member).tuple);
+            let proc = (*form).amproc;
+            let procname = string(pgrx::pg_sys::format_procedure(proc));
+            let number = (*form).amprocnum;
+            if number != 1 {
+                report(format!(
+                    "operator family \"{opfamilyname}\" of access method {amname} contains function {procname} with invalid support number {number}"
+                ));
+                continue;
+            }
+            if (*form).amproclefttype != opcintype
+                || (*form).amprocrighttype != opcintype
+                || pgrx::pg_sys::get_func_rettype(proc) != TEXTOID
+                || pgrx::pg_sys::get_func_nargs(proc) != 0
+            {
+                report(format!(
+                    "operator family \"{opfamilyname}\" of access method {amname} contains function {procname} with wrong signature for support number {number}"
+                ));
+                continue;
+            }
+            name = support(proc);
+        }
+        pgrx::pg_sys::ReleaseCatCacheList(list);
+
+        let Some(name) = name else {
+            report(format!(
+                "operator class \"{opclassname}\" of access method {amname} is missing support function 1"
+            ));
+            return false;
+        };
+        let Some(shape) = shape(&name) else {
+            report(format!(
+                "operator class \"{opclassname}\" of access method {amname} has unknown support function 1 result \"{name}\""
+            ));
+            return false;
+        };
+
+        if typname(opcintype).as_deref() != Some(shape.input) {
+            let typename = string(pgrx::pg_sys::format_type_be(opcintype));
+            report(format!(
+                "operator class \"{opclassname}\" of access method {amname} has input type {typename}, but \"{name}\" expects {}",
+                shape.input
+            ));
+        }
+
+        let mut order = false;
+        let mut sphere = false;
+        let mut multi_query = false;
+        let list = pgrx::pg_sys::SearchSysCacheList(
+            SysCacheIdentifier::AMOPSTRATEGY as _,
+            1,
+            opfamily.into(),
+            pgrx::pg_sys::Datum::null(),
+            pgrx::pg_sys::Datum::null(),
+        );
+        for &member in (*list).members.as_slice((*list).n_members as _) {
+            let form =
+                pgrx::pg_sys::heap_tuple_get_struct::<FormData_pg_amop>(&raw mut (*member).tuple);
+            let opr = (*form).amopopr;
+            let oprname = string(pgrx::pg_sys::format_operator(opr));
+            let strategy = (*form).amopstrategy;
+            let purpose = (*form).amoppurpose as u8;
+            let left = (*form).amoplefttype;
+            let right = (*form).amoprighttype;
+            let rettype = pgrx::pg_sys::get_op_rettype(opr);
+            // distances are real or double precision, sorted by `float_ops`
+            let ordering = purpose == AMOP_ORDER
+                && (*form).amopsortfamily != Oid::INVALID
+                && matches!(rettype, FLOAT4OID | FLOAT8OID);
+            if strategy == shape.order {
+                if !ordering {
+                    report(format!(
+                        "operator family \"{opfamilyname}\" of access method {amname} contains invalid ORDER BY specification for operator {oprname}"
+                    ));
+                    continue;
+                }
+                if left != opcintype || right != opcintype {
+                    report(format!(
+                        "operator family \"{opfamilyname}\" of access method {amname} contains operator {oprname} with wrong signature"
+                    ));
+                    continue;
+                }
+                order = true;
+            } else if strategy == 2
+                && let Some(sphere_type) = shape.sphere
+            {
+                if purpose != AMOP_SEARCH || rettype != BOOLOID {
+                    report(format!(
+                        "operator family \"{opfamilyname}\" of access method {amname} contains invalid search specification for operator {oprname}"
+                    ));
+                    continue;
+                }
+                if left != opcintype || typname(right).as_deref() != Some(sphere_type) {
+                    report(format!(
+                        "operator family \"{opfamilyname}\" of access method {amname} contains operator {oprname} with wrong signature"
+                    ));
+                    continue;
+                }
+                sphere = true;
+            } else if strategy == 4
+                && let Some(multi_query_type) = shape.multi_query
+            {
+                if !ordering {
+                    report(format!(
+                        "operator family \"{opfamilyname}\" of access method {amname} contains invalid ORDER BY specification for operator {oprname}"
+                    ));
+                    continue;
+                }
+                if left != opcintype || typname(right).as_deref() != Some(multi_query_type) {
+                    report(format!(
+                        "operator family \"{opfamilyname}\" of access method {amname} contains operator {oprname} with wrong signature"
+                    ));
+                    continue;
+                }
+                multi_query = true;
+            } else {
+                report(format!(
+                    "operator family \"{opfamilyname}\" of access method {amname} contains operator {oprname} with invalid strategy number {strategy}"
+                ));
+            }
+        }
+        pgrx::pg_sys::ReleaseCatCacheList(list);
+
+        if !order {
+            report(format!(
+                "operator class \"{opclassname}\" of access method {amname} is missing operator {}",
+                shape.order
+            ));
+        }
+        if shape.sphere.is_some() && !sphere {
+            report(format!(
+                "operator class \"{opclassname}\" of access method {amname} is missing operator 2"
+            ));
+        }
+        if shape.multi_query.is_some() && !multi_query {
+            report(format!(
+                "operator class \"{opclassname}\" of access method {amname} is missing operator 4"
+            ));
+        }
+    }
+
+    result
+}
@@ -262,6 +262,7 @@ impl<O: Opaque> Drop for PostgresBufferWriteGuard<O> {
 #[derive(Debug, Clone)]
 pub struct PostgresRelation<Opaque> {
     raw: pgrx::pg_sys::Relation,
+    fork: pgrx::pg_sys::ForkNumber::Type,
     _phantom: PhantomData<fn(Opaque) -> Opaque>,
 }
 
@@ -269,9 +270,22 @@ impl<Opaque> PostgresRelation<Opaque> {
     pub unsafe fn new(raw: pgrx::pg_sys::Relation) -> Self {
         Self {
             raw,
+            fork: pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
             _phantom: PhantomData,
         }
     }
+    /// The init fork of an unlogged index. Free space is not tracked since
+    /// the free space map only covers the main fork.
+    pub unsafe fn new_init_fork(raw: pgrx::pg_sys::Relation) -> Self {
+        Self {
+            raw,
+            fork: pgrx::pg_sys::ForkNumber::INIT_FORKNUM,
+            _phantom: PhantomData,
+        }
+    }
+    fn tracking_freespace(&self, tracking_freespace: bool) -> bool {
+        tracking_freespace && self.fork == pgrx::pg_sys::ForkNumber::MAIN_FORKNUM
+    }
 }
 
 impl<O: Opaque> Relation for PostgresRelation<O> {
@@ -287,12 +301,11 @@ impl<O: Opaque> RelationRead for PostgresRelation<O> {
         assert!(id != u32::MAX, "no such page");
         unsafe {
             use pgrx::pg_sys::{
-                BUFFER_LOCK_SHARE, BufferGetPage, ForkNumber, LockBuffer, ReadBufferExtended,
-                ReadBufferMode,
+                BUFFER_LOCK_SHARE, BufferGetPage, LockBuffer, ReadBufferExtended, ReadBufferMode,
             };
             let buf = ReadBufferExtended(
                 self.raw,
-                ForkNumber::MAIN_FORKNUM,
+                self.fork,
                 id,
                 ReadBufferMode::RBM_NORMAL,
                 std::ptr::null_mut(),
@@ -313,12 +326,12 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
         assert!(id != u32::MAX, "no such page");
         unsafe {
             use pgrx::pg_sys::{
-                BUFFER_LOCK_EXCLUSIVE, ForkNumber, GenericXLogRegisterBuffer, GenericXLogStart,
-                LockBuffer, ReadBufferExtended, ReadBufferMode,
+                BUFFER_LOCK_EXCLUSIVE, GenericXLogRegisterBuffer, GenericXLogStart, LockBuffer,
+                ReadBufferExtended, ReadBufferMode,
             };
             let buf = ReadBufferExtended(
                 self.raw,
-                ForkNumber::MAIN_FORKNUM,
+                self.fork,
                 id,
                 ReadBufferMode::RBM_NORMAL,
                 std::ptr::null_mut(),
@@ -335,7 +348,7 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
                 page: page.cast(),
                 state,
                 id,
-                tracking_freespace,
+                tracking_freespace: self.tracking_freespace(tracking_freespace),
             }
         }
     }
@@ -352,14 +365,13 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
             #[cfg(any(feature = "pg14", feature = "pg15"))]
             {
                 use pgrx::pg_sys::{
-                    BUFFER_LOCK_EXCLUSIVE, ExclusiveLock, ForkNumber, LockBuffer,
-                    LockRelationForExtension, ReadBufferExtended, ReadBufferMode,
-                    UnlockRelationForExtension,
+                    BUFFER_LOCK_EXCLUSIVE, ExclusiveLock, LockBuffer, LockRelationForExtension,
+                    ReadBufferExtended, ReadBufferMode, UnlockRelationForExtension,
                 };
                 LockRelationForExtension(self.raw, ExclusiveLock as _);
                 buf = ReadBufferExtended(
                     self.raw,
-                    ForkNumber::MAIN_FORKNUM,
+                    self.fork,
                     u32::MAX,
                     ReadBufferMode::RBM_NORMAL,
                     std::ptr::null_mut(),
@@ -369,9 +381,7 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
             }
             #[cfg(any(feature = "pg16", feature = "pg17", feature = "pg18"))]
             {
-                use pgrx::pg_sys::{
-                    BufferManagerRelation, ExtendBufferedFlags, ExtendBufferedRel, ForkNumber,
-                };
+                use pgrx::pg_sys::{BufferManagerRelation, ExtendBufferedFlags, ExtendBufferedRel};
                 let bmr = BufferManagerRelation {
                     rel: self.raw,
                     smgr: std::ptr::null_mut(),
@@ -379,7 +389,7 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
                 };
                 buf = ExtendBufferedRel(
                     bmr,
-                    ForkNumber::MAIN_FORKNUM,
+                    self.fork,
                     std::ptr::null_mut(),
                     ExtendBufferedFlags::EB_LOCK_FIRST as _,
                 );
@@ -397,11 +407,14 @@ impl<O: Opaque> RelationWrite for PostgresRelation<O> {
                 page: page.cast(),
                 state,
                 id: pgrx::pg_sys::BufferGetBlockNumber(buf),
-                tracking_freespace,
+                tracking_freespace: self.tracking_freespace(tracking_freespace),
             }
         }
     }
     fn search(&self, freespace: usize) -> Option<PostgresBufferWriteGuard<O>> {
+        if !self.tracking_freespace(true) {
+            return None;
+        }
         unsafe {
             loop {
                 let id = pgrx::pg_sys::GetPageWithFreeSpace(self.raw, freespace);
@@ -426,7 +439,7 @@ impl<O: Opaque> RelationPrefetch for PostgresRelation<O> {
         assert!(id != u32::MAX, "no such page");
         unsafe {
             use pgrx::pg_sys::PrefetchBuffer;
-            PrefetchBuffer(self.raw, 0, id);
+            PrefetchBuffer(self.raw, self.fork, id);
         }
     }
 }
@@ -688,7 +701,7 @@ impl<O: Opaque> RelationReadStream for PostgresRelation<O> {
                 flags as i32,
                 core::ptr::null_mut(),
                 self.raw,
-                pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+                self.fork,
                 Some(callback::<I>),
                 cache.as_ptr().cast(),
                 0,
@@ -55,6 +55,40 @@ impl HeapTraverser {
             scan,
         }
     }
+    /// Only tuples visible to the MVCC snapshot are traversed, and heap-only
+    /// tuples are reported with the ids of their root tuples. The caller keeps
+    /// the snapshot registered until the traversal ends.
+    pub unsafe fn with_snapshot(
+        heap_relation: pgrx::pg_sys::Relation,
+        index_relation: pgrx::pg_sys::Relation,
+        index_info: *mut pgrx::pg_sys::IndexInfo,
+        snapshot: pgrx::pg_sys::Snapshot,
+    ) -> Self {
+        unsafe {
+            use pgrx::pg_sys::ScanOptions::*;
+            use pgrx::pg_sys::ffi::pg_guard_ffi_boundary;
+            let table_am = (*heap_relation).rd_tableam;
+            if table_am.is_null() {
+                panic!("unknown heap access method");
+            }
+            let scan_begin = (*table_am)
+                .scan_begin
+                .expect("unsupported heap access method");
+            let flags = SO_TYPE_SEQSCAN | SO_ALLOW_STRAT | SO_ALLOW_SYNC | SO_ALLOW_PAGEMODE;
+            #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
+            let scan = pg_guard_ffi_boundary(|| {
+                scan_begin(
+                    heap_relation,
+                    snapshot,
+                    0,
+                    std::ptr::null_mut(),
+                    std::ptr::null_mut(),
+                    flags,
+                )
+            });
+            Self::new(heap_relation, index_relation, index_info, scan)
+        }
+    }
 }
 
 impl Drop for HeapTraverser {
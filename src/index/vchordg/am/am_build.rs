@@ -128,10 +128,11 @@ pub unsafe extern "C-unwind" fn ambuild(
     if let Err(errors) = Validate::validate(&vchordg_options) {
         pgrx::error!("error while validating options: {}", errors);
     }
-    if vector_options.d != DistanceKind::L2S
+    if !matches!(vector_options.d, DistanceKind::L2S | DistanceKind::Hamming)
         && (vchordg_options.index.alpha != [1.0] && vchordg_options.index.alpha != [1.0, 1.2])
     {
-        let warnings = "alpha not equal to `1.0` are only applicable to l2 and cosine distance.";
+        let warnings =
+            "alpha not equal to `1.0` are only applicable to l2, cosine and hamming distance.";
         pgrx::warning!("warning while validating options: {warnings}");
     }
     let index = unsafe { PostgresRelation::new(index_relation) };
@@ -552,8 +553,16 @@ unsafe fn sequential_build(
 }
 
 #[pgrx::pg_guard]
-pub unsafe extern "C-unwind" fn ambuildempty(_index_relation: pgrx::pg_sys::Relation) {
-    pgrx::error!("Unlogged indexes are not supported.");
+pub unsafe extern "C-unwind" fn ambuildempty(index_relation: pgrx::pg_sys::Relation) {
+    use pgrx::pg_sys::ForkNumber::INIT_FORKNUM;
+    let (vector_options, vchordg_options) = unsafe { options(index_relation) };
+    let index = unsafe { PostgresRelation::new_init_fork(index_relation) };
+    crate::index::vchordg::dispatch::build(vector_options, vchordg_options.index, &index);
+    // buffers of unlogged relations are not WAL-logged, but init forks must be
+    unsafe {
+        let n = pgrx::pg_sys::RelationGetNumberOfBlocksInFork(index_relation, INIT_FORKNUM);
+        pgrx::pg_sys::log_newpage_range(index_relation, INIT_FORKNUM, 0, n, true);
+    }
 }
 
 unsafe fn options(
@@ -13,14 +13,18 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 mod am_build;
+mod am_verify;
+
+pub use am_verify::verify;
 
 use crate::index::fetcher::*;
 use crate::index::gucs;
+use crate::index::hook::{limit_count, orderby_datum};
 use crate::index::scanners::SearchBuilder;
 use crate::index::storage::PostgresRelation;
 use crate::index::vchordg::opclass::opfamily;
 use crate::index::vchordg::scanners::*;
-use crate::recorder::DefaultRecorder;
+use crate::recorder::{DefaultRecorder, Parameters, Recorder};
 use pgrx::datum::Internal;
 use pgrx::pg_sys::Datum;
 use std::cell::LazyCell;
@@ -29,6 +33,7 @@ use std::num::NonZero;
 use std::ops::DerefMut;
 use std::ptr::NonNull;
 use std::sync::OnceLock;
+use std::time::Instant;
 
 #[repr(C)]
 pub struct Reloption {
@@ -162,8 +167,8 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = const {
 };
 
 #[pgrx::pg_guard]
-pub unsafe extern "C-unwind" fn amvalidate(_opclass_oid: pgrx::pg_sys::Oid) -> bool {
-    true
+pub unsafe extern "C-unwind" fn amvalidate(opclass_oid: pgrx::pg_sys::Oid) -> bool {
+    unsafe { crate::index::vchordg::opclass::validate(opclass_oid) }
 }
 
 #[pgrx::pg_guard]
@@ -287,7 +292,10 @@ pub unsafe extern "C-unwind" fn ambulkdelete(
             pg_guard_ffi_boundary(|| callback(&mut ctid, callback_state))
         }
     };
-    crate::index::vchordg::dispatch::bulkdelete(opfamily, &index, check, callback);
+    let removed = crate::index::vchordg::dispatch::bulkdelete(opfamily, &index, check, callback);
+    unsafe {
+        (*stats).tuples_removed += removed as f64;
+    }
     stats
 }
 
@@ -310,7 +318,15 @@ pub unsafe extern "C-unwind" fn amvacuumcleanup(
         #[cfg(feature = "pg18")]
         pgrx::pg_sys::vacuum_delay_point(false);
     };
-    crate::index::vchordg::dispatch::maintain(opfamily, &index, check);
+    let maintain = crate::index::vchordg::dispatch::maintain(opfamily, &index, check);
+    unsafe {
+        (*stats).num_pages = pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            (*info).index,
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        );
+        (*stats).num_index_tuples = maintain.number_of_tuples as f64;
+        (*stats).estimated_count = false;
+    }
     stats
 }
 
@@ -325,11 +341,21 @@ pub unsafe extern "C-unwind" fn ambeginscan(
     let scan = unsafe { pgrx::pg_sys::RelationGetIndexScan(index_relation, n_keys, n_orderbys) };
     let scanner: Scanner = Scanner {
         hack: None,
+        limit: None,
+        projection: None,
+        rettype: pgrx::pg_sys::Oid::INVALID,
+        recorder: None,
         scanning: LazyCell::new(Box::new(|| Box::new(std::iter::empty()))),
         bump: Box::new(bumpalo::Bump::new()),
     };
     unsafe {
         (*scan).opaque = CurrentMemoryContext.leak_and_drop_on_delete(scanner).cast();
+        if n_orderbys > 0 {
+            let n = n_orderbys as usize;
+            (*scan).xs_orderbyvals = pgrx::pg_sys::palloc0(n * size_of::<Datum>()).cast();
+            (*scan).xs_orderbynulls = pgrx::pg_sys::palloc(n * size_of::<bool>()).cast();
+            std::ptr::write_bytes((*scan).xs_orderbynulls, 1, n);
+        }
     }
     scan
 }
@@ -358,6 +384,14 @@ pub unsafe extern "C-unwind" fn amrescan(
         let scanner = &mut *(*scan).opaque.cast::<Scanner>();
         scanner.scanning = LazyCell::new(Box::new(|| Box::new(std::iter::empty())));
         scanner.bump.reset();
+        if let Some(recorder) = scanner.recorder.take() {
+            recorder.flush();
+        }
+        scanner.rettype = if (*scan).numberOfOrderBys > 0 {
+            pgrx::pg_sys::get_func_rettype((*(*scan).orderByData).sk_func.fn_oid)
+        } else {
+            pgrx::pg_sys::Oid::INVALID
+        };
         let opfamily = opfamily((*scan).indexRelation);
         let index = PostgresRelation::new((*scan).indexRelation);
         let options = SearchOptions {
@@ -366,6 +400,7 @@ pub unsafe extern "C-unwind" fn amrescan(
             max_scan_tuples: gucs::vchordg_max_scan_tuples(),
             io_search: gucs::vchordg_io_search(),
             io_rerank: gucs::vchordg_io_rerank(),
+            prefilter: gucs::vchordg_prefilter(),
         };
         let fetcher = {
             let hack = scanner.hack;
@@ -383,13 +418,22 @@ pub unsafe extern "C-unwind" fn amrescan(
                 )
             })
         };
-        // Query recorde is disable for vchordg indexes for now.
-        let recorder = DefaultRecorder {
-            enable: false,
-            rate: None,
-            max_records: 0,
-            index: (*(*scan).indexRelation).rd_id.to_u32(),
+        let rate = match gucs::vchordg_query_sampling_rate() {
+            0.0 => None,
+            rate => Some(rate),
         };
+        let recorder = DefaultRecorder::new(
+            gucs::vchordg_query_sampling_enable(),
+            rate,
+            gucs::vchordg_query_sampling_max_records(),
+            (*(*scan).indexRelation).rd_id.to_u32(),
+            Parameters {
+                max_scan_tuples: options.max_scan_tuples,
+                prefilter: Some(options.prefilter),
+                ..Default::default()
+            },
+        );
+        scanner.recorder = recorder.is_enabled().then(|| recorder.clone());
         // PAY ATTENTATION: `scanning` references `bump`, so `scanning` must be dropped before `bump`.
         let bump = scanner.bump.as_ref();
         scanner.scanning = match opfamily {
@@ -404,7 +448,9 @@ pub unsafe extern "C-unwind" fn amrescan(
             | Opfamily::Rabitq8Ip
             | Opfamily::Rabitq4L2
             | Opfamily::Rabitq4Cosine
-            | Opfamily::Rabitq4Ip => {
+            | Opfamily::Rabitq4Ip
+            | Opfamily::BitHamming
+            | Opfamily::BitJaccard => {
                 let mut builder = DefaultBuilder::new(opfamily);
                 for i in 0..(*scan).numberOfOrderBys {
                     let data = (*scan).orderByData.add(i as usize);
@@ -424,6 +470,29 @@ pub unsafe extern "C-unwind" fn amrescan(
                     builder.build(index, options, fetcher, bump, recorder)
                 }))
             }
+            Opfamily::VectorMaxsim
+            | Opfamily::HalfvecMaxsim
+            | Opfamily::Rabitq8Maxsim
+            | Opfamily::Rabitq4Maxsim => {
+                let mut builder = MaxsimBuilder::new(opfamily);
+                for i in 0..(*scan).numberOfOrderBys {
+                    let data = (*scan).orderByData.add(i as usize);
+                    let value = (*data).sk_argument;
+                    let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
+                    builder.add((*data).sk_strategy, (!is_null).then_some(value));
+                }
+                for i in 0..(*scan).numberOfKeys {
+                    let data = (*scan).keyData.add(i as usize);
+                    let value = (*data).sk_argument;
+                    let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
+                    builder.add((*data).sk_strategy, (!is_null).then_some(value));
+                }
+                LazyCell::new(Box::new(move || {
+                    // only do this since `PostgresRelation` has no destructor
+                    let index = bump.alloc(index.clone());
+                    builder.build(index, options, fetcher, bump, recorder)
+                }))
+            }
         };
     }
 }
@@ -445,11 +514,34 @@ pub unsafe extern "C-unwind" fn amgettuple(
         pgrx::error!("scanning with a non-MVCC-compliant snapshot is not supported");
     }
     let scanner = unsafe { (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked() };
-    if let Some((_, key, recheck)) = scanner.scanning.deref_mut().next() {
+    let next = if let Some(recorder) = scanner.recorder.as_ref() {
+        if let Some(limit) = scanner.limit {
+            recorder.set_limit(unsafe { limit_count(limit) });
+        }
+        let start = Instant::now();
+        let next = scanner.scanning.deref_mut().next();
+        recorder.observe(start.elapsed(), next.is_some() as u64);
+        next
+    } else {
+        scanner.scanning.deref_mut().next()
+    };
+    if let Some((distance, key, recheck)) = next {
         unsafe {
             (*scan).xs_heaptid = key_to_ctid(key);
             (*scan).xs_recheck = recheck;
             (*scan).xs_recheckorderby = false;
+            // vector search supports only one `ORDER BY` clause, so the distance
+            // is always the value of the first one
+            if (*scan).numberOfOrderBys > 0 {
+                let value = orderby_datum(scanner.rettype, distance);
+                (*scan).xs_orderbyvals.write(value);
+                (*scan).xs_orderbynulls.write(false);
+                if let Some(mut projection) = scanner.projection {
+                    let projection = projection.as_mut();
+                    projection.value = value;
+                    projection.isnull = false;
+                }
+            }
         }
         true
     } else {
@@ -462,12 +554,19 @@ pub unsafe extern "C-unwind" fn amendscan(scan: pgrx::pg_sys::IndexScanDesc) {
     let scanner = unsafe { &mut *(*scan).opaque.cast::<Scanner>() };
     scanner.scanning = LazyCell::new(Box::new(|| Box::new(std::iter::empty())));
     scanner.bump.reset();
+    if let Some(recorder) = scanner.recorder.take() {
+        recorder.flush();
+    }
 }
 
 type Iter = Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
 
 pub struct Scanner {
     pub hack: Option<NonNull<pgrx::pg_sys::IndexScanState>>,
+    pub limit: Option<NonNull<pgrx::pg_sys::LimitState>>,
+    pub projection: Option<NonNull<pgrx::pg_sys::ParamExecData>>,
+    rettype: pgrx::pg_sys::Oid,
+    recorder: Option<DefaultRecorder>,
     scanning: LazyCell<Iter, Box<dyn FnOnce() -> Iter>>,
     bump: Box<bumpalo::Bump>,
 }
@@ -18,12 +18,14 @@ use index::prefetcher::*;
 use index::relation::{
     Hints, Page, RelationPrefetch, RelationRead, RelationReadStream, RelationWrite,
 };
-use index_accessor::{Dot, L2S};
+use index_accessor::{Dot, Hamming, Jaccard, L2S};
 use simd::f16;
 use std::num::NonZero;
 use vchordg::operator::Op;
 use vchordg::types::*;
+use vchordg::{GraphStats, Maintain};
 use vector::VectorOwned;
+use vector::bvect::BVectOwned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
 use vector::vect::{VectBorrowed, VectOwned};
@@ -58,6 +60,66 @@ where
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordg::prewarm::<_, Op<Rabitq4Owned, Dot>>(index)
         }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordg::prewarm::<_, Op<BVectOwned, Hamming>>(index)
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordg::prewarm::<_, Op<BVectOwned, Jaccard>>(index)
+        }
+        (
+            VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Rabitq8 | VectorKind::Rabitq4,
+            DistanceKind::Hamming | DistanceKind::Jaccard,
+        ) => unreachable!(),
+        (VectorKind::Bit, DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
+    }
+}
+
+pub fn graph_stats<R>(
+    opfamily: Opfamily,
+    index: &R,
+    number_of_pages: u32,
+    check: impl Fn(),
+) -> GraphStats
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordg::Opaque>,
+{
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordg::graph_stats::<_, Op<VectOwned<f32>, L2S>>(index, number_of_pages, check)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordg::graph_stats::<_, Op<VectOwned<f32>, Dot>>(index, number_of_pages, check)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordg::graph_stats::<_, Op<VectOwned<f16>, L2S>>(index, number_of_pages, check)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordg::graph_stats::<_, Op<VectOwned<f16>, Dot>>(index, number_of_pages, check)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordg::graph_stats::<_, Op<Rabitq8Owned, L2S>>(index, number_of_pages, check)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordg::graph_stats::<_, Op<Rabitq8Owned, Dot>>(index, number_of_pages, check)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordg::graph_stats::<_, Op<Rabitq4Owned, L2S>>(index, number_of_pages, check)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordg::graph_stats::<_, Op<Rabitq4Owned, Dot>>(index, number_of_pages, check)
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordg::graph_stats::<_, Op<BVectOwned, Hamming>>(index, number_of_pages, check)
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordg::graph_stats::<_, Op<BVectOwned, Jaccard>>(index, number_of_pages, check)
+        }
+        (
+            VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Rabitq8 | VectorKind::Rabitq4,
+            DistanceKind::Hamming | DistanceKind::Jaccard,
+        ) => unreachable!(),
+        (VectorKind::Bit, DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
     }
 }
 
@@ -66,68 +128,141 @@ pub fn bulkdelete<R>(
     index: &R,
     check: impl Fn(),
     callback: impl Fn(NonZero<u64>) -> bool,
-) where
+) -> u64
+where
     R: RelationRead + RelationWrite,
     R::Page: Page<Opaque = vchordg::Opaque>,
 {
     match (opfamily.vector_kind(), opfamily.distance_kind()) {
         (VectorKind::Vecf32, DistanceKind::L2S) => {
-            vchordg::bulkdelete::<_, Op<VectOwned<f32>, L2S>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<VectOwned<f32>, L2S>>(index, &check, &callback)
         }
         (VectorKind::Vecf32, DistanceKind::Dot) => {
-            vchordg::bulkdelete::<_, Op<VectOwned<f32>, Dot>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<VectOwned<f32>, Dot>>(index, &check, &callback)
         }
         (VectorKind::Vecf16, DistanceKind::L2S) => {
-            vchordg::bulkdelete::<_, Op<VectOwned<f16>, L2S>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<VectOwned<f16>, L2S>>(index, &check, &callback)
         }
         (VectorKind::Vecf16, DistanceKind::Dot) => {
-            vchordg::bulkdelete::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback)
         }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
-            vchordg::bulkdelete::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback)
         }
         (VectorKind::Rabitq8, DistanceKind::Dot) => {
-            vchordg::bulkdelete::<_, Op<Rabitq8Owned, Dot>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<Rabitq8Owned, Dot>>(index, &check, &callback)
         }
         (VectorKind::Rabitq4, DistanceKind::L2S) => {
-            vchordg::bulkdelete::<_, Op<Rabitq4Owned, L2S>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<Rabitq4Owned, L2S>>(index, &check, &callback)
         }
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
-            vchordg::bulkdelete::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback);
+            vchordg::bulkdelete::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback)
         }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordg::bulkdelete::<_, Op<BVectOwned, Hamming>>(index, &check, &callback)
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordg::bulkdelete::<_, Op<BVectOwned, Jaccard>>(index, &check, &callback)
+        }
+        (
+            VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Rabitq8 | VectorKind::Rabitq4,
+            DistanceKind::Hamming | DistanceKind::Jaccard,
+        ) => unreachable!(),
+        (VectorKind::Bit, DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
     }
 }
 
-pub fn maintain<R>(opfamily: Opfamily, index: &R, check: impl Fn())
+pub fn maintain<R>(opfamily: Opfamily, index: &R, check: impl Fn()) -> Maintain
 where
     R: RelationRead + RelationWrite,
     R::Page: Page<Opaque = vchordg::Opaque>,
 {
     match (opfamily.vector_kind(), opfamily.distance_kind()) {
         (VectorKind::Vecf32, DistanceKind::L2S) => {
-            vchordg::maintain::<_, Op<VectOwned<f32>, L2S>>(index, &check);
+            vchordg::maintain::<_, Op<VectOwned<f32>, L2S>>(index, &check)
         }
         (VectorKind::Vecf32, DistanceKind::Dot) => {
-            vchordg::maintain::<_, Op<VectOwned<f32>, Dot>>(index, &check);
+            vchordg::maintain::<_, Op<VectOwned<f32>, Dot>>(index, &check)
         }
         (VectorKind::Vecf16, DistanceKind::L2S) => {
-            vchordg::maintain::<_, Op<VectOwned<f16>, L2S>>(index, &check);
+            vchordg::maintain::<_, Op<VectOwned<f16>, L2S>>(index, &check)
         }
         (VectorKind::Vecf16, DistanceKind::Dot) => {
-            vchordg::maintain::<_, Op<VectOwned<f16>, Dot>>(index, &check);
+            vchordg::maintain::<_, Op<VectOwned<f16>, Dot>>(index, &check)
         }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
-            vchordg::maintain::<_, Op<Rabitq8Owned, L2S>>(index, &check);
+            vchordg::maintain::<_, Op<Rabitq8Owned, L2S>>(index, &check)
         }
         (VectorKind::Rabitq8, DistanceKind::Dot) => {
-            vchordg::maintain::<_, Op<Rabitq8Owned, Dot>>(index, &check);
+            vchordg::maintain::<_, Op<Rabitq8Owned, Dot>>(index, &check)
         }
         (VectorKind::Rabitq4, DistanceKind::L2S) => {
-            vchordg::maintain::<_, Op<Rabitq4Owned, L2S>>(index, &check);
+            vchordg::maintain::<_, Op<Rabitq4Owned, L2S>>(index, &check)
         }
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
-            vchordg::maintain::<_, Op<Rabitq4Owned, Dot>>(index, &check);
+            vchordg::maintain::<_, Op<Rabitq4Owned, Dot>>(index, &check)
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordg::maintain::<_, Op<BVectOwned, Hamming>>(index, &check)
         }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordg::maintain::<_, Op<BVectOwned, Jaccard>>(index, &check)
+        }
+        (
+            VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Rabitq8 | VectorKind::Rabitq4,
+            DistanceKind::Hamming | DistanceKind::Jaccard,
+        ) => unreachable!(),
+        (VectorKind::Bit, DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
+    }
+}
+
+pub fn verify<R>(
+    opfamily: Opfamily,
+    index: &R,
+    number_of_pages: u32,
+    check: impl Fn(),
+    callback: impl FnMut(NonZero<u64>),
+) -> Vec<vchordg::Problem>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordg::Opaque>,
+{
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<VectOwned<f32>, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<VectOwned<f32>, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<VectOwned<f16>, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<VectOwned<f16>, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<Rabitq8Owned, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<Rabitq8Owned, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordg::verify::<_, Op<Rabitq4Owned, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordg::verify::<_, Op<Rabitq4Owned, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordg::verify::<_, Op<BVectOwned, Hamming>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordg::verify::<_, Op<BVectOwned, Jaccard>>(index, number_of_pages, check, callback)
+        }
+        (
+            VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Rabitq8 | VectorKind::Rabitq4,
+            DistanceKind::Hamming | DistanceKind::Jaccard,
+        ) => unreachable!(),
+        (VectorKind::Bit, DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
     }
 }
 
@@ -161,6 +296,17 @@ where
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordg::build::<_, Op<Rabitq4Owned, Dot>>(vector_options, vchordg_options, index)
         }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordg::build::<_, Op<BVectOwned, Hamming>>(vector_options, vchordg_options, index)
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordg::build::<_, Op<BVectOwned, Jaccard>>(vector_options, vchordg_options, index)
+        }
+        (
+            VectorKind::Vecf32 | VectorKind::Vecf16 | VectorKind::Rabitq8 | VectorKind::Rabitq4,
+            DistanceKind::Hamming | DistanceKind::Jaccard,
+        ) => unreachable!(),
+        (VectorKind::Bit, DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
     }
 }
 
@@ -265,6 +411,36 @@ where
                 make_vector_plain_prefetcher,
             )
         }
+        (OwnedVector::Bit(unprojected), DistanceKind::Hamming) => {
+            assert!(opfamily.vector_kind() == VectorKind::Bit);
+            vchordg::insert::<_, Op<BVectOwned, Hamming>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (OwnedVector::Bit(unprojected), DistanceKind::Jaccard) => {
+            assert!(opfamily.vector_kind() == VectorKind::Bit);
+            vchordg::insert::<_, Op<BVectOwned, Jaccard>>(
+                index,
+                unprojected.as_borrowed(),
+                payload,
+                &bump,
+                make_vertex_plain_prefetcher,
+                make_vector_plain_prefetcher,
+            )
+        }
+        (
+            OwnedVector::Vecf32(_)
+            | OwnedVector::Vecf16(_)
+            | OwnedVector::Rabitq8(_)
+            | OwnedVector::Rabitq4(_),
+            DistanceKind::Hamming | DistanceKind::Jaccard,
+        ) => unreachable!(),
+        (OwnedVector::Bit(_), DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
     }
 }
 
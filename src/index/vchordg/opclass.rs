@@ -12,11 +12,12 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use crate::datatype::memory_bit::{BitInput, BitOutput};
 use crate::datatype::memory_halfvec::{HalfvecInput, HalfvecOutput};
 use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
 use crate::datatype::memory_rabitq8::{Rabitq8Input, Rabitq8Output};
 use crate::datatype::memory_vector::{VectorInput, VectorOutput};
-use crate::index::opclass::Sphere;
+use crate::index::opclass::{Shape, Sphere, support};
 use distance::Distance;
 use pgrx::datum::FromDatum;
 use pgrx::heap_tuple::PgHeapTuple;
@@ -39,9 +40,38 @@ pub enum Opfamily {
     Rabitq4L2,
     Rabitq4Cosine,
     Rabitq4Ip,
+    BitHamming,
+    BitJaccard,
+    VectorMaxsim,
+    HalfvecMaxsim,
+    Rabitq8Maxsim,
+    Rabitq4Maxsim,
 }
 
 impl Opfamily {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "vchordg_vector_l2_ops" => Some(Self::VectorL2),
+            "vchordg_vector_ip_ops" => Some(Self::VectorIp),
+            "vchordg_vector_cosine_ops" => Some(Self::VectorCosine),
+            "vchordg_halfvec_l2_ops" => Some(Self::HalfvecL2),
+            "vchordg_halfvec_ip_ops" => Some(Self::HalfvecIp),
+            "vchordg_halfvec_cosine_ops" => Some(Self::HalfvecCosine),
+            "vchordg_rabitq8_l2_ops" => Some(Self::Rabitq8L2),
+            "vchordg_rabitq8_ip_ops" => Some(Self::Rabitq8Ip),
+            "vchordg_rabitq8_cosine_ops" => Some(Self::Rabitq8Cosine),
+            "vchordg_rabitq4_l2_ops" => Some(Self::Rabitq4L2),
+            "vchordg_rabitq4_ip_ops" => Some(Self::Rabitq4Ip),
+            "vchordg_rabitq4_cosine_ops" => Some(Self::Rabitq4Cosine),
+            "vchordg_bit_hamming_ops" => Some(Self::BitHamming),
+            "vchordg_bit_jaccard_ops" => Some(Self::BitJaccard),
+            "vchordg_vector_maxsim_ops" => Some(Self::VectorMaxsim),
+            "vchordg_halfvec_maxsim_ops" => Some(Self::HalfvecMaxsim),
+            "vchordg_rabitq8_maxsim_ops" => Some(Self::Rabitq8Maxsim),
+            "vchordg_rabitq4_maxsim_ops" => Some(Self::Rabitq4Maxsim),
+            _ => None,
+        }
+    }
     fn input(self, vector: BorrowedVector<'_>) -> OwnedVector {
         use {BorrowedVector as B, OwnedVector as O};
         match (self, vector) {
@@ -69,6 +99,18 @@ impl Opfamily {
             (Self::Rabitq4Cosine, _) => unreachable!(),
             (Self::Rabitq4Ip, B::Rabitq4(x)) => O::Rabitq4(x.own()),
             (Self::Rabitq4Ip, _) => unreachable!(),
+            (Self::BitHamming, B::Bit(x)) => O::Bit(x.own()),
+            (Self::BitHamming, _) => unreachable!(),
+            (Self::BitJaccard, B::Bit(x)) => O::Bit(x.own()),
+            (Self::BitJaccard, _) => unreachable!(),
+            (Self::VectorMaxsim, B::Vecf32(x)) => O::Vecf32(x.own()),
+            (Self::VectorMaxsim, _) => unreachable!(),
+            (Self::HalfvecMaxsim, B::Vecf16(x)) => O::Vecf16(x.own()),
+            (Self::HalfvecMaxsim, _) => unreachable!(),
+            (Self::Rabitq8Maxsim, B::Rabitq8(x)) => O::Rabitq8(x.own()),
+            (Self::Rabitq8Maxsim, _) => unreachable!(),
+            (Self::Rabitq4Maxsim, B::Rabitq4(x)) => O::Rabitq4(x.own()),
+            (Self::Rabitq4Maxsim, _) => unreachable!(),
         }
     }
     pub unsafe fn store(self, datum: Datum) -> Option<Vec<(OwnedVector, u16)>> {
@@ -92,6 +134,61 @@ impl Opfamily {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 vec![(self.input(BorrowedVector::Rabitq4(vector.as_borrowed())), 0)]
             }
+            Self::BitHamming | Self::BitJaccard => {
+                let vector = unsafe { BitInput::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Bit(vector.as_borrowed())), 0)]
+            }
+            Self::VectorMaxsim => {
+                let vectors =
+                    unsafe { pgrx::datum::Array::<VectorInput>::from_datum(datum, false).unwrap() };
+                let mut result = Vec::with_capacity(vectors.len());
+                for (i, vector) in vectors.iter_deny_null().enumerate() {
+                    result.push((
+                        self.input(BorrowedVector::Vecf32(vector.as_borrowed())),
+                        i as u16,
+                    ));
+                }
+                result
+            }
+            Self::HalfvecMaxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<HalfvecInput>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for (i, vector) in vectors.iter_deny_null().enumerate() {
+                    result.push((
+                        self.input(BorrowedVector::Vecf16(vector.as_borrowed())),
+                        i as u16,
+                    ));
+                }
+                result
+            }
+            Self::Rabitq8Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq8Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for (i, vector) in vectors.iter_deny_null().enumerate() {
+                    result.push((
+                        self.input(BorrowedVector::Rabitq8(vector.as_borrowed())),
+                        i as u16,
+                    ));
+                }
+                result
+            }
+            Self::Rabitq4Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq4Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for (i, vector) in vectors.iter_deny_null().enumerate() {
+                    result.push((
+                        self.input(BorrowedVector::Rabitq4(vector.as_borrowed())),
+                        i as u16,
+                    ));
+                }
+                result
+            }
         };
         Some(store)
     }
@@ -103,22 +200,26 @@ impl Opfamily {
         let attno_2 = NonZero::new(2_usize).unwrap();
         let tuple = unsafe { PgHeapTuple::from_composite_datum(datum) };
         let center = match self {
-            Self::VectorL2 | Self::VectorCosine | Self::VectorIp => {
+            Self::VectorL2 | Self::VectorCosine | Self::VectorIp | Self::VectorMaxsim => {
                 let vector = tuple.get_by_index::<VectorOutput>(attno_1).unwrap()?;
                 self.input(BorrowedVector::Vecf32(vector.as_borrowed()))
             }
-            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp => {
+            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp | Self::HalfvecMaxsim => {
                 let vector = tuple.get_by_index::<HalfvecOutput>(attno_1).unwrap()?;
                 self.input(BorrowedVector::Vecf16(vector.as_borrowed()))
             }
-            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip => {
+            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip | Self::Rabitq8Maxsim => {
                 let vector = tuple.get_by_index::<Rabitq8Output>(attno_1).unwrap()?;
                 self.input(BorrowedVector::Rabitq8(vector.as_borrowed()))
             }
-            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip => {
+            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip | Self::Rabitq4Maxsim => {
                 let vector = tuple.get_by_index::<Rabitq4Output>(attno_1).unwrap()?;
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::BitHamming | Self::BitJaccard => {
+                let vector = tuple.get_by_index::<BitOutput>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Bit(vector.as_borrowed()))
+            }
         };
         let radius = tuple.get_by_index::<f32>(attno_2).unwrap()?;
         Some(Sphere { center, radius })
@@ -128,25 +229,77 @@ impl Opfamily {
             return None;
         }
         let vector = match self {
-            Self::VectorL2 | Self::VectorCosine | Self::VectorIp => {
+            Self::VectorL2 | Self::VectorCosine | Self::VectorIp | Self::VectorMaxsim => {
                 let vector = unsafe { VectorInput::from_datum(datum, false).unwrap() };
                 self.input(BorrowedVector::Vecf32(vector.as_borrowed()))
             }
-            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp => {
+            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp | Self::HalfvecMaxsim => {
                 let vector = unsafe { HalfvecInput::from_datum(datum, false).unwrap() };
                 self.input(BorrowedVector::Vecf16(vector.as_borrowed()))
             }
-            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip => {
+            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip | Self::Rabitq8Maxsim => {
                 let vector = unsafe { Rabitq8Input::from_datum(datum, false).unwrap() };
                 self.input(BorrowedVector::Rabitq8(vector.as_borrowed()))
             }
-            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip => {
+            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip | Self::Rabitq4Maxsim => {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::BitHamming | Self::BitJaccard => {
+                let vector = unsafe { BitInput::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Bit(vector.as_borrowed()))
+            }
         };
         Some(vector)
     }
+    pub unsafe fn input_vectors(self, datum: Datum) -> Option<Vec<OwnedVector>> {
+        if datum.is_null() {
+            return None;
+        }
+        let vectors = match self {
+            Self::VectorL2 | Self::VectorCosine | Self::VectorIp | Self::VectorMaxsim => {
+                let vectors =
+                    unsafe { pgrx::datum::Array::<VectorInput>::from_datum(datum, false).unwrap() };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Vecf32(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp | Self::HalfvecMaxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<HalfvecInput>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Vecf16(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip | Self::Rabitq8Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq8Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Rabitq8(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip | Self::Rabitq4Maxsim => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<Rabitq4Input>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Rabitq4(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::BitHamming | Self::BitJaccard => unreachable!(),
+        };
+        Some(vectors)
+    }
     pub fn output(self, x: Distance) -> f32 {
         match self {
             Self::VectorCosine
@@ -157,6 +310,11 @@ impl Opfamily {
                 x.to_f32().sqrt()
             }
             Self::VectorIp | Self::HalfvecIp | Self::Rabitq8Ip | Self::Rabitq4Ip => x.to_f32(),
+            Self::BitHamming | Self::BitJaccard => x.to_f32(),
+            Self::VectorMaxsim
+            | Self::HalfvecMaxsim
+            | Self::Rabitq8Maxsim
+            | Self::Rabitq4Maxsim => x.to_f32(),
         }
     }
     pub const fn distance_kind(self) -> DistanceKind {
@@ -171,14 +329,82 @@ impl Opfamily {
             Self::VectorIp | Self::HalfvecIp | Self::Rabitq8Ip | Self::Rabitq4Ip => {
                 DistanceKind::Dot
             }
+            Self::VectorMaxsim
+            | Self::HalfvecMaxsim
+            | Self::Rabitq8Maxsim
+            | Self::Rabitq4Maxsim => DistanceKind::Dot,
+            Self::BitHamming => DistanceKind::Hamming,
+            Self::BitJaccard => DistanceKind::Jaccard,
         }
     }
     pub const fn vector_kind(self) -> VectorKind {
         match self {
-            Self::VectorL2 | Self::VectorCosine | Self::VectorIp => VectorKind::Vecf32,
-            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp => VectorKind::Vecf16,
-            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip => VectorKind::Rabitq8,
-            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip => VectorKind::Rabitq4,
+            Self::VectorL2 | Self::VectorCosine | Self::VectorIp | Self::VectorMaxsim => {
+                VectorKind::Vecf32
+            }
+            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp | Self::HalfvecMaxsim => {
+                VectorKind::Vecf16
+            }
+            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip | Self::Rabitq8Maxsim => {
+                VectorKind::Rabitq8
+            }
+            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip | Self::Rabitq4Maxsim => {
+                VectorKind::Rabitq4
+            }
+            Self::BitHamming | Self::BitJaccard => VectorKind::Bit,
+        }
+    }
+    pub const fn shape(self) -> Shape {
+        let (input, sphere) = match self {
+            Self::VectorL2 | Self::VectorCosine | Self::VectorIp => ("vector", "sphere_vector"),
+            Self::HalfvecL2 | Self::HalfvecCosine | Self::HalfvecIp => {
+                ("halfvec", "sphere_halfvec")
+            }
+            Self::Rabitq8L2 | Self::Rabitq8Cosine | Self::Rabitq8Ip => {
+                ("rabitq8", "sphere_rabitq8")
+            }
+            Self::Rabitq4L2 | Self::Rabitq4Cosine | Self::Rabitq4Ip => {
+                ("rabitq4", "sphere_rabitq4")
+            }
+            Self::BitHamming | Self::BitJaccard => ("bit", "sphere_bit"),
+            Self::VectorMaxsim => {
+                return Shape {
+                    input: "_vector",
+                    order: 3,
+                    sphere: None,
+                    multi_query: None,
+                };
+            }
+            Self::HalfvecMaxsim => {
+                return Shape {
+                    input: "_halfvec",
+                    order: 3,
+                    sphere: None,
+                    multi_query: None,
+                };
+            }
+            Self::Rabitq8Maxsim => {
+                return Shape {
+                    input: "_rabitq8",
+                    order: 3,
+                    sphere: None,
+                    multi_query: None,
+                };
+            }
+            Self::Rabitq4Maxsim => {
+                return Shape {
+                    input: "_rabitq4",
+                    order: 3,
+                    sphere: None,
+                    multi_query: None,
+                };
+            }
+        };
+        Shape {
+            input,
+            order: 1,
+            sphere: Some(sphere),
+            multi_query: None,
         }
     }
 }
@@ -192,50 +418,13 @@ pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
         pgrx::error!("support function 1 is not found");
     }
 
-    let mut flinfo = pgrx::pg_sys::FmgrInfo::default();
-
-    unsafe {
-        pgrx::pg_sys::fmgr_info(proc, &mut flinfo);
-    }
-
-    let fn_addr = flinfo.fn_addr.expect("null function pointer");
+    let result_string = unsafe { support(proc) }.expect("null return value");
 
-    let mut fcinfo = unsafe { std::mem::zeroed::<pgrx::pg_sys::FunctionCallInfoBaseData>() };
-    fcinfo.flinfo = &mut flinfo;
-    fcinfo.fncollation = pgrx::pg_sys::DEFAULT_COLLATION_OID;
-    fcinfo.context = std::ptr::null_mut();
-    fcinfo.resultinfo = std::ptr::null_mut();
-    fcinfo.isnull = true;
-    fcinfo.nargs = 0;
-
-    let result_datum = unsafe {
-        #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
-        pgrx::pg_sys::ffi::pg_guard_ffi_boundary(|| fn_addr(&mut fcinfo))
-    };
-
-    let result_option = unsafe { String::from_datum(result_datum, fcinfo.isnull) };
-
-    let result_string = result_option.expect("null return value");
-
-    let result = match result_string.as_str() {
-        "vchordg_vector_l2_ops" => Opfamily::VectorL2,
-        "vchordg_vector_ip_ops" => Opfamily::VectorIp,
-        "vchordg_vector_cosine_ops" => Opfamily::VectorCosine,
-        "vchordg_halfvec_l2_ops" => Opfamily::HalfvecL2,
-        "vchordg_halfvec_ip_ops" => Opfamily::HalfvecIp,
-        "vchordg_halfvec_cosine_ops" => Opfamily::HalfvecCosine,
-        "vchordg_rabitq8_l2_ops" => Opfamily::Rabitq8L2,
-        "vchordg_rabitq8_ip_ops" => Opfamily::Rabitq8Ip,
-        "vchordg_rabitq8_cosine_ops" => Opfamily::Rabitq8Cosine,
-        "vchordg_rabitq4_l2_ops" => Opfamily::Rabitq4L2,
-        "vchordg_rabitq4_ip_ops" => Opfamily::Rabitq4Ip,
-        "vchordg_rabitq4_cosine_ops" => Opfamily::Rabitq4Cosine,
-        _ => pgrx::error!("unknown operator class"),
-    };
+    Opfamily::from_name(&result_string).unwrap_or_else(|| pgrx::error!("unknown operator class"))
+}
 
+pub unsafe fn validate(opclass: pgrx::pg_sys::Oid) -> bool {
     unsafe {
-        pgrx::pg_sys::pfree(result_datum.cast_mut_ptr());
+        crate::index::opclass::validate(opclass, |name| Some(Opfamily::from_name(name)?.shape()))
     }
-
-    result
 }
@@ -12,7 +12,7 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
-use crate::index::fetcher::{Fetcher, pointer_to_kv};
+use crate::index::fetcher::{Fetcher, FilterableTuple, pointer_to_kv};
 use crate::index::opclass::Sphere;
 use crate::index::scanners::{Io, SearchBuilder};
 use crate::index::vchordg::dispatch::*;
@@ -22,12 +22,13 @@ use crate::recorder::{Recorder, text};
 use distance::Distance;
 use index::bump::Bump;
 use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
-use index_accessor::{Dot, L2S};
+use index_accessor::{Dot, Hamming, Jaccard, L2S};
 use simd::f16;
 use std::num::NonZero;
 use vchordg::operator::{self};
 use vchordg::search;
 use vchordg::types::{DistanceKind, OwnedVector, VectorKind};
+use vector::bvect::{BVectBorrowed, BVectOwned};
 use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
 use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
 use vector::vect::{VectBorrowed, VectOwned};
@@ -61,6 +62,8 @@ impl SearchBuilder for DefaultBuilder {
                 | Opfamily::Rabitq4Cosine
                 | Opfamily::Rabitq4L2
                 | Opfamily::Rabitq4Ip
+                | Opfamily::BitHamming
+                | Opfamily::BitJaccard
         ));
         Self {
             opfamily,
@@ -87,7 +90,7 @@ impl SearchBuilder for DefaultBuilder {
         self,
         index: &'b R,
         options: SearchOptions,
-        _fetcher: impl Fetcher + 'b,
+        mut fetcher: impl Fetcher + 'b,
         bump: &'b impl Bump,
         recorder: impl Recorder,
     ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
@@ -130,6 +133,17 @@ impl SearchBuilder for DefaultBuilder {
             index,
             hints: vector_hints,
         };
+        let prefilter = options.prefilter;
+        let predicate = move |payload| {
+            if !prefilter {
+                return true;
+            }
+            let (key, _) = pointer_to_kv(payload);
+            let Some(mut tuple) = fetcher.fetch(key) else {
+                return false;
+            };
+            tuple.filter()
+        };
         let iter: Box<dyn Iterator<Item = (Distance, NonZero<u64>)>> =
             match (opfamily.vector_kind(), opfamily.distance_kind()) {
                 (VectorKind::Vecf32, DistanceKind::L2S) => {
@@ -152,6 +166,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -161,6 +176,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -170,6 +186,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -179,6 +196,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -188,6 +206,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -197,6 +216,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -206,6 +226,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -215,6 +236,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -224,6 +246,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
@@ -247,6 +270,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -256,6 +280,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -265,6 +290,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -274,6 +300,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -283,6 +310,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -292,6 +320,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -301,6 +330,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -310,6 +340,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -319,6 +350,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
@@ -342,6 +374,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -351,6 +384,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -360,6 +394,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -369,6 +404,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -378,6 +414,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -387,6 +424,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -396,6 +434,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -405,6 +444,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -414,6 +454,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
@@ -437,6 +478,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -446,6 +488,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -455,6 +498,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -464,6 +508,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -473,6 +518,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -482,6 +528,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -491,6 +538,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -500,6 +548,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -509,6 +558,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
@@ -536,6 +586,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -545,6 +596,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -554,6 +606,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -563,6 +616,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -572,6 +626,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -581,6 +636,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -590,6 +646,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -599,6 +656,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -608,6 +666,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
@@ -635,6 +694,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -644,6 +704,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -653,6 +714,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -662,6 +724,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -671,6 +734,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -680,6 +744,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -689,6 +754,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -698,6 +764,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -707,6 +774,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
@@ -734,6 +802,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -743,6 +812,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -752,6 +822,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -761,6 +832,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -770,6 +842,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -779,6 +852,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -788,6 +862,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -797,6 +872,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -806,6 +882,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
@@ -833,6 +910,209 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                            predicate,
+                        ),
+                    }
+                }
+                (VectorKind::Bit, DistanceKind::Hamming) => {
+                    type Op = operator::Op<BVectOwned, Hamming>;
+                    let unprojected = if let OwnedVector::Bit(vector) = vector.clone() {
+                        let vector = vector.as_borrowed();
+                        BVectBorrowed::new(vector.dim(), bump.alloc_slice(vector.data()))
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Plain, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_plain_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Plain, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_plain_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Simple, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_simple_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Simple, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_simple_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Simple, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_simple_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Stream, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_stream_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Stream, Io::Simple) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_simple_prefetcher,
+                            make_vector_stream_prefetcher,
+                            predicate,
+                        ),
+                        (Io::Stream, Io::Stream) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_stream_prefetcher,
+                            make_vector_stream_prefetcher,
+                            predicate,
+                        ),
+                    }
+                }
+                (VectorKind::Bit, DistanceKind::Jaccard) => {
+                    type Op = operator::Op<BVectOwned, Jaccard>;
+                    let unprojected = if let OwnedVector::Bit(vector) = vector.clone() {
+                        let vector = vector.as_borrowed();
+                        BVectBorrowed::new(vector.dim(), bump.alloc_slice(vector.data()))
+                    } else {
+                        unreachable!()
+                    };
+                    match (options.io_search, options.io_rerank) {
+                        (Io::Plain, Io::Plain) => search::<_, Op>(
+                            index,
+                            unprojected,
+                            options.ef_search,
+                            options.beam_search,
+                            bump,
+                            make_vertex_plain_prefetcher,
+                            make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Simple) => search::<_, Op>(
                             index,
@@ -842,6 +1122,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Plain, Io::Stream) => search::<_, Op>(
                             index,
@@ -851,6 +1132,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_plain_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Plain) => search::<_, Op>(
                             index,
@@ -860,6 +1142,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Simple) => search::<_, Op>(
                             index,
@@ -869,6 +1152,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Simple, Io::Stream) => search::<_, Op>(
                             index,
@@ -878,6 +1162,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_simple_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Plain) => search::<_, Op>(
                             index,
@@ -887,6 +1172,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_plain_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Simple) => search::<_, Op>(
                             index,
@@ -896,6 +1182,7 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_simple_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                         (Io::Stream, Io::Stream) => search::<_, Op>(
                             index,
@@ -905,9 +1192,18 @@ impl SearchBuilder for DefaultBuilder {
                             bump,
                             make_vertex_stream_prefetcher,
                             make_vector_stream_prefetcher,
+                            predicate,
                         ),
                     }
                 }
+                (
+                    VectorKind::Vecf32
+                    | VectorKind::Vecf16
+                    | VectorKind::Rabitq8
+                    | VectorKind::Rabitq4,
+                    DistanceKind::Hamming | DistanceKind::Jaccard,
+                ) => unreachable!(),
+                (VectorKind::Bit, DistanceKind::L2S | DistanceKind::Dot) => unreachable!(),
             };
         let iter = if let Some(threshold) = threshold {
             Box::new(iter.take_while(move |(distance, _)| distance.to_f32() < threshold))
@@ -933,6 +1229,9 @@ impl SearchBuilder for DefaultBuilder {
                 OwnedVector::Rabitq4(v) => {
                     recorder.send(&text::rabitq4_out(v.as_borrowed()));
                 }
+                OwnedVector::Bit(v) => {
+                    recorder.send(&text::bit_out(v.as_borrowed()));
+                }
             }
         }
         Box::new(iter.map(move |(distance, pointer)| {
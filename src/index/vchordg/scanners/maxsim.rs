@@ -0,0 +1,387 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::index::fetcher::{Fetcher, FilterableTuple, Tuple, pointer_to_kv};
+use crate::index::scanners::{Io, SearchBuilder};
+use crate::index::vchordg::dispatch::*;
+use crate::index::vchordg::opclass::Opfamily;
+use crate::index::vchordg::scanners::SearchOptions;
+use crate::recorder::Recorder;
+use distance::Distance;
+use index::bump::Bump;
+use index::prefetcher::PrefetcherSequenceFamily;
+use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
+use index_accessor::Dot;
+use simd::f16;
+use std::cell::RefCell;
+use std::num::NonZero;
+use std::rc::Rc;
+use vchordg::operator::{self, Operator};
+use vchordg::search;
+use vchordg::types::{DistanceKind, OwnedVector, VectorKind};
+use vector::rabitq4::{Rabitq4Borrowed, Rabitq4Owned};
+use vector::rabitq8::{Rabitq8Borrowed, Rabitq8Owned};
+use vector::vect::{VectBorrowed, VectOwned};
+use vector::{VectorBorrowed, VectorOwned};
+
+pub struct MaxsimBuilder {
+    opfamily: Opfamily,
+    orderbys: Vec<Option<Vec<OwnedVector>>>,
+}
+
+impl SearchBuilder for MaxsimBuilder {
+    type Options = SearchOptions;
+
+    type Opfamily = Opfamily;
+
+    type Opaque = vchordg::Opaque;
+
+    fn new(opfamily: Opfamily) -> Self {
+        assert!(matches!(
+            opfamily,
+            Opfamily::VectorMaxsim
+                | Opfamily::HalfvecMaxsim
+                | Opfamily::Rabitq8Maxsim
+                | Opfamily::Rabitq4Maxsim
+        ));
+        Self {
+            opfamily,
+            orderbys: Vec::new(),
+        }
+    }
+
+    unsafe fn add(&mut self, strategy: u16, datum: Option<pgrx::pg_sys::Datum>) {
+        match strategy {
+            3 => {
+                let x = unsafe { datum.and_then(|x| self.opfamily.input_vectors(x)) };
+                self.orderbys.push(x);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn build<'b, R>(
+        self,
+        index: &'b R,
+        options: SearchOptions,
+        fetcher: impl Fetcher + 'b,
+        bump: &'b impl Bump,
+        _recorder: impl Recorder,
+    ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
+    where
+        R: RelationRead + RelationPrefetch + RelationReadStream,
+        R::Page: Page<Opaque = vchordg::Opaque>,
+    {
+        let mut vectors = None;
+        for orderby_vectors in self.orderbys.into_iter().flatten() {
+            if vectors.is_none() {
+                vectors = Some(orderby_vectors);
+            } else {
+                pgrx::error!("maxsim search with multiple vectors is not supported");
+            }
+        }
+        if let Some(_max_scan_tuples) = options.max_scan_tuples {
+            pgrx::error!("maxsim search with max_scan_tuples is not supported");
+        }
+        let opfamily = self.opfamily;
+        let Some(vectors) = vectors else {
+            return Box::new(std::iter::empty()) as Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
+        };
+        assert!(matches!(opfamily.distance_kind(), DistanceKind::Dot));
+        // the fetcher is shared by the searches of all query vectors
+        let fetcher = Rc::new(RefCell::new(fetcher));
+        let prefilter = options.prefilter;
+        let predicate = || {
+            let fetcher = fetcher.clone();
+            move |payload| {
+                if !prefilter {
+                    return true;
+                }
+                let (key, _) = pointer_to_kv(payload);
+                let mut fetcher = fetcher.borrow_mut();
+                let Some(mut tuple) = fetcher.fetch(key) else {
+                    return false;
+                };
+                tuple.filter()
+            }
+        };
+        let n = vectors.len();
+        let originals = vectors.clone();
+        let results: Vec<Vec<(Distance, NonZero<u64>)>> = match opfamily.vector_kind() {
+            VectorKind::Vecf32 => {
+                type Op = operator::Op<VectOwned<f32>, Dot>;
+                let projected = vectors
+                    .into_iter()
+                    .map(|vector| {
+                        if let OwnedVector::Vecf32(vector) = vector {
+                            let projected = RandomProject::project(vector.as_borrowed());
+                            VectBorrowed::new(bump.alloc_slice(projected.slice()))
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                search_all::<_, Op, _>(index, projected, &options, bump, &predicate)
+            }
+            VectorKind::Vecf16 => {
+                type Op = operator::Op<VectOwned<f16>, Dot>;
+                let projected = vectors
+                    .into_iter()
+                    .map(|vector| {
+                        if let OwnedVector::Vecf16(vector) = vector {
+                            let projected = RandomProject::project(vector.as_borrowed());
+                            VectBorrowed::new(bump.alloc_slice(projected.slice()))
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                search_all::<_, Op, _>(index, projected, &options, bump, &predicate)
+            }
+            VectorKind::Rabitq8 => {
+                type Op = operator::Op<Rabitq8Owned, Dot>;
+                let projected = vectors
+                    .into_iter()
+                    .map(|vector| {
+                        if let OwnedVector::Rabitq8(vector) = vector {
+                            let vector = vector.as_borrowed();
+                            Rabitq8Borrowed::new(
+                                vector.dim(),
+                                vector.sum_of_x2(),
+                                vector.norm_of_lattice(),
+                                vector.sum_of_code(),
+                                vector.sum_of_abs_x(),
+                                bump.alloc_slice(vector.packed_code()),
+                            )
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                search_all::<_, Op, _>(index, projected, &options, bump, &predicate)
+            }
+            VectorKind::Rabitq4 => {
+                type Op = operator::Op<Rabitq4Owned, Dot>;
+                let projected = vectors
+                    .into_iter()
+                    .map(|vector| {
+                        if let OwnedVector::Rabitq4(vector) = vector {
+                            let vector = vector.as_borrowed();
+                            Rabitq4Borrowed::new(
+                                vector.dim(),
+                                vector.sum_of_x2(),
+                                vector.norm_of_lattice(),
+                                vector.sum_of_code(),
+                                vector.sum_of_abs_x(),
+                                bump.alloc_slice(vector.packed_code()),
+                            )
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                search_all::<_, Op, _>(index, projected, &options, bump, &predicate)
+            }
+            VectorKind::Bit => unreachable!(),
+        };
+        let mut updates = Vec::new();
+        let mut estimations = Vec::new();
+        for (query_id, results) in results.into_iter().enumerate() {
+            updates.reserve(results.len());
+            let is_empty = results.is_empty();
+            // a document that is not found by a query vector is assumed to be
+            // as far as the farthest one that is found
+            let mut estimation = Distance::NEG_INFINITY;
+            for (distance, payload) in results {
+                estimation = std::cmp::max(estimation, distance);
+                let (key, _) = pointer_to_kv(payload);
+                updates.push((key, query_id, distance));
+            }
+            estimations.push(if !is_empty {
+                estimation
+            } else {
+                Distance::ZERO
+            });
+        }
+        updates.sort_unstable_by_key(|&(key, ..)| key);
+        let mut results = updates
+            .chunk_by(|(kl, ..), (kr, ..)| kl == kr)
+            .map(|chunk| {
+                let key = chunk[0].0;
+                let mut value = vec![None; n];
+                for &(_, query_id, distance) in chunk {
+                    let this = value[query_id].get_or_insert(Distance::INFINITY);
+                    *this = std::cmp::min(*this, distance);
+                }
+                let mut maxsim = 0.0f32;
+                for (query_id, distance) in value.into_iter().enumerate() {
+                    let d = distance.unwrap_or(estimations[query_id]);
+                    maxsim += Distance::to_f32(d);
+                }
+                (Distance::from_f32(maxsim), key)
+            })
+            .collect::<Vec<_>>();
+        // the estimation only decides which documents are found, and then they
+        // are reranked by the exact MaxSim of the vectors in the table, which
+        // is fetched once per document
+        results.sort_unstable();
+        let mut fetcher = fetcher.borrow_mut();
+        let mut results = results
+            .into_iter()
+            .filter_map(|(_, key)| {
+                let mut tuple = fetcher.fetch(key)?;
+                let (datums, is_nulls) = tuple.build();
+                let datum = (!is_nulls[0]).then_some(datums[0])?;
+                let vectors = unsafe { opfamily.input_vectors(datum)? };
+                Some((maxsim(&originals, &vectors), key))
+            })
+            .collect::<Vec<_>>();
+        results.sort_unstable();
+        Box::new(results.into_iter().map(move |(distance, key)| {
+            let recheck = false;
+            (opfamily.output(distance), key, recheck)
+        }))
+    }
+}
+
+/// Returns the MaxSim of `lhs`, the query vectors, and `rhs`, the vectors of a
+/// document, in the same way as the `@#` operator.
+fn maxsim(lhs: &[OwnedVector], rhs: &[OwnedVector]) -> Distance {
+    let mut maxsim = 0.0f32;
+    for lhs in lhs {
+        let mut d = f32::INFINITY;
+        for rhs in rhs {
+            let distance = match (lhs, rhs) {
+                (OwnedVector::Vecf32(lhs), OwnedVector::Vecf32(rhs)) => {
+                    VectBorrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                }
+                (OwnedVector::Vecf16(lhs), OwnedVector::Vecf16(rhs)) => {
+                    VectBorrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                }
+                (OwnedVector::Rabitq8(lhs), OwnedVector::Rabitq8(rhs)) => {
+                    Rabitq8Borrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                }
+                (OwnedVector::Rabitq4(lhs), OwnedVector::Rabitq4(rhs)) => {
+                    Rabitq4Borrowed::operator_dot(lhs.as_borrowed(), rhs.as_borrowed())
+                }
+                _ => unreachable!(),
+            };
+            d = d.min(distance.to_f32());
+        }
+        maxsim += d;
+    }
+    Distance::from_f32(maxsim)
+}
+
+fn search_all<'b, R, O, P>(
+    index: &'b R,
+    vectors: Vec<<O::Vector as VectorOwned>::Borrowed<'b>>,
+    options: &SearchOptions,
+    bump: &'b impl Bump,
+    predicate: &impl Fn() -> P,
+) -> Vec<Vec<(Distance, NonZero<u64>)>>
+where
+    R: RelationRead + RelationPrefetch + RelationReadStream,
+    R::Page: Page<Opaque = vchordg::Opaque>,
+    O: Operator,
+    P: FnMut(NonZero<u64>) -> bool + 'b,
+{
+    let plain = MakePlainPrefetcher { index };
+    let simple = MakeSimplePrefetcher { index };
+    let stream = MakeStreamPrefetcher {
+        index,
+        hints: Hints::default().full(true),
+    };
+    match (options.io_search, options.io_rerank) {
+        (Io::Plain, Io::Plain) => search_with::<_, O, _>(
+            index,
+            vectors,
+            options,
+            bump,
+            plain.clone(),
+            plain,
+            predicate,
+        ),
+        (Io::Plain, Io::Simple) => {
+            search_with::<_, O, _>(index, vectors, options, bump, simple, plain, predicate)
+        }
+        (Io::Plain, Io::Stream) => {
+            search_with::<_, O, _>(index, vectors, options, bump, stream, plain, predicate)
+        }
+        (Io::Simple, Io::Plain) => {
+            search_with::<_, O, _>(index, vectors, options, bump, plain, simple, predicate)
+        }
+        (Io::Simple, Io::Simple) => search_with::<_, O, _>(
+            index,
+            vectors,
+            options,
+            bump,
+            simple.clone(),
+            simple,
+            predicate,
+        ),
+        (Io::Simple, Io::Stream) => {
+            search_with::<_, O, _>(index, vectors, options, bump, stream, simple, predicate)
+        }
+        (Io::Stream, Io::Plain) => {
+            search_with::<_, O, _>(index, vectors, options, bump, plain, stream, predicate)
+        }
+        (Io::Stream, Io::Simple) => {
+            search_with::<_, O, _>(index, vectors, options, bump, simple, stream, predicate)
+        }
+        (Io::Stream, Io::Stream) => search_with::<_, O, _>(
+            index,
+            vectors,
+            options,
+            bump,
+            stream.clone(),
+            stream,
+            predicate,
+        ),
+    }
+}
+
+fn search_with<'b, R, O, P>(
+    index: &'b R,
+    vectors: Vec<<O::Vector as VectorOwned>::Borrowed<'b>>,
+    options: &SearchOptions,
+    bump: &'b impl Bump,
+    make_vertex_prefetcher: impl PrefetcherSequenceFamily<'b, R> + Clone + 'b,
+    make_vector_prefetcher: impl PrefetcherSequenceFamily<'b, R> + Clone + 'b,
+    predicate: &impl Fn() -> P,
+) -> Vec<Vec<(Distance, NonZero<u64>)>>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordg::Opaque>,
+    O: Operator,
+    P: FnMut(NonZero<u64>) -> bool + 'b,
+{
+    vectors
+        .into_iter()
+        .map(|vector| {
+            search::<_, O>(
+                index,
+                vector,
+                options.ef_search,
+                options.beam_search,
+                bump,
+                make_vertex_prefetcher.clone(),
+                make_vector_prefetcher.clone(),
+                predicate(),
+            )
+            .take(options.ef_search as _)
+            .collect()
+        })
+        .collect()
+}
@@ -13,8 +13,10 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 mod default;
+mod maxsim;
 
 pub use default::DefaultBuilder;
+pub use maxsim::MaxsimBuilder;
 
 #[derive(Debug)]
 pub struct SearchOptions {
@@ -23,4 +25,5 @@ pub struct SearchOptions {
     pub max_scan_tuples: Option<u32>,
     pub io_search: crate::index::scanners::Io,
     pub io_rerank: crate::index::scanners::Io,
+    pub prefilter: bool,
 }
@@ -34,8 +34,10 @@ use std::num::NonZero;
 use std::ops::Deref;
 use vchordrq::types::*;
 use vchordrq::{InsertChooser, MaintainChooser};
+use vector::bvect::BVectOwned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::svect::SVectOwned;
 use vector::vect::VectOwned;
 
 #[derive(Debug, Clone, Copy)]
@@ -226,6 +228,14 @@ pub unsafe extern "C-unwind" fn ambuild(
         let errors = "residual_quantization is not supported for rabitq4 type";
         pgrx::error!("error while validating options: {errors}");
     }
+    if vector_options.v == VectorKind::Svecf32 && vchordrq_options.index.residual_quantization {
+        let errors = "residual_quantization is not supported for sparsevec type";
+        pgrx::error!("error while validating options: {errors}");
+    }
+    if vector_options.v == VectorKind::Bit && vchordrq_options.index.residual_quantization {
+        let errors = "residual_quantization is not supported for bit type";
+        pgrx::error!("error while validating options: {errors}");
+    }
     let opfamily = unsafe { opfamily(index_relation) };
     let reporter = PostgresReporter {
         _phantom: PhantomData,
@@ -264,6 +274,18 @@ pub unsafe extern "C-unwind" fn ambuild(
         }
     }
     reporter.phase(BuildPhase::from_code(BuildPhaseCode::Build));
+    if unsafe { (*(*index_relation).rd_rel).relpersistence }
+        == pgrx::pg_sys::RELPERSISTENCE_UNLOGGED as std::ffi::c_char
+    {
+        let copied = structures
+            .iter()
+            .map(|structure| Structure {
+                centroids: structure.centroids.clone(),
+                children: structure.children.clone(),
+            })
+            .collect();
+        UNLOGGED_STRUCTURES.set(Some((unsafe { (*index_relation).rd_id }, copied)));
+    }
     let index = unsafe { PostgresRelation::new(index_relation) };
     crate::index::vchordrq::dispatch::build(
         vector_options,
@@ -1073,7 +1095,15 @@ unsafe fn parallel_build(
         n: unsafe { (*vchordrqshared).nparticipants as usize },
         k: order as usize,
     };
-    crate::index::vchordrq::dispatch::maintain(opfamily, &index, &mut chooser, check);
+    crate::index::vchordrq::dispatch::maintain(
+        opfamily,
+        &index,
+        &mut chooser,
+        None,
+        || false,
+        || (),
+        check,
+    );
 
     sync_2();
 }
@@ -1199,14 +1229,128 @@ unsafe fn sequential_build(
     let index = unsafe { PostgresRelation::new(index_relation) };
 
     let mut chooser = ChooseAll;
-    crate::index::vchordrq::dispatch::maintain(opfamily, &index, &mut chooser, check);
+    crate::index::vchordrq::dispatch::maintain(
+        opfamily,
+        &index,
+        &mut chooser,
+        None,
+        || false,
+        || (),
+        check,
+    );
 
     sync_2();
 }
 
 #[pgrx::pg_guard]
-pub unsafe extern "C-unwind" fn ambuildempty(_index_relation: pgrx::pg_sys::Relation) {
-    pgrx::error!("Unlogged indexes are not supported.");
+pub unsafe extern "C-unwind" fn ambuildempty(index_relation: pgrx::pg_sys::Relation) {
+    use pgrx::pg_sys::ForkNumber::INIT_FORKNUM;
+    let (vector_options, vchordrq_options) = unsafe { options(index_relation) };
+    // PostgreSQL calls `ambuildempty` just after `ambuild`, so the init fork
+    // keeps the centroids of the main fork and stays valid after crash reset
+    let structures = match UNLOGGED_STRUCTURES.take() {
+        Some((indexrelid, structures)) if indexrelid == unsafe { (*index_relation).rd_id } => {
+            structures
+        }
+        _ => make_default_build(vector_options, VchordrqDefaultBuildOptions::default()),
+    };
+    let index = unsafe { PostgresRelation::new_init_fork(index_relation) };
+    crate::index::vchordrq::dispatch::build(
+        vector_options,
+        vchordrq_options.index,
+        &index,
+        structures,
+    );
+    // buffers of unlogged relations are not WAL-logged, but init forks must be
+    unsafe {
+        let n = pgrx::pg_sys::RelationGetNumberOfBlocksInFork(index_relation, INIT_FORKNUM);
+        pgrx::pg_sys::log_newpage_range(index_relation, INIT_FORKNUM, 0, n, true);
+    }
+}
+
+/// Re-clusters a built index with centroids computed on a fresh sample of the
+/// heap. The caller must hold `ShareUpdateExclusiveLock` on both relations.
+///
+/// Searches and insertions go on during re-clustering, except that `ShareLock`
+/// is taken on the index while the last vectors are moved and the new tree is
+/// switched in, which blocks insertions for that short time. Then it waits for
+/// all transactions holding locks on the index before the old tree is freed.
+pub unsafe fn recluster(
+    heap_relation: pgrx::pg_sys::Relation,
+    index_relation: pgrx::pg_sys::Relation,
+    text: &str,
+) {
+    use validator::Validate;
+    let (vector_options, vchordrq_options) = unsafe { options(index_relation) };
+    if vchordrq_options.index.rerank_in_table {
+        pgrx::error!("re-clustering is not supported for indexes with `rerank_in_table = true`");
+    }
+    let internal_build = if text.trim().is_empty() {
+        match vchordrq_options.build.source {
+            VchordrqBuildSourceOptions::Internal(internal_build) => internal_build,
+            _ => VchordrqInternalBuildOptions::default(),
+        }
+    } else {
+        match toml::from_str::<VchordrqInternalBuildOptions>(text) {
+            Ok(p) => p,
+            Err(e) => pgrx::error!("failed to parse options: {}", e),
+        }
+    };
+    if let Err(errors) = Validate::validate(&internal_build) {
+        pgrx::error!("error while validating options: {}", errors);
+    }
+    let opfamily = unsafe { opfamily(index_relation) };
+    let reporter = PostgresReporter {
+        _phantom: PhantomData,
+    };
+    let snapshot =
+        unsafe { pgrx::pg_sys::RegisterSnapshot(pgrx::pg_sys::GetTransactionSnapshot()) };
+    let sampler = unsafe { HeapSampler::new(index_relation, heap_relation, snapshot) };
+    let mut structures =
+        make_internal_build(vector_options, opfamily, internal_build, sampler, &reporter);
+    unsafe {
+        pgrx::pg_sys::UnregisterSnapshot(snapshot);
+    }
+    for structure in structures.iter_mut() {
+        for centroid in structure.centroids.iter_mut() {
+            rabitq::rotate::rotate_inplace(centroid);
+        }
+    }
+    let wait = |lockmode: u32| unsafe {
+        let locktag = pgrx::pg_sys::LOCKTAG {
+            locktag_field1: pgrx::pg_sys::MyDatabaseId.to_u32(),
+            locktag_field2: (*index_relation).rd_id.to_u32(),
+            locktag_field3: 0,
+            locktag_field4: 0,
+            locktag_type: pgrx::pg_sys::LockTagType::LOCKTAG_RELATION as _,
+            locktag_lockmethodid: pgrx::pg_sys::DEFAULT_LOCKMETHOD as _,
+        };
+        pgrx::pg_sys::WaitForLockers(locktag, lockmode as _, false);
+    };
+    let index = unsafe { PostgresRelation::new(index_relation) };
+    let recluster = crate::index::vchordrq::dispatch::recluster(
+        opfamily,
+        &index,
+        structures,
+        // insertions hold `RowExclusiveLock` on the index
+        || unsafe { pgrx::pg_sys::LockRelation(index_relation, pgrx::pg_sys::ShareLock as _) },
+        || unsafe { pgrx::pg_sys::UnlockRelation(index_relation, pgrx::pg_sys::ShareLock as _) },
+        // searches hold `AccessShareLock` on the index
+        || wait(pgrx::pg_sys::AccessExclusiveLock),
+        || {
+            pgrx::check_for_interrupts!();
+        },
+    );
+    if recluster.is_none() {
+        pgrx::error!("re-clustering is not supported for indexes with `rerank_in_table = true`");
+    }
+}
+
+type UnloggedStructures = Option<(pgrx::pg_sys::Oid, Vec<Structure<Normalized>>)>;
+
+std::thread_local! {
+    static UNLOGGED_STRUCTURES: std::cell::RefCell<UnloggedStructures> =
+        const { std::cell::RefCell::new(None) };
 }
 
 unsafe fn options(
@@ -1326,6 +1470,8 @@ fn make_internal_build(
                             OwnedVector::Vecf16(x) => VectOwned::normalize(x),
                             OwnedVector::Rabitq8(x) => Rabitq8Owned::normalize(x),
                             OwnedVector::Rabitq4(x) => Rabitq4Owned::normalize(x),
+                            OwnedVector::Svecf32(x) => SVectOwned::normalize(x),
+                            OwnedVector::Bit(x) => BVectOwned::normalize(x),
                         };
                         assert_eq!(
                             vector_options.dim,
@@ -1487,6 +1633,8 @@ fn make_internal_build(
                                                 OwnedVector::Rabitq4(x) => {
                                                     Rabitq4Owned::normalize(x)
                                                 }
+                                                OwnedVector::Svecf32(x) => SVectOwned::normalize(x),
+                                                OwnedVector::Bit(x) => BVectOwned::normalize(x),
                                             };
                                             assert_eq!(
                                                 vector_options.dim,
@@ -0,0 +1,77 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::index::vchordrq::am::Index;
+use crate::index::vchordrq::opclass::{Opfamily, opfamily};
+use pgrx::PgList;
+use pgrx::pg_sys::{IndexPath, Node, NodeTag, Oid, OpExpr};
+
+// Maxsim search sums the similarities of all vectors of a document, which are
+// spread over all lists, and multi-query search merges the results of several
+// query vectors, so lists cannot be split among the participants of a parallel
+// scan. Partial paths of such scans are removed before the planner gathers
+// them, which keeps parallel plans away from them.
+pub unsafe fn set_rel_pathlist(rel: *mut pgrx::pg_sys::RelOptInfo) {
+    unsafe {
+        if (*rel).partial_pathlist.is_null() {
+            return;
+        }
+        let am = pgrx::pg_sys::get_index_am_oid(c"vchordrq".as_ptr(), true);
+        if am == Oid::INVALID {
+            return;
+        }
+        let paths = PgList::<pgrx::pg_sys::Path>::from_pg((*rel).partial_pathlist);
+        let mut kept = PgList::<pgrx::pg_sys::Path>::new();
+        for path in paths.iter_ptr() {
+            if !is_serial(path, am) {
+                kept.push(path);
+            }
+        }
+        (*rel).partial_pathlist = kept.into_pg();
+    }
+}
+
+unsafe fn is_serial(path: *mut pgrx::pg_sys::Path, am: Oid) -> bool {
+    unsafe {
+        if (*path).type_ != NodeTag::T_IndexPath {
+            return false;
+        }
+        let index = (*(path as *mut IndexPath)).indexinfo;
+        if (*index).relam != am {
+            return false;
+        }
+        let opfamily = {
+            let relation = Index::open((*index).indexoid, pgrx::pg_sys::NoLock as _);
+            opfamily(relation.raw())
+        };
+        if matches!(
+            opfamily,
+            Opfamily::VectorMaxsim
+                | Opfamily::HalfvecMaxsim
+                | Opfamily::Rabitq8Maxsim
+                | Opfamily::Rabitq4Maxsim
+        ) {
+            return true;
+        }
+        // multi-query operators are the ordering operators of strategy 4
+        let orderbys = PgList::<Node>::from_pg((*(path as *mut IndexPath)).indexorderbys);
+        orderbys.iter_ptr().any(|orderby| {
+            (*orderby).type_ == NodeTag::T_OpExpr
+                && pgrx::pg_sys::get_op_opfamily_strategy(
+                    (*(orderby as *mut OpExpr)).opno,
+                    *(*index).opfamily,
+                ) == 4
+        })
+    }
+}
@@ -0,0 +1,282 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::index::vchordrq::am::Index;
+use crate::index::vchordrq::opclass::{Opfamily, opfamily};
+use pgrx::datum::{FromDatum, IntoDatum};
+use pgrx::pg_sys::{Datum, NodeTag, Oid};
+use pgrx::{PgBox, PgList};
+use std::ffi::CStr;
+
+// PostgreSQL only asks planner support functions of the top-level function of
+// a clause, so `val <-> '[0, 0, 0]' < 1` never reaches one on `<->`. We derive
+// the index condition that such a function would return after the planner
+// has generated its own index paths. Only unordered paths are added, since a
+// sphere does not stop an ordered scan early.
+pub unsafe fn set_rel_pathlist(
+    root: *mut pgrx::pg_sys::PlannerInfo,
+    rel: *mut pgrx::pg_sys::RelOptInfo,
+    rte: *mut pgrx::pg_sys::RangeTblEntry,
+) {
+    unsafe {
+        if (*rel).reloptkind != pgrx::pg_sys::RelOptKind::RELOPT_BASEREL
+            || (*rte).rtekind != pgrx::pg_sys::RTEKind::RTE_RELATION
+        {
+            return;
+        }
+        if (*rel).baserestrictinfo.is_null() || (*rel).indexlist.is_null() {
+            return;
+        }
+        let am = pgrx::pg_sys::get_index_am_oid(c"vchordrq".as_ptr(), true);
+        if am == Oid::INVALID {
+            return;
+        }
+        let indexes = PgList::<pgrx::pg_sys::IndexOptInfo>::from_pg((*rel).indexlist);
+        for index in indexes.iter_ptr() {
+            if (*index).relam != am || (*index).ncolumns != 1 || (*index).hypothetical {
+                continue;
+            }
+            let opfamily = {
+                let relation = Index::open((*index).indexoid, pgrx::pg_sys::NoLock as _);
+                opfamily(relation.raw())
+            };
+            if matches!(
+                opfamily,
+                Opfamily::VectorMaxsim
+                    | Opfamily::HalfvecMaxsim
+                    | Opfamily::Rabitq8Maxsim
+                    | Opfamily::Rabitq4Maxsim
+            ) {
+                continue;
+            }
+            let Some(operators) = Operators::new(*(*index).opfamily, *(*index).opcintype) else {
+                continue;
+            };
+            let mut indexclauses = PgList::<pgrx::pg_sys::IndexClause>::new();
+            let restrictinfos =
+                PgList::<pgrx::pg_sys::RestrictInfo>::from_pg((*rel).baserestrictinfo);
+            for rinfo in restrictinfos.iter_ptr() {
+                if let Some(indexclause) = match_clause(root, rel, index, rinfo, &operators) {
+                    indexclauses.push(indexclause);
+                }
+            }
+            if indexclauses.is_empty() {
+                continue;
+            }
+            let path = pgrx::pg_sys::create_index_path(
+                root,
+                index,
+                indexclauses.into_pg(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                std::ptr::null_mut(),
+                pgrx::pg_sys::ScanDirection::ForwardScanDirection,
+                false,
+                std::ptr::null_mut(),
+                1.0,
+                false,
+            );
+            pgrx::pg_sys::add_path(rel, path.cast());
+        }
+    }
+}
+
+struct Operators {
+    distance: Oid,
+    sphere: Oid,
+    sphere_type: Oid,
+}
+
+impl Operators {
+    unsafe fn new(opfamily: Oid, opcintype: Oid) -> Option<Self> {
+        unsafe {
+            let distance = pgrx::pg_sys::get_opfamily_member(opfamily, opcintype, opcintype, 1);
+            if distance == Oid::INVALID {
+                return None;
+            }
+            let mut sphere = None;
+            let list = pgrx::pg_sys::SearchSysCacheList(
+                pgrx::pg_sys::SysCacheIdentifier::AMOPSTRATEGY as _,
+                1,
+                Datum::from(opfamily),
+                Datum::null(),
+                Datum::null(),
+            );
+            for &member in (*list).members.as_slice((*list).n_members as _) {
+                let form = pgrx::pg_sys::heap_tuple_get_struct::<pgrx::pg_sys::FormData_pg_amop>(
+                    &raw mut (*member).tuple,
+                );
+                if (*form).amoplefttype == opcintype && (*form).amopstrategy == 2 {
+                    sphere = Some(((*form).amopopr, (*form).amoprighttype));
+                }
+            }
+            pgrx::pg_sys::ReleaseCatCacheList(list);
+            let (sphere, sphere_type) = sphere?;
+            Some(Self {
+                distance,
+                sphere,
+                sphere_type,
+            })
+        }
+    }
+}
+
+unsafe fn match_clause(
+    root: *mut pgrx::pg_sys::PlannerInfo,
+    rel: *mut pgrx::pg_sys::RelOptInfo,
+    index: *mut pgrx::pg_sys::IndexOptInfo,
+    rinfo: *mut pgrx::pg_sys::RestrictInfo,
+    operators: &Operators,
+) -> Option<*mut pgrx::pg_sys::IndexClause> {
+    use pgrx::pg_sys::{FLOAT4OID, FLOAT8OID, Node, OpExpr};
+
+    unsafe {
+        let clause = (*rinfo).clause as *mut Node;
+        if (*clause).type_ != NodeTag::T_OpExpr {
+            return None;
+        }
+        let clause = clause as *mut OpExpr;
+        if (*clause).opno.to_u32() >= pgrx::pg_sys::FirstNormalObjectId {
+            return None;
+        }
+        let args = PgList::<Node>::from_pg((*clause).args);
+        let (Some(left), Some(right), 2) = (args.get_ptr(0), args.get_ptr(1), args.len()) else {
+            return None;
+        };
+        for arg in [left, right] {
+            if !matches!(pgrx::pg_sys::exprType(arg), FLOAT4OID | FLOAT8OID) {
+                return None;
+            }
+        }
+        let name = pgrx::pg_sys::get_opname((*clause).opno);
+        if name.is_null() {
+            return None;
+        }
+        let (expr, radius, inclusive) = match CStr::from_ptr(name).to_bytes() {
+            b"<" => (left, right, false),
+            b"<=" => (left, right, true),
+            b">" => (right, left, false),
+            b">=" => (right, left, true),
+            _ => return None,
+        };
+        if (*expr).type_ != NodeTag::T_OpExpr {
+            return None;
+        }
+        let expr = expr as *mut OpExpr;
+        if (*expr).opno != operators.distance {
+            return None;
+        }
+        let args = PgList::<Node>::from_pg((*expr).args);
+        let (Some(key), Some(center), 2) = (args.get_ptr(0), args.get_ptr(1), args.len()) else {
+            return None;
+        };
+        if !pgrx::pg_sys::match_index_to_operand(key, 0, index) {
+            return None;
+        }
+        if pgrx::pg_sys::bms_is_member((*rel).relid as _, pgrx::pg_sys::pull_varnos(root, center))
+            || pgrx::pg_sys::contain_volatile_functions(center)
+        {
+            return None;
+        }
+        if (*radius).type_ != NodeTag::T_Const {
+            return None;
+        }
+        let radius = radius as *mut pgrx::pg_sys::Const;
+        let value = match (*radius).consttype {
+            FLOAT4OID => f32::from_datum((*radius).constvalue, (*radius).constisnull)? as f64,
+            FLOAT8OID => f64::from_datum((*radius).constvalue, (*radius).constisnull)?,
+            _ => return None,
+        };
+        if value.is_nan() {
+            return None;
+        }
+        // the index compares distances in single precision, so the radius is
+        // rounded up and the original clause is rechecked
+        let mut radius = value as f32;
+        if (radius as f64) < value {
+            radius = radius.next_up();
+        }
+        if inclusive {
+            radius = radius.next_up();
+        }
+        let mut row = PgBox::<pgrx::pg_sys::RowExpr>::alloc_node(NodeTag::T_RowExpr);
+        let mut fields = PgList::<Node>::new();
+        fields.push(pgrx::pg_sys::copyObjectImpl(center.cast()).cast());
+        fields.push(
+            pgrx::pg_sys::makeConst(
+                FLOAT4OID,
+                -1,
+                Oid::INVALID,
+                4,
+                radius.into_datum()?,
+                false,
+                true,
+            )
+            .cast(),
+        );
+        row.args = fields.into_pg();
+        row.row_typeid = operators.sphere_type;
+        row.row_format = pgrx::pg_sys::CoercionForm::COERCE_EXPLICIT_CAST;
+        row.location = -1;
+        let mut row = row.into_pg() as *mut pgrx::pg_sys::Expr;
+        if (*center).type_ == NodeTag::T_Const {
+            row = pgrx::pg_sys::evaluate_expr(row, operators.sphere_type, -1, Oid::INVALID);
+        }
+        let qual = pgrx::pg_sys::make_opclause(
+            operators.sphere,
+            pgrx::pg_sys::BOOLOID,
+            false,
+            pgrx::pg_sys::copyObjectImpl(key.cast()).cast(),
+            row,
+            Oid::INVALID,
+            Oid::INVALID,
+        );
+        pgrx::pg_sys::set_opfuncid(qual.cast());
+        #[cfg(any(feature = "pg14", feature = "pg15"))]
+        let qual = pgrx::pg_sys::make_restrictinfo(
+            root,
+            qual,
+            true,
+            false,
+            false,
+            (*rinfo).security_level,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        );
+        #[cfg(any(feature = "pg16", feature = "pg17", feature = "pg18"))]
+        let qual = pgrx::pg_sys::make_restrictinfo(
+            root,
+            qual,
+            true,
+            false,
+            false,
+            false,
+            (*rinfo).security_level,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        );
+        let mut indexquals = PgList::<pgrx::pg_sys::RestrictInfo>::new();
+        indexquals.push(qual);
+        let mut indexclause =
+            PgBox::<pgrx::pg_sys::IndexClause>::alloc_node(NodeTag::T_IndexClause);
+        indexclause.rinfo = rinfo;
+        indexclause.indexquals = indexquals.into_pg();
+        indexclause.lossy = true;
+        indexclause.indexcol = 0;
+        indexclause.indexcols = std::ptr::null_mut();
+        Some(indexclause.into_pg())
+    }
+}
@@ -14,7 +14,7 @@
 
 use crate::index::vchordrq::am::PostgresRelation;
 use crate::index::vchordrq::opclass::opfamily;
-use vchordrq::MaintainChooser;
+use vchordrq::{Maintain, MaintainChooser};
 
 #[pgrx::pg_guard]
 pub unsafe extern "C-unwind" fn amvacuumcleanup(
@@ -28,8 +28,18 @@ pub unsafe extern "C-unwind" fn amvacuumcleanup(
         };
     }
     let index_relation = unsafe { (*info).index };
+    let maintain = unsafe { sequential_vacuumcleanup(index_relation, || (), || ()) };
+    let freed = maintain.number_of_freed_pages as pgrx::pg_sys::BlockNumber;
     unsafe {
-        sequential_vacuumcleanup(index_relation, || (), || ());
+        (*stats).num_pages = pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            index_relation,
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        );
+        (*stats).num_index_tuples = maintain.number_of_tuples as f64;
+        (*stats).estimated_count = false;
+        (*stats).pages_newly_deleted += freed;
+        (*stats).pages_deleted += freed;
+        (*stats).pages_free += freed;
     }
     stats
 }
@@ -38,7 +48,7 @@ unsafe fn sequential_vacuumcleanup(
     index_relation: pgrx::pg_sys::Relation,
     sync_0: impl FnOnce(),
     sync_1: impl FnOnce(),
-) {
+) -> Maintain {
     struct ChooseAll;
     impl MaintainChooser for ChooseAll {
         fn choose(&mut self, _: usize) -> bool {
@@ -58,7 +68,32 @@ unsafe fn sequential_vacuumcleanup(
     sync_0();
 
     let mut chooser = ChooseAll;
-    crate::index::vchordrq::dispatch::maintain(opfamily, &index, &mut chooser, check);
+    let split_factor = crate::index::gucs::vchordrq_maintain_split_factor();
+    // parallel scans hold a share lock on the meta page, since a split list
+    // changes the positions of lists they claim, and a list is not split
+    // until the next vacuum if it is not granted
+    let lock_split = || unsafe {
+        pgrx::pg_sys::ConditionalLockPage(index_relation, 0, pgrx::pg_sys::ExclusiveLock as _)
+    };
+    let unlock_split = || unsafe {
+        pgrx::pg_sys::UnlockPage(index_relation, 0, pgrx::pg_sys::ExclusiveLock as _);
+    };
+    let maintain = crate::index::vchordrq::dispatch::maintain(
+        opfamily,
+        &index,
+        &mut chooser,
+        split_factor,
+        lock_split,
+        unlock_split,
+        check,
+    );
+    if split_factor.is_some() && !maintain.splittable {
+        pgrx::notice!(
+            "lists are not split because the index has a single level or `rerank_in_table` is enabled"
+        );
+    }
 
     sync_1();
+
+    maintain
 }
@@ -0,0 +1,58 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::index::storage::PostgresRelation;
+use crate::index::vchordrq::opclass::opfamily;
+use crate::index::verify::Problem;
+
+pub unsafe fn verify(
+    heap_relation: pgrx::pg_sys::Relation,
+    index_relation: pgrx::pg_sys::Relation,
+    heapallindexed: bool,
+) -> Vec<Problem> {
+    let index = unsafe { PostgresRelation::new(index_relation) };
+    let opfamily = unsafe { opfamily(index_relation) };
+    unsafe {
+        crate::index::verify::verify(
+            heap_relation,
+            index_relation,
+            heapallindexed,
+            |number_of_pages, callback| {
+                crate::index::vchordrq::dispatch::verify(
+                    opfamily,
+                    &index,
+                    number_of_pages,
+                    || {
+                        pgrx::check_for_interrupts!();
+                    },
+                    callback,
+                )
+                .into_iter()
+                .map(|problem| Problem {
+                    page: problem.page,
+                    message: problem.message,
+                })
+                .collect()
+            },
+            |datum| {
+                let store = unsafe { opfamily.store(datum) };
+                store
+                    .into_iter()
+                    .flatten()
+                    .map(|(_, extra)| extra)
+                    .collect()
+            },
+        )
+    }
+}
@@ -13,15 +13,22 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 mod am_build;
+pub mod am_parallel;
+pub mod am_pushdown;
 mod am_vacuumcleanup;
+mod am_verify;
+
+pub use am_build::recluster;
+pub use am_verify::verify;
 
 use crate::index::fetcher::*;
 use crate::index::gucs;
+use crate::index::hook::{limit_count, orderby_datum};
 use crate::index::scanners::SearchBuilder;
 use crate::index::storage::PostgresRelation;
 use crate::index::vchordrq::opclass::{Opfamily, opfamily};
 use crate::index::vchordrq::scanners::*;
-use crate::recorder::DefaultRecorder;
+use crate::recorder::{DefaultRecorder, Parameters, Recorder};
 use pgrx::datum::Internal;
 use pgrx::pg_sys::Datum;
 use rand::RngExt;
@@ -31,7 +38,9 @@ use std::num::NonZero;
 use std::ops::DerefMut;
 use std::ptr::NonNull;
 use std::sync::OnceLock;
-use vchordrq::InsertChooser;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Instant;
+use vchordrq::{InsertChooser, Probes};
 
 #[repr(C)]
 pub struct Reloption {
@@ -206,6 +215,7 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = const {
 
     am_routine.amsupport = 1;
     am_routine.amcanorderbyop = true;
+    am_routine.amcanparallel = true;
 
     #[cfg(any(feature = "pg17", feature = "pg18"))]
     {
@@ -235,8 +245,13 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = const {
     am_routine.ambeginscan = Some(ambeginscan);
     am_routine.amrescan = Some(amrescan);
     am_routine.amgettuple = Some(amgettuple);
+    am_routine.amgetbitmap = Some(amgetbitmap);
     am_routine.amendscan = Some(amendscan);
 
+    am_routine.amestimateparallelscan = Some(amestimateparallelscan);
+    am_routine.aminitparallelscan = Some(aminitparallelscan);
+    am_routine.amparallelrescan = Some(amparallelrescan);
+
     am_routine.amparallelvacuumoptions = pgrx::pg_sys::VACUUM_OPTION_PARALLEL_BULKDEL as u8
         | pgrx::pg_sys::VACUUM_OPTION_PARALLEL_CLEANUP as u8;
 
@@ -244,8 +259,8 @@ const AM_HANDLER: pgrx::pg_sys::IndexAmRoutine = const {
 };
 
 #[pgrx::pg_guard]
-pub unsafe extern "C-unwind" fn amvalidate(_opclass_oid: pgrx::pg_sys::Oid) -> bool {
-    true
+pub unsafe extern "C-unwind" fn amvalidate(opclass_oid: pgrx::pg_sys::Oid) -> bool {
+    unsafe { crate::index::vchordrq::opclass::validate(opclass_oid) }
 }
 
 #[pgrx::pg_guard]
@@ -325,6 +340,8 @@ pub unsafe extern "C-unwind" fn amcostestimate(
                     | Opfamily::Rabitq4Cosine
                     | Opfamily::Rabitq4Ip
                     | Opfamily::Rabitq4L2
+                    | Opfamily::BitHamming
+                    | Opfamily::BitJaccard
             ) {
                 *index_startup_cost = 0.0;
                 *index_total_cost = 0.0;
@@ -334,8 +351,9 @@ pub unsafe extern "C-unwind" fn amcostestimate(
                 return;
             }
             let index = PostgresRelation::<vchordrq::Opaque>::new(relation.raw());
-            let probes = gucs::vchordrq_probes(relation.raw());
             let cost = vchordrq::cost(&index);
+            // auto probes are estimated as their maximum
+            let probes = gucs::vchordrq_probes(relation.raw()).resolve(&cost.cells);
             if cost.cells.len() != 1 + probes.len() {
                 panic!(
                     "need {} probes, but {} probes provided",
@@ -367,8 +385,12 @@ pub unsafe extern "C-unwind" fn amcostestimate(
                 pages += cost.cells[0] as f64;
                 pages
             };
-            let next_count =
-                f64::max(1.0, (*root).limit_tuples) * f64::min(1000.0, 1.0 / selectivity);
+            let next_count = if (*path).indexorderbys.is_null() {
+                // range queries, such as bitmap scans, consume all tuples in spheres
+                selectivity * (*index_opt_info).tuples
+            } else {
+                f64::max(1.0, (*root).limit_tuples) * f64::min(1000.0, 1.0 / selectivity)
+            };
             *index_startup_cost = 0.001 * node_count;
             *index_total_cost = 0.001 * node_count + next_count;
             *index_selectivity = selectivity;
@@ -465,7 +487,10 @@ pub unsafe extern "C-unwind" fn ambulkdelete(
             pg_guard_ffi_boundary(|| callback(&mut ctid, callback_state))
         }
     };
-    crate::index::vchordrq::dispatch::bulkdelete(opfamily, &index, check, callback);
+    let removed = crate::index::vchordrq::dispatch::bulkdelete(opfamily, &index, check, callback);
+    unsafe {
+        (*stats).tuples_removed += removed as f64;
+    }
     stats
 }
 
@@ -480,11 +505,22 @@ pub unsafe extern "C-unwind" fn ambeginscan(
     let scan = unsafe { pgrx::pg_sys::RelationGetIndexScan(index_relation, n_keys, n_orderbys) };
     let scanner: Scanner = Scanner {
         hack: None,
+        limit: None,
+        projection: None,
+        rettype: pgrx::pg_sys::Oid::INVALID,
+        recorder: None,
         scanning: LazyCell::new(Box::new(|| Box::new(std::iter::empty()))),
         bump: Box::new(bumpalo::Bump::new()),
+        split_locked: false,
     };
     unsafe {
         (*scan).opaque = CurrentMemoryContext.leak_and_drop_on_delete(scanner).cast();
+        if n_orderbys > 0 {
+            let n = n_orderbys as usize;
+            (*scan).xs_orderbyvals = pgrx::pg_sys::palloc0(n * size_of::<Datum>()).cast();
+            (*scan).xs_orderbynulls = pgrx::pg_sys::palloc(n * size_of::<bool>()).cast();
+            std::ptr::write_bytes((*scan).xs_orderbynulls, 1, n);
+        }
     }
     scan
 }
@@ -513,6 +549,14 @@ pub unsafe extern "C-unwind" fn amrescan(
         let scanner = &mut *(*scan).opaque.cast::<Scanner>();
         scanner.scanning = LazyCell::new(Box::new(|| Box::new(std::iter::empty())));
         scanner.bump.reset();
+        if let Some(recorder) = scanner.recorder.take() {
+            recorder.flush();
+        }
+        scanner.rettype = if (*scan).numberOfOrderBys > 0 {
+            pgrx::pg_sys::get_func_rettype((*(*scan).orderByData).sk_func.fn_oid)
+        } else {
+            pgrx::pg_sys::Oid::INVALID
+        };
         let opfamily = opfamily((*scan).indexRelation);
         let index = PostgresRelation::new((*scan).indexRelation);
         let options = SearchOptions {
@@ -524,7 +568,16 @@ pub unsafe extern "C-unwind" fn amrescan(
             io_search: gucs::vchordrq_io_search(),
             io_rerank: gucs::vchordrq_io_rerank(),
             prefilter: gucs::vchordrq_prefilter(),
+            iterative_scan: gucs::vchordrq_iterative_scan(),
+            limit: None,
+            parallel: parallel(scan),
         };
+        // participants claim lists by their positions, which are changed if a
+        // list is split by vacuum, so splits wait until the scan ends
+        if options.parallel.is_some() && !scanner.split_locked {
+            pgrx::pg_sys::LockPage((*scan).indexRelation, 0, pgrx::pg_sys::ShareLock as _);
+            scanner.split_locked = true;
+        }
         let fetcher = {
             let hack = scanner.hack;
             LazyCell::new(move || {
@@ -545,15 +598,71 @@ pub unsafe extern "C-unwind" fn amrescan(
             0.0 => None,
             rate => Some(rate),
         };
-        let recorder = DefaultRecorder {
-            enable: gucs::vchordrq_query_sampling_enable(),
-            rate,
-            max_records: gucs::vchordrq_query_sampling_max_records(),
-            index: (*(*scan).indexRelation).rd_id.to_u32(),
+        // rows that are rechecked against the keys may be filtered out after
+        // they are returned, so the limit does not tell how many rows are
+        // wanted then
+        let limit = if (*scan).numberOfKeys > 0 {
+            None
+        } else {
+            scanner.limit
         };
+        let filtered = scanner
+            .hack
+            .is_some_and(|node| !(*node.as_ptr()).ss.ps.qual.is_null());
+        let recorder = DefaultRecorder::new(
+            gucs::vchordrq_query_sampling_enable(),
+            rate,
+            gucs::vchordrq_query_sampling_max_records(),
+            (*(*scan).indexRelation).rd_id.to_u32(),
+            Parameters {
+                probes: Some(match &options.probes {
+                    Probes::Fixed(probes) => probes
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(","),
+                    Probes::Auto(_) => "auto".to_string(),
+                }),
+                epsilon: Some(options.epsilon),
+                max_scan_tuples: options.max_scan_tuples,
+                prefilter: Some(options.prefilter),
+            },
+        );
+        scanner.recorder = recorder.is_enabled().then(|| recorder.clone());
         // PAY ATTENTATION: `scanning` references `bump`, so `scanning` must be dropped before `bump`.
         let bump = scanner.bump.as_ref();
+        // a weighted multi-query is ordered by strategy 4
+        let multi_query = (0..(*scan).numberOfOrderBys)
+            .any(|i| (*(*scan).orderByData.add(i as usize)).sk_strategy == 4);
         scanner.scanning = match opfamily {
+            Opfamily::VectorL2
+            | Opfamily::VectorIp
+            | Opfamily::VectorCosine
+            | Opfamily::HalfvecL2
+            | Opfamily::HalfvecIp
+            | Opfamily::HalfvecCosine
+                if multi_query =>
+            {
+                let mut builder = MultiQueryBuilder::new(opfamily);
+                for i in 0..(*scan).numberOfOrderBys {
+                    let data = (*scan).orderByData.add(i as usize);
+                    let value = (*data).sk_argument;
+                    let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
+                    builder.add((*data).sk_strategy, (!is_null).then_some(value));
+                }
+                for i in 0..(*scan).numberOfKeys {
+                    let data = (*scan).keyData.add(i as usize);
+                    let value = (*data).sk_argument;
+                    let is_null = ((*data).sk_flags & pgrx::pg_sys::SK_ISNULL as i32) != 0;
+                    builder.add((*data).sk_strategy, (!is_null).then_some(value));
+                }
+                LazyCell::new(Box::new(move || {
+                    // only do this since `PostgresRelation` has no destructor
+                    let index = bump.alloc(index.clone());
+                    let options = options.with_limit(limit.and_then(|x| limit_count(x)), filtered);
+                    builder.build(index, options, fetcher, bump, recorder)
+                }))
+            }
             Opfamily::VectorL2
             | Opfamily::VectorIp
             | Opfamily::VectorCosine
@@ -565,7 +674,12 @@ pub unsafe extern "C-unwind" fn amrescan(
             | Opfamily::Rabitq8Cosine
             | Opfamily::Rabitq4L2
             | Opfamily::Rabitq4Ip
-            | Opfamily::Rabitq4Cosine => {
+            | Opfamily::Rabitq4Cosine
+            | Opfamily::SparsevecL2
+            | Opfamily::SparsevecIp
+            | Opfamily::SparsevecCosine
+            | Opfamily::BitHamming
+            | Opfamily::BitJaccard => {
                 let mut builder = DefaultBuilder::new(opfamily);
                 for i in 0..(*scan).numberOfOrderBys {
                     let data = (*scan).orderByData.add(i as usize);
@@ -582,6 +696,7 @@ pub unsafe extern "C-unwind" fn amrescan(
                 LazyCell::new(Box::new(move || {
                     // only do this since `PostgresRelation` has no destructor
                     let index = bump.alloc(index.clone());
+                    let options = options.with_limit(limit.and_then(|x| limit_count(x)), filtered);
                     builder.build(index, options, fetcher, bump, recorder)
                 }))
             }
@@ -605,6 +720,7 @@ pub unsafe extern "C-unwind" fn amrescan(
                 LazyCell::new(Box::new(move || {
                     // only do this since `PostgresRelation` has no destructor
                     let index = bump.alloc(index.clone());
+                    let options = options.with_limit(limit.and_then(|x| limit_count(x)), filtered);
                     builder.build(index, options, fetcher, bump, recorder)
                 }))
             }
@@ -629,11 +745,34 @@ pub unsafe extern "C-unwind" fn amgettuple(
         pgrx::error!("scanning with a non-MVCC-compliant snapshot is not supported");
     }
     let scanner = unsafe { (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked() };
-    if let Some((_, key, recheck)) = scanner.scanning.deref_mut().next() {
+    let next = if let Some(recorder) = scanner.recorder.as_ref() {
+        if let Some(limit) = scanner.limit {
+            recorder.set_limit(unsafe { limit_count(limit) });
+        }
+        let start = Instant::now();
+        let next = scanner.scanning.deref_mut().next();
+        recorder.observe(start.elapsed(), next.is_some() as u64);
+        next
+    } else {
+        scanner.scanning.deref_mut().next()
+    };
+    if let Some((distance, key, recheck)) = next {
         unsafe {
             (*scan).xs_heaptid = key_to_ctid(key);
             (*scan).xs_recheck = recheck;
             (*scan).xs_recheckorderby = false;
+            // vector search supports only one `ORDER BY` clause, so the distance
+            // is always the value of the first one
+            if (*scan).numberOfOrderBys > 0 {
+                let value = orderby_datum(scanner.rettype, distance);
+                (*scan).xs_orderbyvals.write(value);
+                (*scan).xs_orderbynulls.write(false);
+                if let Some(mut projection) = scanner.projection {
+                    let projection = projection.as_mut();
+                    projection.value = value;
+                    projection.isnull = false;
+                }
+            }
         }
         true
     } else {
@@ -641,19 +780,117 @@ pub unsafe extern "C-unwind" fn amgettuple(
     }
 }
 
+#[pgrx::pg_guard]
+pub unsafe extern "C-unwind" fn amgetbitmap(
+    scan: pgrx::pg_sys::IndexScanDesc,
+    tbm: *mut pgrx::pg_sys::TIDBitmap,
+) -> i64 {
+    if unsafe { (*(*scan).xs_snapshot).snapshot_type } != pgrx::pg_sys::SnapshotType::SNAPSHOT_MVCC
+    {
+        pgrx::error!("scanning with a non-MVCC-compliant snapshot is not supported");
+    }
+    if unsafe { (*scan).numberOfOrderBys } != 0 {
+        pgrx::error!("bitmap scanning with ORDER BY clauses is not supported");
+    }
+    let scanner = unsafe { (*scan).opaque.cast::<Scanner>().as_mut().unwrap_unchecked() };
+    let start = scanner.recorder.is_some().then(Instant::now);
+    let mut count = 0_i64;
+    for (_, key, recheck) in scanner.scanning.deref_mut() {
+        let mut ctid = key_to_ctid(key);
+        unsafe {
+            pgrx::pg_sys::tbm_add_tuples(tbm, &mut ctid, 1, recheck);
+        }
+        count += 1;
+    }
+    if let (Some(recorder), Some(start)) = (scanner.recorder.as_ref(), start) {
+        recorder.observe(start.elapsed(), count as u64);
+    }
+    count
+}
+
 #[pgrx::pg_guard]
 pub unsafe extern "C-unwind" fn amendscan(scan: pgrx::pg_sys::IndexScanDesc) {
     let scanner = unsafe { &mut *(*scan).opaque.cast::<Scanner>() };
     scanner.scanning = LazyCell::new(Box::new(|| Box::new(std::iter::empty())));
     scanner.bump.reset();
+    if let Some(recorder) = scanner.recorder.take() {
+        recorder.flush();
+    }
+    if std::mem::take(&mut scanner.split_locked) {
+        unsafe {
+            pgrx::pg_sys::UnlockPage((*scan).indexRelation, 0, pgrx::pg_sys::ShareLock as _);
+        }
+    }
+}
+
+#[cfg(any(feature = "pg14", feature = "pg15", feature = "pg16"))]
+#[pgrx::pg_guard]
+pub unsafe extern "C-unwind" fn amestimateparallelscan() -> pgrx::pg_sys::Size {
+    size_of::<AtomicU32>()
+}
+
+#[cfg(feature = "pg17")]
+#[pgrx::pg_guard]
+pub unsafe extern "C-unwind" fn amestimateparallelscan(
+    _n_keys: std::os::raw::c_int,
+    _n_orderbys: std::os::raw::c_int,
+) -> pgrx::pg_sys::Size {
+    size_of::<AtomicU32>()
+}
+
+#[cfg(feature = "pg18")]
+#[pgrx::pg_guard]
+pub unsafe extern "C-unwind" fn amestimateparallelscan(
+    _index_relation: pgrx::pg_sys::Relation,
+    _n_keys: std::os::raw::c_int,
+    _n_orderbys: std::os::raw::c_int,
+) -> pgrx::pg_sys::Size {
+    size_of::<AtomicU32>()
+}
+
+#[pgrx::pg_guard]
+pub unsafe extern "C-unwind" fn aminitparallelscan(target: *mut std::os::raw::c_void) {
+    unsafe {
+        target.cast::<AtomicU32>().write(AtomicU32::new(0));
+    }
+}
+
+#[pgrx::pg_guard]
+pub unsafe extern "C-unwind" fn amparallelrescan(scan: pgrx::pg_sys::IndexScanDesc) {
+    if let Some(parallel) = unsafe { parallel(scan) } {
+        unsafe {
+            parallel.as_ref().store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+// The shared state of a parallel scan is the number of lists that have been
+// claimed by participants.
+unsafe fn parallel(scan: pgrx::pg_sys::IndexScanDesc) -> Option<NonNull<AtomicU32>> {
+    unsafe {
+        let parallel_scan = (*scan).parallel_scan;
+        if parallel_scan.is_null() {
+            return None;
+        }
+        #[cfg(any(feature = "pg14", feature = "pg15", feature = "pg16", feature = "pg17"))]
+        let offset = (*parallel_scan).ps_offset;
+        #[cfg(feature = "pg18")]
+        let offset = (*parallel_scan).ps_offset_am;
+        NonNull::new(parallel_scan.cast::<u8>().add(offset).cast())
+    }
 }
 
 type Iter = Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
 
 pub struct Scanner {
     pub hack: Option<NonNull<pgrx::pg_sys::IndexScanState>>,
+    pub limit: Option<NonNull<pgrx::pg_sys::LimitState>>,
+    pub projection: Option<NonNull<pgrx::pg_sys::ParamExecData>>,
+    rettype: pgrx::pg_sys::Oid,
+    recorder: Option<DefaultRecorder>,
     scanning: LazyCell<Iter, Box<dyn FnOnce() -> Iter>>,
     bump: Box<bumpalo::Bump>,
+    split_locked: bool,
 }
 
 struct Index {
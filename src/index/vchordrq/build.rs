@@ -13,8 +13,10 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use simd::{Floating, f16};
+use vector::bvect::BVectOwned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::svect::SVectOwned;
 use vector::vect::VectOwned;
 use vector::{VectorBorrowed, VectorOwned};
 
@@ -100,3 +102,37 @@ impl Normalize for Rabitq4Owned {
         )
     }
 }
+
+impl Normalize for SVectOwned<f32> {
+    fn normalize(vector: Self) -> Normalized {
+        let vector = vector.as_borrowed();
+        let mut result = vec![0.0_f32; vector.dim() as _];
+        for (&index, &value) in std::iter::zip(vector.indexes(), vector.values()) {
+            result[index as usize] = value;
+        }
+        result
+    }
+
+    fn denormalize(mut vector: Normalized) -> Self {
+        // centroids are rotated before they are stored, but sparse vectors are
+        // stored without rotation, so rotate them back
+        rabitq::rotate::rotate_reversed_inplace(&mut vector);
+        let dim = vector.len() as u32;
+        let elements = vector.into_iter().enumerate();
+        vchordrq::operator::sparse_centroid(dim, elements.map(|(i, x)| (i as u32, x)))
+    }
+}
+
+impl Normalize for BVectOwned {
+    fn normalize(vector: Self) -> Normalized {
+        let vector = vector.as_borrowed();
+        vector.iter().map(|x| x as u8 as f32).collect()
+    }
+
+    fn denormalize(mut vector: Normalized) -> Self {
+        // centroids are rotated before they are stored, but binary vectors are
+        // stored without rotation, so rotate them back
+        rabitq::rotate::rotate_reversed_inplace(&mut vector);
+        vchordrq::operator::bit_centroid(&vector)
+    }
+}
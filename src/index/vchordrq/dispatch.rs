@@ -20,16 +20,18 @@ use index::prefetcher::*;
 use index::relation::{
     Hints, Page, RelationPrefetch, RelationRead, RelationReadStream, RelationWrite,
 };
-use index_accessor::{Dot, L2S};
+use index_accessor::{Dot, Hamming, Jaccard, L2S};
 use simd::f16;
 use std::collections::BinaryHeap;
 use std::num::NonZero;
 use vchordrq::operator::Op;
 use vchordrq::types::*;
-use vchordrq::{FastHeap, InsertChooser, MaintainChooser};
+use vchordrq::{FastHeap, InsertChooser, Maintain, MaintainChooser, Recluster};
 use vector::VectorOwned;
+use vector::bvect::BVectOwned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::svect::SVectOwned;
 use vector::vect::{VectBorrowed, VectOwned};
 
 pub fn prewarm<R>(opfamily: Opfamily, index: &R, height: i32) -> String
@@ -63,6 +65,27 @@ where
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
             vchordrq::prewarm::<_, Op<Rabitq4Owned, Dot>>(index, height, make_h0_plain_prefetcher)
         }
+        (VectorKind::Svecf32, DistanceKind::L2S) => {
+            vchordrq::prewarm::<_, Op<SVectOwned<f32>, L2S>>(
+                index,
+                height,
+                make_h0_plain_prefetcher,
+            )
+        }
+        (VectorKind::Svecf32, DistanceKind::Dot) => {
+            vchordrq::prewarm::<_, Op<SVectOwned<f32>, Dot>>(
+                index,
+                height,
+                make_h0_plain_prefetcher,
+            )
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordrq::prewarm::<_, Op<BVectOwned, Hamming>>(index, height, make_h0_plain_prefetcher)
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordrq::prewarm::<_, Op<BVectOwned, Jaccard>>(index, height, make_h0_plain_prefetcher)
+        }
+        _ => unreachable!(),
     }
 }
 
@@ -71,43 +94,137 @@ pub fn bulkdelete<R>(
     index: &R,
     check: impl Fn(),
     callback: impl Fn(NonZero<u64>) -> bool,
-) where
+) -> u64
+where
     R: RelationRead + RelationWrite,
     R::Page: Page<Opaque = vchordrq::Opaque>,
 {
     match (opfamily.vector_kind(), opfamily.distance_kind()) {
         (VectorKind::Vecf32, DistanceKind::L2S) => {
-            vchordrq::bulkdelete::<_, Op<VectOwned<f32>, L2S>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<VectOwned<f32>, L2S>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<VectOwned<f32>, L2S>>(index, &check, &callback);
+            removed
         }
         (VectorKind::Vecf32, DistanceKind::Dot) => {
-            vchordrq::bulkdelete::<_, Op<VectOwned<f32>, Dot>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<VectOwned<f32>, Dot>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<VectOwned<f32>, Dot>>(index, &check, &callback);
+            removed
         }
         (VectorKind::Vecf16, DistanceKind::L2S) => {
-            vchordrq::bulkdelete::<_, Op<VectOwned<f16>, L2S>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<VectOwned<f16>, L2S>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<VectOwned<f16>, L2S>>(index, &check, &callback);
+            removed
         }
         (VectorKind::Vecf16, DistanceKind::Dot) => {
-            vchordrq::bulkdelete::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<VectOwned<f16>, Dot>>(index, &check, &callback);
+            removed
         }
         (VectorKind::Rabitq8, DistanceKind::L2S) => {
-            vchordrq::bulkdelete::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<Rabitq8Owned, L2S>>(index, &check, &callback);
+            removed
         }
         (VectorKind::Rabitq8, DistanceKind::Dot) => {
-            vchordrq::bulkdelete::<_, Op<Rabitq8Owned, Dot>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<Rabitq8Owned, Dot>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<Rabitq8Owned, Dot>>(index, &check, &callback);
+            removed
         }
         (VectorKind::Rabitq4, DistanceKind::L2S) => {
-            vchordrq::bulkdelete::<_, Op<Rabitq4Owned, L2S>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<Rabitq4Owned, L2S>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<Rabitq4Owned, L2S>>(index, &check, &callback);
+            removed
         }
         (VectorKind::Rabitq4, DistanceKind::Dot) => {
-            vchordrq::bulkdelete::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback);
+            let removed =
+                vchordrq::bulkdelete::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback);
             vchordrq::bulkdelete_vectors::<_, Op<Rabitq4Owned, Dot>>(index, &check, &callback);
+            removed
+        }
+        (VectorKind::Svecf32, DistanceKind::L2S) => {
+            let removed =
+                vchordrq::bulkdelete::<_, Op<SVectOwned<f32>, L2S>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<SVectOwned<f32>, L2S>>(index, &check, &callback);
+            removed
+        }
+        (VectorKind::Svecf32, DistanceKind::Dot) => {
+            let removed =
+                vchordrq::bulkdelete::<_, Op<SVectOwned<f32>, Dot>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<SVectOwned<f32>, Dot>>(index, &check, &callback);
+            removed
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            let removed =
+                vchordrq::bulkdelete::<_, Op<BVectOwned, Hamming>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<BVectOwned, Hamming>>(index, &check, &callback);
+            removed
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            let removed =
+                vchordrq::bulkdelete::<_, Op<BVectOwned, Jaccard>>(index, &check, &callback);
+            vchordrq::bulkdelete_vectors::<_, Op<BVectOwned, Jaccard>>(index, &check, &callback);
+            removed
+        }
+        _ => unreachable!(),
+    }
+}
+
+pub fn verify<R>(
+    opfamily: Opfamily,
+    index: &R,
+    number_of_pages: u32,
+    check: impl Fn(),
+    callback: impl FnMut(NonZero<u64>),
+) -> Vec<vchordrq::Problem>
+where
+    R: RelationRead,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<VectOwned<f32>, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<VectOwned<f32>, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<VectOwned<f16>, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<VectOwned<f16>, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<Rabitq8Owned, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<Rabitq8Owned, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<Rabitq4Owned, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<Rabitq4Owned, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Svecf32, DistanceKind::L2S) => {
+            vchordrq::verify::<_, Op<SVectOwned<f32>, L2S>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Svecf32, DistanceKind::Dot) => {
+            vchordrq::verify::<_, Op<SVectOwned<f32>, Dot>>(index, number_of_pages, check, callback)
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordrq::verify::<_, Op<BVectOwned, Hamming>>(index, number_of_pages, check, callback)
         }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordrq::verify::<_, Op<BVectOwned, Jaccard>>(index, number_of_pages, check, callback)
+        }
+        _ => unreachable!(),
     }
 }
 
@@ -115,8 +232,12 @@ pub fn maintain<R>(
     opfamily: Opfamily,
     index: &R,
     chooser: &mut impl MaintainChooser,
+    split_factor: Option<f64>,
+    lock_split: impl Fn() -> bool,
+    unlock_split: impl Fn(),
     check: impl Fn(),
-) where
+) -> Maintain
+where
     R: RelationRead + RelationWrite,
     R::Page: Page<Opaque = vchordrq::Opaque>,
 {
@@ -127,6 +248,9 @@ pub fn maintain<R>(
                 index,
                 make_h0_plain_prefetcher,
                 chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
                 check,
             )
         }
@@ -135,6 +259,9 @@ pub fn maintain<R>(
                 index,
                 make_h0_plain_prefetcher,
                 chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
                 check,
             )
         }
@@ -143,6 +270,9 @@ pub fn maintain<R>(
                 index,
                 make_h0_plain_prefetcher,
                 chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
                 check,
             )
         }
@@ -151,6 +281,9 @@ pub fn maintain<R>(
                 index,
                 make_h0_plain_prefetcher,
                 chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
                 check,
             )
         }
@@ -158,26 +291,83 @@ pub fn maintain<R>(
             index,
             make_h0_plain_prefetcher,
             chooser,
+            split_factor,
+            &lock_split,
+            &unlock_split,
             check,
         ),
         (VectorKind::Rabitq8, DistanceKind::Dot) => vchordrq::maintain::<_, Op<Rabitq8Owned, Dot>>(
             index,
             make_h0_plain_prefetcher,
             chooser,
+            split_factor,
+            &lock_split,
+            &unlock_split,
             check,
         ),
         (VectorKind::Rabitq4, DistanceKind::L2S) => vchordrq::maintain::<_, Op<Rabitq4Owned, L2S>>(
             index,
             make_h0_plain_prefetcher,
             chooser,
+            split_factor,
+            &lock_split,
+            &unlock_split,
             check,
         ),
         (VectorKind::Rabitq4, DistanceKind::Dot) => vchordrq::maintain::<_, Op<Rabitq4Owned, Dot>>(
             index,
             make_h0_plain_prefetcher,
             chooser,
+            split_factor,
+            &lock_split,
+            &unlock_split,
             check,
         ),
+        (VectorKind::Svecf32, DistanceKind::L2S) => {
+            vchordrq::maintain::<_, Op<SVectOwned<f32>, L2S>>(
+                index,
+                make_h0_plain_prefetcher,
+                chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
+                check,
+            )
+        }
+        (VectorKind::Svecf32, DistanceKind::Dot) => {
+            vchordrq::maintain::<_, Op<SVectOwned<f32>, Dot>>(
+                index,
+                make_h0_plain_prefetcher,
+                chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
+                check,
+            )
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordrq::maintain::<_, Op<BVectOwned, Hamming>>(
+                index,
+                make_h0_plain_prefetcher,
+                chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
+                check,
+            )
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordrq::maintain::<_, Op<BVectOwned, Jaccard>>(
+                index,
+                make_h0_plain_prefetcher,
+                chooser,
+                split_factor,
+                &lock_split,
+                &unlock_split,
+                check,
+            )
+        }
+        _ => unreachable!(),
     };
     pgrx::debug1!(
         "maintain: number_of_formerly_allocated_pages = {}",
@@ -191,6 +381,161 @@ pub fn maintain<R>(
         "maintain: number_of_freed_pages = {}",
         maintain.number_of_freed_pages
     );
+    pgrx::debug1!(
+        "maintain: number_of_split_lists = {}",
+        maintain.number_of_split_lists
+    );
+    maintain
+}
+
+pub fn recluster<R>(
+    opfamily: Opfamily,
+    index: &R,
+    structures: Vec<Structure<Normalized>>,
+    lock_writers: impl FnOnce(),
+    unlock_writers: impl FnOnce(),
+    wait_for_readers: impl FnOnce(),
+    check: impl Fn(),
+) -> Option<Recluster>
+where
+    R: RelationRead + RelationWrite,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+{
+    let recluster = match (opfamily.vector_kind(), opfamily.distance_kind()) {
+        (VectorKind::Vecf32, DistanceKind::L2S) => {
+            vchordrq::recluster::<_, Op<VectOwned<f32>, L2S>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Vecf32, DistanceKind::Dot) => {
+            vchordrq::recluster::<_, Op<VectOwned<f32>, Dot>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Vecf16, DistanceKind::L2S) => {
+            vchordrq::recluster::<_, Op<VectOwned<f16>, L2S>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Vecf16, DistanceKind::Dot) => {
+            vchordrq::recluster::<_, Op<VectOwned<f16>, Dot>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Rabitq8, DistanceKind::L2S) => {
+            vchordrq::recluster::<_, Op<Rabitq8Owned, L2S>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Rabitq8, DistanceKind::Dot) => {
+            vchordrq::recluster::<_, Op<Rabitq8Owned, Dot>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Rabitq4, DistanceKind::L2S) => {
+            vchordrq::recluster::<_, Op<Rabitq4Owned, L2S>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Rabitq4, DistanceKind::Dot) => {
+            vchordrq::recluster::<_, Op<Rabitq4Owned, Dot>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Svecf32, DistanceKind::L2S) => {
+            vchordrq::recluster::<_, Op<SVectOwned<f32>, L2S>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Svecf32, DistanceKind::Dot) => {
+            vchordrq::recluster::<_, Op<SVectOwned<f32>, Dot>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Bit, DistanceKind::Hamming) => {
+            vchordrq::recluster::<_, Op<BVectOwned, Hamming>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        (VectorKind::Bit, DistanceKind::Jaccard) => {
+            vchordrq::recluster::<_, Op<BVectOwned, Jaccard>>(
+                index,
+                map_structures(structures, Normalize::denormalize),
+                lock_writers,
+                unlock_writers,
+                wait_for_readers,
+                check,
+            )
+        }
+        _ => unreachable!(),
+    };
+    if let Some(recluster) = recluster.as_ref() {
+        pgrx::debug1!("recluster: number_of_lists = {}", recluster.number_of_lists);
+        pgrx::debug1!(
+            "recluster: number_of_tuples = {}",
+            recluster.number_of_tuples
+        );
+        pgrx::debug1!(
+            "recluster: number_of_freed_pages = {}",
+            recluster.number_of_freed_pages
+        );
+    }
+    recluster
 }
 
 pub fn build<R>(
@@ -251,6 +596,31 @@ pub fn build<R>(
             index,
             map_structures(structures, Normalize::denormalize),
         ),
+        (VectorKind::Svecf32, DistanceKind::L2S) => vchordrq::build::<_, Op<SVectOwned<f32>, L2S>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Svecf32, DistanceKind::Dot) => vchordrq::build::<_, Op<SVectOwned<f32>, Dot>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Bit, DistanceKind::Hamming) => vchordrq::build::<_, Op<BVectOwned, Hamming>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        (VectorKind::Bit, DistanceKind::Jaccard) => vchordrq::build::<_, Op<BVectOwned, Jaccard>>(
+            vector_options,
+            vchordrq_options,
+            index,
+            map_structures(structures, Normalize::denormalize),
+        ),
+        _ => unreachable!(),
     }
 }
 
@@ -425,6 +795,83 @@ pub fn insert<R>(
                 skip_freespaces,
             )
         }
+        (OwnedVector::Svecf32(vector), DistanceKind::Dot) => {
+            assert!(opfamily.vector_kind() == VectorKind::Svecf32);
+            let key = vchordrq::insert_vector::<_, Op<SVectOwned<f32>, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<SVectOwned<f32>, Dot>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Svecf32(vector), DistanceKind::L2S) => {
+            assert!(opfamily.vector_kind() == VectorKind::Svecf32);
+            let key = vchordrq::insert_vector::<_, Op<SVectOwned<f32>, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<SVectOwned<f32>, L2S>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Bit(vector), DistanceKind::Hamming) => {
+            assert!(opfamily.vector_kind() == VectorKind::Bit);
+            let key = vchordrq::insert_vector::<_, Op<BVectOwned, Hamming>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<BVectOwned, Hamming>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        (OwnedVector::Bit(vector), DistanceKind::Jaccard) => {
+            assert!(opfamily.vector_kind() == VectorKind::Bit);
+            let key = vchordrq::insert_vector::<_, Op<BVectOwned, Jaccard>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                chooser,
+                skip_search,
+            );
+            vchordrq::insert::<_, Op<BVectOwned, Jaccard>>(
+                index,
+                payload,
+                vector.as_borrowed(),
+                key,
+                bump,
+                make_h1_plain_prefetcher,
+                skip_freespaces,
+            )
+        }
+        _ => unreachable!(),
     }
 }
 
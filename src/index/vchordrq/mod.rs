@@ -19,3 +19,5 @@ mod filter;
 pub mod opclass;
 mod scanners;
 pub mod types;
+
+pub use scanners::scanned_tuples;
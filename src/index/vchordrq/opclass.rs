@@ -12,11 +12,14 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
+use crate::datatype::composite;
+use crate::datatype::memory_bit::{BitInput, BitOutput};
 use crate::datatype::memory_halfvec::{HalfvecInput, HalfvecOutput};
 use crate::datatype::memory_rabitq4::{Rabitq4Input, Rabitq4Output};
 use crate::datatype::memory_rabitq8::{Rabitq8Input, Rabitq8Output};
+use crate::datatype::memory_sparsevec::{SparsevecInput, SparsevecOutput};
 use crate::datatype::memory_vector::{VectorInput, VectorOutput};
-use crate::index::opclass::Sphere;
+use crate::index::opclass::{Shape, Sphere, support};
 use distance::Distance;
 use pgrx::datum::FromDatum;
 use pgrx::heap_tuple::PgHeapTuple;
@@ -39,6 +42,11 @@ pub enum Opfamily {
     Rabitq4L2,
     Rabitq4Ip,
     Rabitq4Cosine,
+    SparsevecL2,
+    SparsevecIp,
+    SparsevecCosine,
+    BitHamming,
+    BitJaccard,
     VectorMaxsim,
     HalfvecMaxsim,
     Rabitq8Maxsim,
@@ -46,6 +54,32 @@ pub enum Opfamily {
 }
 
 impl Opfamily {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "vchordrq_vector_l2_ops" => Some(Self::VectorL2),
+            "vchordrq_vector_ip_ops" => Some(Self::VectorIp),
+            "vchordrq_vector_cosine_ops" => Some(Self::VectorCosine),
+            "vchordrq_halfvec_l2_ops" => Some(Self::HalfvecL2),
+            "vchordrq_halfvec_ip_ops" => Some(Self::HalfvecIp),
+            "vchordrq_halfvec_cosine_ops" => Some(Self::HalfvecCosine),
+            "vchordrq_rabitq8_l2_ops" => Some(Self::Rabitq8L2),
+            "vchordrq_rabitq8_ip_ops" => Some(Self::Rabitq8Ip),
+            "vchordrq_rabitq8_cosine_ops" => Some(Self::Rabitq8Cosine),
+            "vchordrq_rabitq4_l2_ops" => Some(Self::Rabitq4L2),
+            "vchordrq_rabitq4_ip_ops" => Some(Self::Rabitq4Ip),
+            "vchordrq_rabitq4_cosine_ops" => Some(Self::Rabitq4Cosine),
+            "vchordrq_sparsevec_l2_ops" => Some(Self::SparsevecL2),
+            "vchordrq_sparsevec_ip_ops" => Some(Self::SparsevecIp),
+            "vchordrq_sparsevec_cosine_ops" => Some(Self::SparsevecCosine),
+            "vchordrq_bit_hamming_ops" => Some(Self::BitHamming),
+            "vchordrq_bit_jaccard_ops" => Some(Self::BitJaccard),
+            "vchordrq_vector_maxsim_ops" => Some(Self::VectorMaxsim),
+            "vchordrq_halfvec_maxsim_ops" => Some(Self::HalfvecMaxsim),
+            "vchordrq_rabitq8_maxsim_ops" => Some(Self::Rabitq8Maxsim),
+            "vchordrq_rabitq4_maxsim_ops" => Some(Self::Rabitq4Maxsim),
+            _ => None,
+        }
+    }
     fn input(self, vector: BorrowedVector<'_>) -> OwnedVector {
         use {BorrowedVector as B, OwnedVector as O};
         match (vector, self) {
@@ -65,6 +99,12 @@ impl Opfamily {
             (B::Rabitq4(x), Self::Rabitq4Ip | Self::Rabitq4Maxsim) => O::Rabitq4(x.own()),
             (B::Rabitq4(x), Self::Rabitq4Cosine) => O::Rabitq4(x.function_normalize()),
             (B::Rabitq4(_), _) => unreachable!(),
+            (B::Svecf32(x), Self::SparsevecL2) => O::Svecf32(x.own()),
+            (B::Svecf32(x), Self::SparsevecIp) => O::Svecf32(x.own()),
+            (B::Svecf32(x), Self::SparsevecCosine) => O::Svecf32(x.function_normalize()),
+            (B::Svecf32(_), _) => unreachable!(),
+            (B::Bit(x), Self::BitHamming | Self::BitJaccard) => O::Bit(x.own()),
+            (B::Bit(_), _) => unreachable!(),
         }
     }
     pub unsafe fn store(self, datum: Datum) -> Option<Vec<(OwnedVector, u16)>> {
@@ -88,6 +128,19 @@ impl Opfamily {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 vec![(self.input(BorrowedVector::Rabitq4(vector.as_borrowed())), 0)]
             }
+            Self::SparsevecL2 | Self::SparsevecIp | Self::SparsevecCosine => {
+                let vector = unsafe { SparsevecInput::from_datum(datum, false).unwrap() };
+                if vector.as_borrowed().len() > 1920 {
+                    pgrx::error!(
+                        "sparse vector with more than 1920 non-zero elements is not supported"
+                    );
+                }
+                vec![(self.input(BorrowedVector::Svecf32(vector.as_borrowed())), 0)]
+            }
+            Self::BitHamming | Self::BitJaccard => {
+                let vector = unsafe { BitInput::from_datum(datum, false).unwrap() };
+                vec![(self.input(BorrowedVector::Bit(vector.as_borrowed())), 0)]
+            }
             Self::VectorMaxsim => {
                 let vectors =
                     unsafe { pgrx::datum::Array::<VectorInput>::from_datum(datum, false).unwrap() };
@@ -166,6 +219,14 @@ impl Opfamily {
                 let vector = tuple.get_by_index::<Rabitq4Output>(attno_1).unwrap()?;
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::SparsevecL2 | Self::SparsevecIp | Self::SparsevecCosine => {
+                let vector = tuple.get_by_index::<SparsevecOutput>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Svecf32(vector.as_borrowed()))
+            }
+            Self::BitHamming | Self::BitJaccard => {
+                let vector = tuple.get_by_index::<BitOutput>(attno_1).unwrap()?;
+                self.input(BorrowedVector::Bit(vector.as_borrowed()))
+            }
         };
         let radius = tuple.get_by_index::<f32>(attno_2).unwrap()?;
         Some(Sphere { center, radius })
@@ -191,9 +252,47 @@ impl Opfamily {
                 let vector = unsafe { Rabitq4Input::from_datum(datum, false).unwrap() };
                 self.input(BorrowedVector::Rabitq4(vector.as_borrowed()))
             }
+            Self::SparsevecL2 | Self::SparsevecIp | Self::SparsevecCosine => {
+                let vector = unsafe { SparsevecInput::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Svecf32(vector.as_borrowed()))
+            }
+            Self::BitHamming | Self::BitJaccard => {
+                let vector = unsafe { BitInput::from_datum(datum, false).unwrap() };
+                self.input(BorrowedVector::Bit(vector.as_borrowed()))
+            }
         };
         Some(vector)
     }
+    pub unsafe fn input_maxsim_threshold(self, datum: Datum) -> Option<Sphere<Vec<OwnedVector>>> {
+        if datum.is_null() {
+            return None;
+        }
+        let attno_1 = NonZero::new(1_usize).unwrap();
+        let attno_2 = NonZero::new(2_usize).unwrap();
+        let query = unsafe { composite::get_by_index(datum, attno_1)? };
+        let query = unsafe { self.input_vectors(query)? };
+        let threshold = unsafe { composite::get_by_index(datum, attno_2)? };
+        let threshold = unsafe { f32::from_datum(threshold, false)? };
+        Some(Sphere {
+            center: query,
+            radius: threshold,
+        })
+    }
+    pub unsafe fn input_multi_query(self, datum: Datum) -> Option<Vec<(OwnedVector, f32)>> {
+        if datum.is_null() {
+            return None;
+        }
+        let attno_1 = NonZero::new(1_usize).unwrap();
+        let attno_2 = NonZero::new(2_usize).unwrap();
+        let queries = unsafe { composite::get_by_index(datum, attno_1)? };
+        let queries = unsafe { self.input_vectors(queries)? };
+        let weights = unsafe { composite::get_by_index(datum, attno_2)? };
+        let weights = unsafe { pgrx::datum::Array::<f32>::from_datum(weights, false)? };
+        if queries.len() != weights.len() {
+            pgrx::error!("Bad input: queries and weights are not matched at multi_query");
+        }
+        Some(queries.into_iter().zip(weights.iter_deny_null()).collect())
+    }
     pub unsafe fn input_vectors(self, datum: Datum) -> Option<Vec<OwnedVector>> {
         if datum.is_null() {
             return None;
@@ -238,6 +337,25 @@ impl Opfamily {
                 }
                 result
             }
+            Self::SparsevecL2 | Self::SparsevecIp | Self::SparsevecCosine => {
+                let vectors = unsafe {
+                    pgrx::datum::Array::<SparsevecInput>::from_datum(datum, false).unwrap()
+                };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Svecf32(vector.as_borrowed())));
+                }
+                result
+            }
+            Self::BitHamming | Self::BitJaccard => {
+                let vectors =
+                    unsafe { pgrx::datum::Array::<BitInput>::from_datum(datum, false).unwrap() };
+                let mut result = Vec::with_capacity(vectors.len());
+                for vector in vectors.iter_deny_null() {
+                    result.push(self.input(BorrowedVector::Bit(vector.as_borrowed())));
+                }
+                result
+            }
         };
         Some(vectors)
     }
@@ -246,14 +364,20 @@ impl Opfamily {
             Self::VectorCosine
             | Self::HalfvecCosine
             | Self::Rabitq8Cosine
-            | Self::Rabitq4Cosine => x.to_f32() + 1.0f32,
-            Self::VectorL2 | Self::HalfvecL2 | Self::Rabitq8L2 | Self::Rabitq4L2 => {
-                x.to_f32().sqrt()
-            }
+            | Self::Rabitq4Cosine
+            | Self::SparsevecCosine => x.to_f32() + 1.0f32,
+            Self::VectorL2
+            | Self::HalfvecL2
+            | Self::Rabitq8L2
+            | Self::Rabitq4L2
+            | Self::SparsevecL2 => x.to_f32().sqrt(),
             Self::VectorIp
             | Self::HalfvecIp
             | Self::Rabitq8Ip
             | Self::Rabitq4Ip
+            | Self::SparsevecIp
+            | Self::BitHamming
+            | Self::BitJaccard
             | Self::VectorMaxsim
             | Self::HalfvecMaxsim
             | Self::Rabitq8Maxsim
@@ -262,21 +386,27 @@ impl Opfamily {
     }
     pub const fn distance_kind(self) -> DistanceKind {
         match self {
-            Self::VectorL2 | Self::HalfvecL2 | Self::Rabitq8L2 | Self::Rabitq4L2 => {
-                DistanceKind::L2S
-            }
+            Self::VectorL2
+            | Self::HalfvecL2
+            | Self::Rabitq8L2
+            | Self::Rabitq4L2
+            | Self::SparsevecL2 => DistanceKind::L2S,
             Self::VectorIp
             | Self::HalfvecIp
             | Self::Rabitq8Ip
             | Self::Rabitq4Ip
+            | Self::SparsevecIp
             | Self::VectorCosine
             | Self::HalfvecCosine
             | Self::Rabitq8Cosine
             | Self::Rabitq4Cosine
+            | Self::SparsevecCosine
             | Self::VectorMaxsim
             | Self::HalfvecMaxsim
             | Self::Rabitq8Maxsim
             | Self::Rabitq4Maxsim => DistanceKind::Dot,
+            Self::BitHamming => DistanceKind::Hamming,
+            Self::BitJaccard => DistanceKind::Jaccard,
         }
     }
     pub const fn vector_kind(self) -> VectorKind {
@@ -293,6 +423,66 @@ impl Opfamily {
             Self::Rabitq4L2 | Self::Rabitq4Ip | Self::Rabitq4Cosine | Self::Rabitq4Maxsim => {
                 VectorKind::Rabitq4
             }
+            Self::SparsevecL2 | Self::SparsevecIp | Self::SparsevecCosine => VectorKind::Svecf32,
+            Self::BitHamming | Self::BitJaccard => VectorKind::Bit,
+        }
+    }
+    pub const fn shape(self) -> Shape {
+        let (input, sphere, multi_query) = match self {
+            Self::VectorL2 | Self::VectorIp | Self::VectorCosine => {
+                ("vector", "sphere_vector", Some("multi_query_vector"))
+            }
+            Self::HalfvecL2 | Self::HalfvecIp | Self::HalfvecCosine => {
+                ("halfvec", "sphere_halfvec", Some("multi_query_halfvec"))
+            }
+            Self::Rabitq8L2 | Self::Rabitq8Ip | Self::Rabitq8Cosine => {
+                ("rabitq8", "sphere_rabitq8", None)
+            }
+            Self::Rabitq4L2 | Self::Rabitq4Ip | Self::Rabitq4Cosine => {
+                ("rabitq4", "sphere_rabitq4", None)
+            }
+            Self::SparsevecL2 | Self::SparsevecIp | Self::SparsevecCosine => {
+                ("sparsevec", "sphere_sparsevec", None)
+            }
+            Self::BitHamming | Self::BitJaccard => ("bit", "sphere_bit", None),
+            Self::VectorMaxsim => {
+                return Shape {
+                    input: "_vector",
+                    order: 3,
+                    sphere: Some("maxsim_threshold_vector"),
+                    multi_query: None,
+                };
+            }
+            Self::HalfvecMaxsim => {
+                return Shape {
+                    input: "_halfvec",
+                    order: 3,
+                    sphere: Some("maxsim_threshold_halfvec"),
+                    multi_query: None,
+                };
+            }
+            Self::Rabitq8Maxsim => {
+                return Shape {
+                    input: "_rabitq8",
+                    order: 3,
+                    sphere: Some("maxsim_threshold_rabitq8"),
+                    multi_query: None,
+                };
+            }
+            Self::Rabitq4Maxsim => {
+                return Shape {
+                    input: "_rabitq4",
+                    order: 3,
+                    sphere: Some("maxsim_threshold_rabitq4"),
+                    multi_query: None,
+                };
+            }
+        };
+        Shape {
+            input,
+            order: 1,
+            sphere: Some(sphere),
+            multi_query,
         }
     }
 }
@@ -306,54 +496,13 @@ pub unsafe fn opfamily(index_relation: pgrx::pg_sys::Relation) -> Opfamily {
         pgrx::error!("support function 1 is not found");
     }
 
-    let mut flinfo = pgrx::pg_sys::FmgrInfo::default();
-
-    unsafe {
-        pgrx::pg_sys::fmgr_info(proc, &mut flinfo);
-    }
-
-    let fn_addr = flinfo.fn_addr.expect("null function pointer");
-
-    let mut fcinfo = unsafe { std::mem::zeroed::<pgrx::pg_sys::FunctionCallInfoBaseData>() };
-    fcinfo.flinfo = &mut flinfo;
-    fcinfo.fncollation = pgrx::pg_sys::DEFAULT_COLLATION_OID;
-    fcinfo.context = std::ptr::null_mut();
-    fcinfo.resultinfo = std::ptr::null_mut();
-    fcinfo.isnull = true;
-    fcinfo.nargs = 0;
-
-    let result_datum = unsafe {
-        #[allow(ffi_unwind_calls, reason = "protected by pg_guard_ffi_boundary")]
-        pgrx::pg_sys::ffi::pg_guard_ffi_boundary(|| fn_addr(&mut fcinfo))
-    };
+    let result_string = unsafe { support(proc) }.expect("null return value");
 
-    let result_option = unsafe { String::from_datum(result_datum, fcinfo.isnull) };
-
-    let result_string = result_option.expect("null return value");
-
-    let result = match result_string.as_str() {
-        "vchordrq_vector_l2_ops" => Opfamily::VectorL2,
-        "vchordrq_vector_ip_ops" => Opfamily::VectorIp,
-        "vchordrq_vector_cosine_ops" => Opfamily::VectorCosine,
-        "vchordrq_halfvec_l2_ops" => Opfamily::HalfvecL2,
-        "vchordrq_halfvec_ip_ops" => Opfamily::HalfvecIp,
-        "vchordrq_halfvec_cosine_ops" => Opfamily::HalfvecCosine,
-        "vchordrq_rabitq8_l2_ops" => Opfamily::Rabitq8L2,
-        "vchordrq_rabitq8_ip_ops" => Opfamily::Rabitq8Ip,
-        "vchordrq_rabitq8_cosine_ops" => Opfamily::Rabitq8Cosine,
-        "vchordrq_rabitq4_l2_ops" => Opfamily::Rabitq4L2,
-        "vchordrq_rabitq4_ip_ops" => Opfamily::Rabitq4Ip,
-        "vchordrq_rabitq4_cosine_ops" => Opfamily::Rabitq4Cosine,
-        "vchordrq_vector_maxsim_ops" => Opfamily::VectorMaxsim,
-        "vchordrq_halfvec_maxsim_ops" => Opfamily::HalfvecMaxsim,
-        "vchordrq_rabitq8_maxsim_ops" => Opfamily::Rabitq8Maxsim,
-        "vchordrq_rabitq4_maxsim_ops" => Opfamily::Rabitq4Maxsim,
-        _ => pgrx::error!("unknown operator class"),
-    };
+    Opfamily::from_name(&result_string).unwrap_or_else(|| pgrx::error!("unknown operator class"))
+}
 
+pub unsafe fn validate(opclass: pgrx::pg_sys::Oid) -> bool {
     unsafe {
-        pgrx::pg_sys::pfree(result_datum.cast_mut_ptr());
+        crate::index::opclass::validate(opclass, |name| Some(Opfamily::from_name(name)?.shape()))
     }
-
-    result
 }
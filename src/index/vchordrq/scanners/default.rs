@@ -14,26 +14,23 @@
 
 use crate::index::fetcher::*;
 use crate::index::opclass::Sphere;
-use crate::index::scanners::{Io, SearchBuilder};
+use crate::index::scanners::SearchBuilder;
 use crate::index::vchordrq::dispatch::*;
-use crate::index::vchordrq::filter::filter;
 use crate::index::vchordrq::opclass::Opfamily;
-use crate::index::vchordrq::scanners::SearchOptions;
+use crate::index::vchordrq::scanners::{SearchOptions, candidates, rerank, search};
 use crate::recorder::{Recorder, text};
-use always_equal::AlwaysEqual;
-use dary_heap::QuaternaryHeap as Heap;
+use distance::Distance;
 use index::bump::Bump;
-use index::packed::PackedRefMut4;
-use index::prefetcher::*;
-use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
-use index_accessor::{Dot, L2S};
+use index::relation::{Page, RelationPrefetch, RelationRead, RelationReadStream};
+use index_accessor::{Dot, Hamming, Jaccard, L2S};
 use simd::f16;
 use std::num::NonZero;
 use vchordrq::types::{DistanceKind, OwnedVector, VectorKind};
-use vchordrq::{RerankMethod, default_search, how, rerank_heap, rerank_index};
 use vector::VectorOwned;
+use vector::bvect::BVectOwned;
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
+use vector::svect::SVectOwned;
 use vector::vect::VectOwned;
 
 pub struct DefaultBuilder {
@@ -64,6 +61,11 @@ impl SearchBuilder for DefaultBuilder {
                 | Opfamily::Rabitq4Cosine
                 | Opfamily::Rabitq4Ip
                 | Opfamily::Rabitq4L2
+                | Opfamily::SparsevecL2
+                | Opfamily::SparsevecIp
+                | Opfamily::SparsevecCosine
+                | Opfamily::BitHamming
+                | Opfamily::BitJaccard
         ));
         Self {
             opfamily,
@@ -90,7 +92,7 @@ impl SearchBuilder for DefaultBuilder {
         self,
         index: &'b R,
         options: SearchOptions,
-        mut fetcher: impl Fetcher + 'b,
+        fetcher: impl Fetcher + 'b,
         bump: &'b impl Bump,
         recorder: impl Recorder,
     ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
@@ -119,17 +121,8 @@ impl SearchBuilder for DefaultBuilder {
         let Some(vector) = vector else {
             return Box::new(std::iter::empty()) as Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
         };
-        let search_hints = Hints::default().full(true);
-        let rerank_hints = Hints::default().full(false);
-        let make_h1_plain_prefetcher = MakeH1PlainPrefetcher { index };
-        let make_h0_plain_prefetcher = MakeH0PlainPrefetcher { index };
-        let make_h0_simple_prefetcher = MakeH0SimplePrefetcher { index };
-        let make_h0_stream_prefetcher = MakeH0StreamPrefetcher {
-            index,
-            hints: search_hints,
-        };
         let f = move |(distance, payload)| (opfamily.output(distance), payload);
-        let iter: Box<dyn Iterator<Item = (f32, NonZero<u64>)>> =
+        let iter: Box<dyn Iterator<Item = (Distance, NonZero<u64>)>> =
             match (opfamily.vector_kind(), opfamily.distance_kind()) {
                 (VectorKind::Vecf32, DistanceKind::L2S) => {
                     type Op = vchordrq::operator::Op<VectOwned<f32>, L2S>;
@@ -139,135 +132,16 @@ impl SearchBuilder for DefaultBuilder {
                         unreachable!()
                     };
                     let projected = RandomProject::project(unprojected.as_borrowed());
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
-                    };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf32(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf32(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Vecf32(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
                 (VectorKind::Vecf32, DistanceKind::Dot) => {
                     type Op = vchordrq::operator::Op<VectOwned<f32>, Dot>;
@@ -277,135 +151,16 @@ impl SearchBuilder for DefaultBuilder {
                         unreachable!()
                     };
                     let projected = RandomProject::project(unprojected.as_borrowed());
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
-                    };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf32(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf32(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Vecf32(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
                 (VectorKind::Vecf16, DistanceKind::L2S) => {
                     type Op = vchordrq::operator::Op<VectOwned<f16>, L2S>;
@@ -415,135 +170,16 @@ impl SearchBuilder for DefaultBuilder {
                         unreachable!()
                     };
                     let projected = RandomProject::project(unprojected.as_borrowed());
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
-                    };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf16(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf16(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Vecf16(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
                 (VectorKind::Vecf16, DistanceKind::Dot) => {
                     type Op = vchordrq::operator::Op<VectOwned<f16>, Dot>;
@@ -553,135 +189,16 @@ impl SearchBuilder for DefaultBuilder {
                         unreachable!()
                     };
                     let projected = RandomProject::project(unprojected.as_borrowed());
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            projected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
-                    };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf16(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw = if let OwnedVector::Vecf16(vector) = maybe_vector.unwrap()
-                                {
-                                    vector
-                                } else {
-                                    unreachable!()
-                                };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Vecf16(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
                 (VectorKind::Rabitq8, DistanceKind::L2S) => {
                     type Op = vchordrq::operator::Op<Rabitq8Owned, L2S>;
@@ -690,135 +207,17 @@ impl SearchBuilder for DefaultBuilder {
                     } else {
                         unreachable!()
                     };
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
-                    };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq8(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq8(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Rabitq8(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
                 (VectorKind::Rabitq8, DistanceKind::Dot) => {
                     type Op = vchordrq::operator::Op<Rabitq8Owned, Dot>;
@@ -827,135 +226,17 @@ impl SearchBuilder for DefaultBuilder {
                     } else {
                         unreachable!()
                     };
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
-                    };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq8(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq8(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Rabitq8(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
                 (VectorKind::Rabitq4, DistanceKind::L2S) => {
                     type Op = vchordrq::operator::Op<Rabitq4Owned, L2S>;
@@ -964,135 +245,17 @@ impl SearchBuilder for DefaultBuilder {
                     } else {
                         unreachable!()
                     };
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
-                    };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq4(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq4(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Rabitq4(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
                 (VectorKind::Rabitq4, DistanceKind::Dot) => {
                     type Op = vchordrq::operator::Op<Rabitq4Owned, Dot>;
@@ -1101,137 +264,97 @@ impl SearchBuilder for DefaultBuilder {
                     } else {
                         unreachable!()
                     };
-                    let results = match options.io_search {
-                        Io::Plain => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_plain_prefetcher,
-                        ),
-                        Io::Simple => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_simple_prefetcher,
-                        ),
-                        Io::Stream => default_search::<_, Op>(
-                            index,
-                            unprojected.as_borrowed(),
-                            options.probes,
-                            options.epsilon,
-                            bump,
-                            make_h1_plain_prefetcher,
-                            make_h0_stream_prefetcher,
-                        ),
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Rabitq4(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                }
+                (VectorKind::Svecf32, DistanceKind::L2S) => {
+                    type Op = vchordrq::operator::Op<SVectOwned<f32>, L2S>;
+                    let unprojected = if let OwnedVector::Svecf32(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
                     };
-                    let method = how(index);
-                    let sequence = Heap::from(results);
-                    match (method, options.io_rerank, options.prefilter) {
-                        (RerankMethod::Index, Io::Plain, false) => {
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Plain, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, false) => {
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Simple, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = SimplePrefetcher::new(index, sequence);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, false) => {
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Index, Io::Stream, true) => {
-                            let predicate =
-                                id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
-                                    let (key, _) = pointer_to_kv(*pointer);
-                                    let Some(mut tuple) = fetcher.fetch(key) else {
-                                        return false;
-                                    };
-                                    tuple.filter()
-                                });
-                            let sequence = filter(sequence, predicate);
-                            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
-                            Box::new(rerank_index::<Op, _, _, _>(unprojected, prefetcher).map(f))
-                        }
-                        (RerankMethod::Heap, _, false) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq4(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                        (RerankMethod::Heap, _, true) => {
-                            let fetch = move |payload| {
-                                let (key, _) = pointer_to_kv(payload);
-                                let mut tuple = fetcher.fetch(key)?;
-                                if !tuple.filter() {
-                                    return None;
-                                }
-                                let (datums, is_nulls) = tuple.build();
-                                let datum = (!is_nulls[0]).then_some(datums[0]);
-                                let maybe_vector =
-                                    unsafe { datum.and_then(|x| opfamily.input_vector(x)) };
-                                let raw =
-                                    if let OwnedVector::Rabitq4(vector) = maybe_vector.unwrap() {
-                                        vector
-                                    } else {
-                                        unreachable!()
-                                    };
-                                Some(raw)
-                            };
-                            let prefetcher = PlainPrefetcher::new(index, sequence);
-                            Box::new(
-                                rerank_heap::<Op, _, _, _>(unprojected, prefetcher, fetch).map(f),
-                            )
-                        }
-                    }
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Svecf32(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                }
+                (VectorKind::Svecf32, DistanceKind::Dot) => {
+                    type Op = vchordrq::operator::Op<SVectOwned<f32>, Dot>;
+                    let unprojected = if let OwnedVector::Svecf32(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Svecf32(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
                 }
+                (VectorKind::Bit, DistanceKind::Hamming) => {
+                    type Op = vchordrq::operator::Op<BVectOwned, Hamming>;
+                    let unprojected = if let OwnedVector::Bit(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Bit(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                }
+                (VectorKind::Bit, DistanceKind::Jaccard) => {
+                    type Op = vchordrq::operator::Op<BVectOwned, Jaccard>;
+                    let unprojected = if let OwnedVector::Bit(vector) = vector.clone() {
+                        vector
+                    } else {
+                        unreachable!()
+                    };
+                    let projected = unprojected.clone();
+                    let search = search::<_, Op>(index, projected, &options, bump);
+                    let sequence = candidates(index, &options, search);
+                    rerank::<_, Op>(index, &options, unprojected, sequence, fetcher, move |x| {
+                        let maybe_vector = unsafe { opfamily.input_vector(x) };
+                        if let Some(OwnedVector::Bit(vector)) = maybe_vector {
+                            vector
+                        } else {
+                            unreachable!()
+                        }
+                    })
+                }
+                _ => unreachable!(),
             };
+        let iter: Box<dyn Iterator<Item = (f32, NonZero<u64>)>> = Box::new(iter.map(f));
         let iter = if let Some(threshold) = threshold {
             Box::new(iter.take_while(move |(x, _)| *x < threshold))
         } else {
@@ -1256,6 +379,12 @@ impl SearchBuilder for DefaultBuilder {
                 OwnedVector::Rabitq4(v) => {
                     recorder.send(&text::rabitq4_out(v.as_borrowed()));
                 }
+                OwnedVector::Svecf32(v) => {
+                    recorder.send(&text::sparsevec_out(v.as_borrowed()));
+                }
+                OwnedVector::Bit(v) => {
+                    recorder.send(&text::bit_out(v.as_borrowed()));
+                }
             }
         }
         Box::new(iter.map(move |(distance, pointer)| {
@@ -1264,11 +393,3 @@ impl SearchBuilder for DefaultBuilder {
         }))
     }
 }
-
-#[inline(always)]
-pub fn id_0<F, A: ?Sized, B: ?Sized, C: ?Sized, D: ?Sized, R: ?Sized>(f: F) -> F
-where
-    F: for<'a> FnMut(&(A, AlwaysEqual<PackedRefMut4<'a, (B, C, D)>>)) -> R,
-{
-    f
-}
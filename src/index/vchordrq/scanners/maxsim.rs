@@ -13,6 +13,7 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use crate::index::fetcher::*;
+use crate::index::opclass::Sphere;
 use crate::index::scanners::{Io, SearchBuilder};
 use crate::index::vchordrq::dispatch::*;
 use crate::index::vchordrq::filter::filter;
@@ -23,24 +24,28 @@ use always_equal::AlwaysEqual;
 use dary_heap::QuaternaryHeap as Heap;
 use distance::Distance;
 use index::bump::Bump;
+use index::fetch::BorrowedIter;
 use index::packed::PackedRefMut8;
 use index::prefetcher::*;
 use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
 use index_accessor::Dot;
+use pgrx::pg_sys::Datum;
 use simd::f16;
 use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 use std::num::NonZero;
+use vchordrq::operator::Operator;
 use vchordrq::types::{DistanceKind, OwnedVector, VectorKind};
-use vchordrq::{RerankMethod, how, maxsim_search, rerank_index};
-use vector::VectorOwned;
+use vchordrq::{Probes, RerankMethod, how, maxsim_search, rerank_heap, rerank_index};
 use vector::rabitq4::Rabitq4Owned;
 use vector::rabitq8::Rabitq8Owned;
 use vector::vect::VectOwned;
+use vector::{VectorBorrowed, VectorOwned};
 
 pub struct MaxsimBuilder {
     opfamily: Opfamily,
     orderbys: Vec<Option<Vec<OwnedVector>>>,
+    thresholds: Vec<Option<Sphere<Vec<OwnedVector>>>>,
 }
 
 impl SearchBuilder for MaxsimBuilder {
@@ -61,6 +66,7 @@ impl SearchBuilder for MaxsimBuilder {
         Self {
             opfamily,
             orderbys: Vec::new(),
+            thresholds: Vec::new(),
         }
     }
 
@@ -70,6 +76,10 @@ impl SearchBuilder for MaxsimBuilder {
                 let x = unsafe { datum.and_then(|x| self.opfamily.input_vectors(x)) };
                 self.orderbys.push(x);
             }
+            2 => {
+                let x = unsafe { datum.and_then(|x| self.opfamily.input_maxsim_threshold(x)) };
+                self.thresholds.push(x);
+            }
             _ => unreachable!(),
         }
     }
@@ -87,6 +97,8 @@ impl SearchBuilder for MaxsimBuilder {
         R::Page: Page<Opaque = vchordrq::Opaque>,
     {
         let mut vectors = None;
+        let mut threshold = None;
+        let mut recheck = false;
         for orderby_vectors in self.orderbys.into_iter().flatten() {
             if vectors.is_none() {
                 vectors = Some(orderby_vectors);
@@ -94,9 +106,29 @@ impl SearchBuilder for MaxsimBuilder {
                 pgrx::error!("maxsim search with multiple vectors is not supported");
             }
         }
+        for Sphere { center, radius } in self.thresholds.into_iter().flatten() {
+            // a document matches if its maxsim score is greater than the threshold,
+            // which cuts off the results only if it is about the searched vectors
+            match &vectors {
+                None => (vectors, threshold) = (Some(center), Some(-radius)),
+                Some(vectors) if is_same_vectors(vectors, &center) => {
+                    threshold = Some(threshold.map_or(-radius, |x: f32| x.min(-radius)));
+                }
+                Some(_) => (),
+            }
+            // maxsim scores are estimated, so the threshold is always rechecked
+            recheck = true;
+        }
         if let Some(_max_scan_tuples) = options.max_scan_tuples {
             pgrx::error!("maxsim search with max_scan_tuples is not supported");
         }
+        // `am_parallel` keeps parallel plans away from maxsim search
+        if options.parallel.is_some() {
+            pgrx::error!("maxsim search with parallel scan is not supported");
+        }
+        let Probes::Fixed(probes) = options.probes else {
+            pgrx::error!("maxsim search with auto probes is not supported");
+        };
         let maxsim_refine = options.maxsim_refine;
         let maxsim_threshold = options.maxsim_threshold;
         let opfamily = self.opfamily;
@@ -104,9 +136,6 @@ impl SearchBuilder for MaxsimBuilder {
             return Box::new(std::iter::empty()) as Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
         };
         let method = how(index);
-        if !matches!(method, RerankMethod::Index) {
-            pgrx::error!("maxsim search with rerank_in_table is not supported");
-        }
         assert!(matches!(opfamily.distance_kind(), DistanceKind::Dot));
         let search_hints = Hints::default().full(true);
         let rerank_hints = Hints::default().full(false);
@@ -141,12 +170,14 @@ impl SearchBuilder for MaxsimBuilder {
                     .iter()
                     .map(|vector| RandomProject::project(vector.as_borrowed()))
                     .collect::<Vec<_>>();
+                // the vectors of a document are decoded once for all query vectors
+                let mut cache = HashMap::new();
                 Box::new((0..n).map(move |i| {
-                    let (results, estimation_by_threshold) = match options.io_search {
+                    let (mut results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
                             projected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -156,7 +187,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
                             projected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -166,7 +197,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
                             projected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -176,9 +207,12 @@ impl SearchBuilder for MaxsimBuilder {
                     };
                     let (mut accu_set, mut rough_set) = (Vec::new(), Vec::new());
                     if maxsim_refine != 0 && !results.is_empty() {
+                        if let RerankMethod::Heap = method {
+                            prefetch_heap(&mut fetcher, &mut results, maxsim_refine as _);
+                        }
                         let sequence = Heap::from(results);
-                        match (options.io_rerank, options.prefilter) {
-                            (Io::Plain, false) => {
+                        match (method, options.io_rerank, options.prefilter) {
+                            (RerankMethod::Index, Io::Plain, false) => {
                                 let prefetcher = PlainPrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -187,7 +221,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Plain, true) => {
+                            (RerankMethod::Index, Io::Plain, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -205,7 +239,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, false) => {
+                            (RerankMethod::Index, Io::Simple, false) => {
                                 let prefetcher = SimplePrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -214,7 +248,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, true) => {
+                            (RerankMethod::Index, Io::Simple, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -232,7 +266,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, false) => {
+                            (RerankMethod::Index, Io::Stream, false) => {
                                 let prefetcher =
                                     StreamPrefetcher::new(index, sequence, rerank_hints);
                                 let mut reranker =
@@ -242,7 +276,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, true) => {
+                            (RerankMethod::Index, Io::Stream, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -261,6 +295,32 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
+                            (RerankMethod::Heap, _, _) => {
+                                let unpack = |datum| {
+                                    let maybe_vectors = unsafe { opfamily.input_vectors(datum) };
+                                    let vectors = maybe_vectors.unwrap_or_default();
+                                    vectors
+                                        .into_iter()
+                                        .map(|vector| {
+                                            if let OwnedVector::Vecf32(vector) = vector {
+                                                vector
+                                            } else {
+                                                unreachable!()
+                                            }
+                                        })
+                                        .collect()
+                                };
+                                (accu_set, rough_set) = rerank_heap_maxsim::<_, Op>(
+                                    index,
+                                    unprojected[i].clone(),
+                                    sequence,
+                                    &mut fetcher,
+                                    &mut cache,
+                                    options.prefilter,
+                                    maxsim_refine,
+                                    unpack,
+                                );
+                            }
                         }
                     } else {
                         let rough_iter = results.into_iter();
@@ -285,12 +345,14 @@ impl SearchBuilder for MaxsimBuilder {
                     .iter()
                     .map(|vector| RandomProject::project(vector.as_borrowed()))
                     .collect::<Vec<_>>();
+                // the vectors of a document are decoded once for all query vectors
+                let mut cache = HashMap::new();
                 Box::new((0..n).map(move |i| {
-                    let (results, estimation_by_threshold) = match options.io_search {
+                    let (mut results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
                             projected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -300,7 +362,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
                             projected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -310,7 +372,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
                             projected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -320,9 +382,12 @@ impl SearchBuilder for MaxsimBuilder {
                     };
                     let (mut accu_set, mut rough_set) = (Vec::new(), Vec::new());
                     if maxsim_refine != 0 && !results.is_empty() {
+                        if let RerankMethod::Heap = method {
+                            prefetch_heap(&mut fetcher, &mut results, maxsim_refine as _);
+                        }
                         let sequence = Heap::from(results);
-                        match (options.io_rerank, options.prefilter) {
-                            (Io::Plain, false) => {
+                        match (method, options.io_rerank, options.prefilter) {
+                            (RerankMethod::Index, Io::Plain, false) => {
                                 let prefetcher = PlainPrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -331,7 +396,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Plain, true) => {
+                            (RerankMethod::Index, Io::Plain, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -349,7 +414,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, false) => {
+                            (RerankMethod::Index, Io::Simple, false) => {
                                 let prefetcher = SimplePrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -358,7 +423,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, true) => {
+                            (RerankMethod::Index, Io::Simple, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -376,7 +441,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, false) => {
+                            (RerankMethod::Index, Io::Stream, false) => {
                                 let prefetcher =
                                     StreamPrefetcher::new(index, sequence, rerank_hints);
                                 let mut reranker =
@@ -386,7 +451,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, true) => {
+                            (RerankMethod::Index, Io::Stream, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -405,6 +470,32 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
+                            (RerankMethod::Heap, _, _) => {
+                                let unpack = |datum| {
+                                    let maybe_vectors = unsafe { opfamily.input_vectors(datum) };
+                                    let vectors = maybe_vectors.unwrap_or_default();
+                                    vectors
+                                        .into_iter()
+                                        .map(|vector| {
+                                            if let OwnedVector::Vecf16(vector) = vector {
+                                                vector
+                                            } else {
+                                                unreachable!()
+                                            }
+                                        })
+                                        .collect()
+                                };
+                                (accu_set, rough_set) = rerank_heap_maxsim::<_, Op>(
+                                    index,
+                                    unprojected[i].clone(),
+                                    sequence,
+                                    &mut fetcher,
+                                    &mut cache,
+                                    options.prefilter,
+                                    maxsim_refine,
+                                    unpack,
+                                );
+                            }
                         }
                     } else {
                         let rough_iter = results.into_iter();
@@ -425,12 +516,14 @@ impl SearchBuilder for MaxsimBuilder {
                         }
                     })
                     .collect::<Vec<_>>();
+                // the vectors of a document are decoded once for all query vectors
+                let mut cache = HashMap::new();
                 Box::new((0..n).map(move |i| {
-                    let (results, estimation_by_threshold) = match options.io_search {
+                    let (mut results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
                             unprojected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -440,7 +533,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
                             unprojected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -450,7 +543,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
                             unprojected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -460,9 +553,12 @@ impl SearchBuilder for MaxsimBuilder {
                     };
                     let (mut accu_set, mut rough_set) = (Vec::new(), Vec::new());
                     if maxsim_refine != 0 && !results.is_empty() {
+                        if let RerankMethod::Heap = method {
+                            prefetch_heap(&mut fetcher, &mut results, maxsim_refine as _);
+                        }
                         let sequence = Heap::from(results);
-                        match (options.io_rerank, options.prefilter) {
-                            (Io::Plain, false) => {
+                        match (method, options.io_rerank, options.prefilter) {
+                            (RerankMethod::Index, Io::Plain, false) => {
                                 let prefetcher = PlainPrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -471,7 +567,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Plain, true) => {
+                            (RerankMethod::Index, Io::Plain, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -489,7 +585,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, false) => {
+                            (RerankMethod::Index, Io::Simple, false) => {
                                 let prefetcher = SimplePrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -498,7 +594,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, true) => {
+                            (RerankMethod::Index, Io::Simple, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -516,7 +612,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, false) => {
+                            (RerankMethod::Index, Io::Stream, false) => {
                                 let prefetcher =
                                     StreamPrefetcher::new(index, sequence, rerank_hints);
                                 let mut reranker =
@@ -526,7 +622,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, true) => {
+                            (RerankMethod::Index, Io::Stream, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -545,6 +641,32 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
+                            (RerankMethod::Heap, _, _) => {
+                                let unpack = |datum| {
+                                    let maybe_vectors = unsafe { opfamily.input_vectors(datum) };
+                                    let vectors = maybe_vectors.unwrap_or_default();
+                                    vectors
+                                        .into_iter()
+                                        .map(|vector| {
+                                            if let OwnedVector::Rabitq8(vector) = vector {
+                                                vector
+                                            } else {
+                                                unreachable!()
+                                            }
+                                        })
+                                        .collect()
+                                };
+                                (accu_set, rough_set) = rerank_heap_maxsim::<_, Op>(
+                                    index,
+                                    unprojected[i].clone(),
+                                    sequence,
+                                    &mut fetcher,
+                                    &mut cache,
+                                    options.prefilter,
+                                    maxsim_refine,
+                                    unpack,
+                                );
+                            }
                         }
                     } else {
                         let rough_iter = results.into_iter();
@@ -565,12 +687,14 @@ impl SearchBuilder for MaxsimBuilder {
                         }
                     })
                     .collect::<Vec<_>>();
+                // the vectors of a document are decoded once for all query vectors
+                let mut cache = HashMap::new();
                 Box::new((0..n).map(move |i| {
-                    let (results, estimation_by_threshold) = match options.io_search {
+                    let (mut results, estimation_by_threshold) = match options.io_search {
                         Io::Plain => maxsim_search::<_, Op>(
                             index,
                             unprojected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -580,7 +704,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Simple => maxsim_search::<_, Op>(
                             index,
                             unprojected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -590,7 +714,7 @@ impl SearchBuilder for MaxsimBuilder {
                         Io::Stream => maxsim_search::<_, Op>(
                             index,
                             unprojected[i].as_borrowed(),
-                            options.probes.clone(),
+                            probes.clone(),
                             options.epsilon,
                             maxsim_threshold,
                             bump,
@@ -600,9 +724,12 @@ impl SearchBuilder for MaxsimBuilder {
                     };
                     let (mut accu_set, mut rough_set) = (Vec::new(), Vec::new());
                     if maxsim_refine != 0 && !results.is_empty() {
+                        if let RerankMethod::Heap = method {
+                            prefetch_heap(&mut fetcher, &mut results, maxsim_refine as _);
+                        }
                         let sequence = Heap::from(results);
-                        match (options.io_rerank, options.prefilter) {
-                            (Io::Plain, false) => {
+                        match (method, options.io_rerank, options.prefilter) {
+                            (RerankMethod::Index, Io::Plain, false) => {
                                 let prefetcher = PlainPrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -611,7 +738,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Plain, true) => {
+                            (RerankMethod::Index, Io::Plain, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -629,7 +756,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, false) => {
+                            (RerankMethod::Index, Io::Simple, false) => {
                                 let prefetcher = SimplePrefetcher::new(index, sequence);
                                 let mut reranker =
                                     rerank_index::<Op, _, _, _>(unprojected[i].clone(), prefetcher);
@@ -638,7 +765,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Simple, true) => {
+                            (RerankMethod::Index, Io::Simple, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -656,7 +783,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, false) => {
+                            (RerankMethod::Index, Io::Stream, false) => {
                                 let prefetcher =
                                     StreamPrefetcher::new(index, sequence, rerank_hints);
                                 let mut reranker =
@@ -666,7 +793,7 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
-                            (Io::Stream, true) => {
+                            (RerankMethod::Index, Io::Stream, true) => {
                                 let predicate =
                                     id_0(|(_, AlwaysEqual(PackedRefMut8((pointer, _, _))))| {
                                         let (key, _) = pointer_to_kv(*pointer);
@@ -685,6 +812,32 @@ impl SearchBuilder for MaxsimBuilder {
                                 accu_set.extend(accu_iter.map(accu_map));
                                 rough_set.extend(rough_iter.into_iter().map(rough_map));
                             }
+                            (RerankMethod::Heap, _, _) => {
+                                let unpack = |datum| {
+                                    let maybe_vectors = unsafe { opfamily.input_vectors(datum) };
+                                    let vectors = maybe_vectors.unwrap_or_default();
+                                    vectors
+                                        .into_iter()
+                                        .map(|vector| {
+                                            if let OwnedVector::Rabitq4(vector) = vector {
+                                                vector
+                                            } else {
+                                                unreachable!()
+                                            }
+                                        })
+                                        .collect()
+                                };
+                                (accu_set, rough_set) = rerank_heap_maxsim::<_, Op>(
+                                    index,
+                                    unprojected[i].clone(),
+                                    sequence,
+                                    &mut fetcher,
+                                    &mut cache,
+                                    options.prefilter,
+                                    maxsim_refine,
+                                    unpack,
+                                );
+                            }
                         }
                     } else {
                         let rough_iter = results.into_iter();
@@ -693,6 +846,7 @@ impl SearchBuilder for MaxsimBuilder {
                     (accu_set, rough_set, estimation_by_threshold)
                 }))
             }
+            VectorKind::Svecf32 | VectorKind::Bit => unreachable!(),
         };
         let mut updates = Vec::new();
         let mut estimations = Vec::new();
@@ -734,11 +888,13 @@ impl SearchBuilder for MaxsimBuilder {
             })
             .collect::<BinaryHeap<_>>()
             .into_iter_sorted_polyfill()
-            .map(|(Reverse(distance), AlwaysEqual(key))| {
-                let distance = distance.to_f32();
-                let recheck = false;
-                (distance, key, recheck)
-            });
+            .map(|(Reverse(distance), AlwaysEqual(key))| (distance.to_f32(), key));
+        let iter: Box<dyn Iterator<Item = _>> = if let Some(threshold) = threshold {
+            Box::new(iter.take_while(move |(x, _)| *x < threshold))
+        } else {
+            Box::new(iter)
+        };
+        let iter = iter.map(move |(distance, key)| (distance, key, recheck));
         let iter: Box<dyn Iterator<Item = _>> = Box::new(iter);
         let iter = if let Some(max_scan_tuples) = options.max_scan_tuples {
             Box::new(iter.take(max_scan_tuples as _))
@@ -750,6 +906,107 @@ impl SearchBuilder for MaxsimBuilder {
     }
 }
 
+fn is_same_vectors(lhs: &[OwnedVector], rhs: &[OwnedVector]) -> bool {
+    lhs.len() == rhs.len()
+        && std::iter::zip(lhs, rhs).all(|pair| match pair {
+            (OwnedVector::Vecf32(lhs), OwnedVector::Vecf32(rhs)) => lhs.slice() == rhs.slice(),
+            (OwnedVector::Vecf16(lhs), OwnedVector::Vecf16(rhs)) => lhs.slice() == rhs.slice(),
+            (OwnedVector::Rabitq8(lhs), OwnedVector::Rabitq8(rhs)) => {
+                let (lhs, rhs) = (lhs.as_borrowed(), rhs.as_borrowed());
+                lhs.dim() == rhs.dim()
+                    && lhs.sum_of_x2() == rhs.sum_of_x2()
+                    && lhs.norm_of_lattice() == rhs.norm_of_lattice()
+                    && lhs.sum_of_code() == rhs.sum_of_code()
+                    && lhs.sum_of_abs_x() == rhs.sum_of_abs_x()
+                    && lhs.packed_code() == rhs.packed_code()
+            }
+            (OwnedVector::Rabitq4(lhs), OwnedVector::Rabitq4(rhs)) => {
+                let (lhs, rhs) = (lhs.as_borrowed(), rhs.as_borrowed());
+                lhs.dim() == rhs.dim()
+                    && lhs.sum_of_x2() == rhs.sum_of_x2()
+                    && lhs.norm_of_lattice() == rhs.norm_of_lattice()
+                    && lhs.sum_of_code() == rhs.sum_of_code()
+                    && lhs.sum_of_abs_x() == rhs.sum_of_abs_x()
+                    && lhs.packed_code() == rhs.packed_code()
+            }
+            _ => false,
+        })
+}
+
+type MaxsimCandidate<'b> = (
+    (Reverse<Distance>, AlwaysEqual<Distance>),
+    AlwaysEqual<PackedRefMut8<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
+);
+
+/// Reranks the candidates of a query vector by the vectors in the table, where
+/// `unpack` decodes all vectors of a document. They are kept in `cache` by the
+/// keys of documents, so a document is fetched and decoded once for all query
+/// vectors. The first `maxsim_refine` candidates are reranked. Returns the
+/// reranked distances and the estimated distances of the remaining candidates.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn rerank_heap_maxsim<'b, R, O>(
+    index: &'b R,
+    vector: O::Vector,
+    sequence: Heap<MaxsimCandidate<'b>>,
+    fetcher: &mut impl Fetcher,
+    cache: &mut HashMap<[u16; 3], Option<Vec<O::Vector>>>,
+    prefilter: bool,
+    maxsim_refine: u32,
+    unpack: impl Fn(Datum) -> Vec<O::Vector>,
+) -> (Vec<(Distance, NonZero<u64>)>, Vec<(Distance, NonZero<u64>)>)
+where
+    R: RelationRead,
+    O: Operator,
+{
+    let fetch = |payload| {
+        let (key, extra) = pointer_to_kv(payload);
+        let vectors = cache.entry(key).or_insert_with(|| {
+            let mut tuple = fetcher.fetch(key)?;
+            if prefilter && !tuple.filter() {
+                return None;
+            }
+            let (datums, is_nulls) = tuple.build();
+            let datum = (!is_nulls[0]).then_some(datums[0])?;
+            Some(unpack(datum))
+        });
+        let Some(vector) = vectors.as_ref()?.get(extra as usize) else {
+            pgrx::error!("data corruption")
+        };
+        Some(vector.clone())
+    };
+    let prefetcher = PlainPrefetcher::new(index, sequence);
+    let mut reranker = rerank_heap::<O, _, _, _>(vector, prefetcher, fetch);
+    let mut accu_set = reranker
+        .by_ref()
+        .take(maxsim_refine as _)
+        .collect::<Vec<_>>();
+    let (rough_iter, accu_iter) = reranker.finish();
+    accu_set.extend(accu_iter.map(|(Reverse(distance), AlwaysEqual(payload))| (distance, payload)));
+    let rough_set = rough_iter
+        .into_iter()
+        .map(
+            |((_, AlwaysEqual(rough)), AlwaysEqual(PackedRefMut8(&mut (payload, ..))))| {
+                (rough, payload)
+            },
+        )
+        .collect();
+    (accu_set, rough_set)
+}
+
+// the candidates with the best estimated distances are reranked first, so
+// their heap pages are prefetched
+fn prefetch_heap(fetcher: &mut impl Fetcher, results: &mut [MaxsimCandidate<'_>], n: usize) {
+    let n = n.min(results.len());
+    if n == 0 {
+        return;
+    }
+    results.select_nth_unstable_by(n - 1, |x, y| y.cmp(x));
+    for (_, AlwaysEqual(PackedRefMut8((pointer, _, _)))) in results[..n].iter() {
+        let (key, _) = pointer_to_kv(*pointer);
+        fetcher.prefetch(key);
+    }
+}
+
 // Emulate unstable library feature `binary_heap_into_iter_sorted`.
 // See https://github.com/rust-lang/rust/issues/59278.
 
@@ -14,20 +14,318 @@
 
 mod default;
 mod maxsim;
+mod multi_query;
 
+use crate::index::fetcher::*;
 use crate::index::scanners::Io;
+use crate::index::vchordrq::dispatch::*;
+use crate::index::vchordrq::filter::filter;
+use always_equal::AlwaysEqual;
+use dary_heap::QuaternaryHeap as Heap;
+use distance::Distance;
+use index::bump::Bump;
+use index::fetch::BorrowedIter;
+use index::packed::PackedRefMut4;
+use index::prefetcher::*;
+use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
+use pgrx::pg_sys::Datum;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::num::NonZero;
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use vchordrq::operator::Operator;
+use vchordrq::{AutoProbes, Probes, RerankMethod, default_search, how, rerank_heap, rerank_index};
+use vector::VectorOwned;
 
 pub use default::DefaultBuilder;
 pub use maxsim::MaxsimBuilder;
+pub use multi_query::MultiQueryBuilder;
 
 #[derive(Debug)]
 pub struct SearchOptions {
     pub epsilon: f32,
-    pub probes: Vec<u32>,
+    pub probes: Probes,
     pub max_scan_tuples: Option<u32>,
     pub maxsim_refine: u32,
     pub maxsim_threshold: u32,
     pub io_search: Io,
     pub io_rerank: Io,
     pub prefilter: bool,
+    pub iterative_scan: bool,
+    pub limit: Option<u32>,
+    pub parallel: Option<NonNull<AtomicU32>>,
+}
+
+impl SearchOptions {
+    /// Sets the number of wanted rows, if it is known. Auto probes stop early
+    /// only if it is known and no rows are filtered out by a `WHERE` clause,
+    /// since the filtered rows still count towards the nearest ones. Otherwise
+    /// `vchordrq.auto_probes_max` lists are probed, as its description says.
+    pub fn with_limit(mut self, limit: Option<i64>, filtered: bool) -> Self {
+        self.limit = limit.map(|limit| limit.clamp(0, u32::MAX as i64) as u32);
+        if let Probes::Auto(AutoProbes { k, .. }) = &mut self.probes {
+            *k = self.limit.filter(|_| !filtered && !self.prefilter);
+        }
+        self
+    }
+}
+
+// the number of tuples scanned by index scans in this backend
+static SCANNED_TUPLES: AtomicU64 = AtomicU64::new(0);
+
+pub fn scanned_tuples() -> u64 {
+    SCANNED_TUPLES.load(Ordering::Relaxed)
+}
+
+fn count_scanned_tuples(n: usize) {
+    SCANNED_TUPLES.fetch_add(n as u64, Ordering::Relaxed);
+}
+
+type Candidate<'b> = (
+    (Reverse<Distance>, AlwaysEqual<()>),
+    AlwaysEqual<PackedRefMut4<'b, (NonZero<u64>, u16, BorrowedIter<'b>)>>,
+);
+
+/// Candidates found by `search`, in order of their estimated distances. With
+/// iterative scan, once all of them are taken, the lists that are not probed
+/// yet are searched with twice the probes, until all lists are probed or
+/// `max_scan_tuples` candidates are scanned. So lists are only opened if rows
+/// are still wanted, and candidates found later may be nearer than the ones
+/// taken before them, which makes the rows returned in a relaxed order.
+struct Candidates<'b, F> {
+    heap: Heap<Candidate<'b>>,
+    search: F,
+    probed: HashSet<u32>,
+    // lists are claimed by participants one by one, so it is disabled for
+    // parallel scans
+    widening: Option<(Vec<u32>, Vec<u32>)>,
+    scanned: usize,
+    max_scan_tuples: Option<u32>,
+}
+
+impl<'b, F> Candidates<'b, F>
+where
+    F: FnMut(Probes, &mut HashSet<u32>) -> Vec<Candidate<'b>>,
+{
+    fn widen(&mut self) {
+        let Some((cells, probes)) = self.widening.as_mut() else {
+            return;
+        };
+        while self.heap.is_empty() {
+            // `cells` is bottom-up and ends with the root, while `probes` is top-down
+            let limits = cells.iter().rev().skip(1);
+            if probes.iter().zip(limits).all(|(p, n)| p >= n) {
+                return;
+            }
+            if let Some(max_scan_tuples) = self.max_scan_tuples
+                && self.scanned >= max_scan_tuples as usize
+            {
+                return;
+            }
+            let limits = cells.iter().rev().skip(1);
+            for (p, n) in probes.iter_mut().zip(limits) {
+                *p = (*p).max(1).saturating_mul(2).min(*n);
+            }
+            let results = (self.search)(Probes::Fixed(probes.clone()), &mut self.probed);
+            count_scanned_tuples(results.len());
+            self.scanned += results.len();
+            self.heap.extend(results);
+        }
+    }
+}
+
+impl<'b, F> Sequence for Candidates<'b, F>
+where
+    F: FnMut(Probes, &mut HashSet<u32>) -> Vec<Candidate<'b>>,
+{
+    type Item = Candidate<'b>;
+    type Inner = std::vec::IntoIter<Candidate<'b>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.widen();
+        self.heap.pop()
+    }
+    fn peek(&mut self) -> Option<&Self::Item> {
+        self.widen();
+        self.heap.peek()
+    }
+    fn into_inner(self) -> Self::Inner {
+        self.heap.into_vec().into_iter()
+    }
+}
+
+fn candidates<'b, R, F>(index: &'b R, options: &SearchOptions, mut search: F) -> Candidates<'b, F>
+where
+    R: RelationRead,
+    F: FnMut(Probes, &mut HashSet<u32>) -> Vec<Candidate<'b>>,
+{
+    let mut probed = HashSet::new();
+    let results = search(options.probes.clone(), &mut probed);
+    count_scanned_tuples(results.len());
+    let scanned = results.len();
+    let widening = (options.iterative_scan && options.parallel.is_none()).then(|| {
+        let cells = vchordrq::cost(index).cells;
+        let probes = options.probes.resolve(&cells);
+        (cells, probes)
+    });
+    Candidates {
+        heap: Heap::from(results),
+        search,
+        probed,
+        widening,
+        scanned,
+        max_scan_tuples: options.max_scan_tuples,
+    }
+}
+
+/// Returns a closure that searches `vector` with the given probes, using the
+/// prefetchers selected by `io_search`.
+fn search<'b, R, O>(
+    index: &'b R,
+    vector: O::Vector,
+    options: &SearchOptions,
+    bump: &'b impl Bump,
+) -> impl FnMut(Probes, &mut HashSet<u32>) -> Vec<Candidate<'b>> + 'b
+where
+    R: RelationRead + RelationPrefetch + RelationReadStream,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+    O: Operator,
+{
+    let (io_search, epsilon) = (options.io_search, options.epsilon);
+    // the shared state lives as long as the scan
+    let parallel = options.parallel.map(|x| unsafe { x.as_ref() });
+    let make_h1_plain_prefetcher = MakeH1PlainPrefetcher { index };
+    let make_h0_plain_prefetcher = MakeH0PlainPrefetcher { index };
+    let make_h0_simple_prefetcher = MakeH0SimplePrefetcher { index };
+    let make_h0_stream_prefetcher = MakeH0StreamPrefetcher {
+        index,
+        hints: Hints::default().full(true),
+    };
+    move |probes, probed| match io_search {
+        Io::Plain => default_search::<_, O>(
+            index,
+            vector.as_borrowed(),
+            probes,
+            epsilon,
+            parallel,
+            probed,
+            bump,
+            make_h1_plain_prefetcher.clone(),
+            make_h0_plain_prefetcher.clone(),
+        ),
+        Io::Simple => default_search::<_, O>(
+            index,
+            vector.as_borrowed(),
+            probes,
+            epsilon,
+            parallel,
+            probed,
+            bump,
+            make_h1_plain_prefetcher.clone(),
+            make_h0_simple_prefetcher.clone(),
+        ),
+        Io::Stream => default_search::<_, O>(
+            index,
+            vector.as_borrowed(),
+            probes,
+            epsilon,
+            parallel,
+            probed,
+            bump,
+            make_h1_plain_prefetcher.clone(),
+            make_h0_stream_prefetcher.clone(),
+        ),
+    }
+}
+
+/// Reranks the candidates by their distances to `vector`. The vectors are
+/// read from the index, or from the table with `unpack` if the index is built
+/// with `rerank_in_table`.
+fn rerank<'b, R, O>(
+    index: &'b R,
+    options: &SearchOptions,
+    vector: O::Vector,
+    sequence: impl Sequence<Item = Candidate<'b>> + 'b,
+    mut fetcher: impl Fetcher + 'b,
+    unpack: impl Fn(Datum) -> O::Vector + 'b,
+) -> Box<dyn Iterator<Item = (Distance, NonZero<u64>)> + 'b>
+where
+    R: RelationRead + RelationPrefetch + RelationReadStream,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+    O: Operator,
+{
+    let rerank_hints = Hints::default().full(false);
+    let prefilter = options.prefilter;
+    match (how(index), options.io_rerank, prefilter) {
+        (RerankMethod::Index, Io::Plain, false) => {
+            let prefetcher = PlainPrefetcher::new(index, sequence);
+            Box::new(rerank_index::<O, _, _, _>(vector, prefetcher))
+        }
+        (RerankMethod::Index, Io::Plain, true) => {
+            let predicate = id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                let (key, _) = pointer_to_kv(*pointer);
+                let Some(mut tuple) = fetcher.fetch(key) else {
+                    return false;
+                };
+                tuple.filter()
+            });
+            let sequence = filter(sequence, predicate);
+            let prefetcher = PlainPrefetcher::new(index, sequence);
+            Box::new(rerank_index::<O, _, _, _>(vector, prefetcher))
+        }
+        (RerankMethod::Index, Io::Simple, false) => {
+            let prefetcher = SimplePrefetcher::new(index, sequence);
+            Box::new(rerank_index::<O, _, _, _>(vector, prefetcher))
+        }
+        (RerankMethod::Index, Io::Simple, true) => {
+            let predicate = id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                let (key, _) = pointer_to_kv(*pointer);
+                let Some(mut tuple) = fetcher.fetch(key) else {
+                    return false;
+                };
+                tuple.filter()
+            });
+            let sequence = filter(sequence, predicate);
+            let prefetcher = SimplePrefetcher::new(index, sequence);
+            Box::new(rerank_index::<O, _, _, _>(vector, prefetcher))
+        }
+        (RerankMethod::Index, Io::Stream, false) => {
+            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+            Box::new(rerank_index::<O, _, _, _>(vector, prefetcher))
+        }
+        (RerankMethod::Index, Io::Stream, true) => {
+            let predicate = id_0(move |(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| {
+                let (key, _) = pointer_to_kv(*pointer);
+                let Some(mut tuple) = fetcher.fetch(key) else {
+                    return false;
+                };
+                tuple.filter()
+            });
+            let sequence = filter(sequence, predicate);
+            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+            Box::new(rerank_index::<O, _, _, _>(vector, prefetcher))
+        }
+        (RerankMethod::Heap, _, _) => {
+            let fetch = move |payload| {
+                let (key, _) = pointer_to_kv(payload);
+                let mut tuple = fetcher.fetch(key)?;
+                if prefilter && !tuple.filter() {
+                    return None;
+                }
+                let (datums, is_nulls) = tuple.build();
+                let datum = (!is_nulls[0]).then_some(datums[0])?;
+                Some(unpack(datum))
+            };
+            let prefetcher = PlainPrefetcher::new(index, sequence);
+            Box::new(rerank_heap::<O, _, _, _>(vector, prefetcher, fetch))
+        }
+    }
+}
+
+#[inline(always)]
+fn id_0<F, A: ?Sized, B: ?Sized, C: ?Sized, D: ?Sized, R: ?Sized>(f: F) -> F
+where
+    F: for<'a> FnMut(&(A, AlwaysEqual<PackedRefMut4<'a, (B, C, D)>>)) -> R,
+{
+    f
 }
@@ -0,0 +1,371 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::index::fetcher::*;
+use crate::index::scanners::{Io, SearchBuilder};
+use crate::index::vchordrq::dispatch::*;
+use crate::index::vchordrq::opclass::Opfamily;
+use crate::index::vchordrq::scanners::{Candidate, SearchOptions, count_scanned_tuples, search};
+use crate::recorder::Recorder;
+use always_equal::AlwaysEqual;
+use dary_heap::QuaternaryHeap as Heap;
+use distance::Distance;
+use index::bump::Bump;
+use index::packed::PackedRefMut4;
+use index::prefetcher::*;
+use index::relation::{Hints, Page, RelationPrefetch, RelationRead, RelationReadStream};
+use index_accessor::{Dot, L2S};
+use pgrx::pg_sys::Datum;
+use simd::f16;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::num::NonZero;
+use vchordrq::operator::Operator;
+use vchordrq::types::{DistanceKind, OwnedVector, VectorKind};
+use vchordrq::{RerankMethod, how, rerank_heap_multi, rerank_index_multi};
+use vector::VectorOwned;
+use vector::vect::VectOwned;
+
+pub struct MultiQueryBuilder {
+    opfamily: Opfamily,
+    orderbys: Vec<Option<Vec<(OwnedVector, f32)>>>,
+    recheck: bool,
+}
+
+impl SearchBuilder for MultiQueryBuilder {
+    type Options = SearchOptions;
+
+    type Opfamily = Opfamily;
+
+    type Opaque = vchordrq::Opaque;
+
+    fn new(opfamily: Opfamily) -> Self {
+        assert!(matches!(
+            opfamily,
+            Opfamily::VectorL2
+                | Opfamily::VectorIp
+                | Opfamily::VectorCosine
+                | Opfamily::HalfvecL2
+                | Opfamily::HalfvecIp
+                | Opfamily::HalfvecCosine
+        ));
+        Self {
+            opfamily,
+            orderbys: Vec::new(),
+            recheck: false,
+        }
+    }
+
+    unsafe fn add(&mut self, strategy: u16, datum: Option<pgrx::pg_sys::Datum>) {
+        match strategy {
+            1 => {
+                pgrx::error!("multi-query search with another vector is not supported");
+            }
+            2 => {
+                // spheres are left to the executor
+                self.recheck = true;
+            }
+            4 => {
+                let x = unsafe { datum.and_then(|x| self.opfamily.input_multi_query(x)) };
+                self.orderbys.push(x);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn build<'b, R>(
+        self,
+        index: &'b R,
+        options: SearchOptions,
+        fetcher: impl Fetcher + 'b,
+        bump: &'b impl Bump,
+        _sender: impl Recorder,
+    ) -> Box<dyn Iterator<Item = (f32, [u16; 3], bool)> + 'b>
+    where
+        R: RelationRead + RelationPrefetch + RelationReadStream,
+        R::Page: Page<Opaque = vchordrq::Opaque>,
+    {
+        let mut queries = None;
+        for orderby_queries in self.orderbys.into_iter().flatten() {
+            if queries.is_none() {
+                queries = Some(orderby_queries);
+            } else {
+                pgrx::error!("multi-query search with multiple multi-queries is not supported");
+            }
+        }
+        // `am_parallel` keeps parallel plans away from multi-query search
+        if options.parallel.is_some() {
+            pgrx::error!("multi-query search with parallel scan is not supported");
+        }
+        let opfamily = self.opfamily;
+        let recheck = self.recheck;
+        let Some(queries) = queries.filter(|queries| !queries.is_empty()) else {
+            return Box::new(std::iter::empty()) as Box<dyn Iterator<Item = (f32, [u16; 3], bool)>>;
+        };
+        let (vectors, weights): (Vec<_>, Vec<_>) = queries.into_iter().unzip();
+        let combine = {
+            let weights = weights.clone();
+            move |distances: Vec<Distance>| {
+                let mut distance = 0.0f32;
+                for (d, weight) in distances.into_iter().zip(weights.iter()) {
+                    distance += weight * opfamily.output(d);
+                }
+                Distance::from_f32(distance)
+            }
+        };
+        let iter: Box<dyn Iterator<Item = (Distance, NonZero<u64>)>> =
+            match (opfamily.vector_kind(), opfamily.distance_kind()) {
+                (VectorKind::Vecf32, DistanceKind::L2S) => {
+                    type Op = vchordrq::operator::Op<VectOwned<f32>, L2S>;
+                    let unprojected = vectors
+                        .into_iter()
+                        .map(|vector| {
+                            if let OwnedVector::Vecf32(vector) = vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let mut results = Vec::new();
+                    let mut seen = HashSet::new();
+                    for vector in probed_around(&unprojected, &weights) {
+                        let projected = RandomProject::project(vector.as_borrowed());
+                        let mut search = search::<_, Op>(index, projected, &options, bump);
+                        let probes = options.probes.clone();
+                        results.extend(unordered(search(probes, &mut HashSet::new()), &mut seen));
+                    }
+                    let sequence = Heap::from(results);
+                    rerank_multi::<_, Op>(
+                        index,
+                        &options,
+                        unprojected,
+                        combine,
+                        sequence,
+                        fetcher,
+                        move |x| {
+                            let maybe_vector = unsafe { opfamily.input_vector(x) };
+                            if let Some(OwnedVector::Vecf32(vector)) = maybe_vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        },
+                    )
+                }
+                (VectorKind::Vecf32, DistanceKind::Dot) => {
+                    type Op = vchordrq::operator::Op<VectOwned<f32>, Dot>;
+                    let unprojected = vectors
+                        .into_iter()
+                        .map(|vector| {
+                            if let OwnedVector::Vecf32(vector) = vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let mut results = Vec::new();
+                    let mut seen = HashSet::new();
+                    for vector in probed_around(&unprojected, &weights) {
+                        let projected = RandomProject::project(vector.as_borrowed());
+                        let mut search = search::<_, Op>(index, projected, &options, bump);
+                        let probes = options.probes.clone();
+                        results.extend(unordered(search(probes, &mut HashSet::new()), &mut seen));
+                    }
+                    let sequence = Heap::from(results);
+                    rerank_multi::<_, Op>(
+                        index,
+                        &options,
+                        unprojected,
+                        combine,
+                        sequence,
+                        fetcher,
+                        move |x| {
+                            let maybe_vector = unsafe { opfamily.input_vector(x) };
+                            if let Some(OwnedVector::Vecf32(vector)) = maybe_vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        },
+                    )
+                }
+                (VectorKind::Vecf16, DistanceKind::L2S) => {
+                    type Op = vchordrq::operator::Op<VectOwned<f16>, L2S>;
+                    let unprojected = vectors
+                        .into_iter()
+                        .map(|vector| {
+                            if let OwnedVector::Vecf16(vector) = vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let mut results = Vec::new();
+                    let mut seen = HashSet::new();
+                    for vector in probed_around(&unprojected, &weights) {
+                        let projected = RandomProject::project(vector.as_borrowed());
+                        let mut search = search::<_, Op>(index, projected, &options, bump);
+                        let probes = options.probes.clone();
+                        results.extend(unordered(search(probes, &mut HashSet::new()), &mut seen));
+                    }
+                    let sequence = Heap::from(results);
+                    rerank_multi::<_, Op>(
+                        index,
+                        &options,
+                        unprojected,
+                        combine,
+                        sequence,
+                        fetcher,
+                        move |x| {
+                            let maybe_vector = unsafe { opfamily.input_vector(x) };
+                            if let Some(OwnedVector::Vecf16(vector)) = maybe_vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        },
+                    )
+                }
+                (VectorKind::Vecf16, DistanceKind::Dot) => {
+                    type Op = vchordrq::operator::Op<VectOwned<f16>, Dot>;
+                    let unprojected = vectors
+                        .into_iter()
+                        .map(|vector| {
+                            if let OwnedVector::Vecf16(vector) = vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        })
+                        .collect::<Vec<_>>();
+                    let mut results = Vec::new();
+                    let mut seen = HashSet::new();
+                    for vector in probed_around(&unprojected, &weights) {
+                        let projected = RandomProject::project(vector.as_borrowed());
+                        let mut search = search::<_, Op>(index, projected, &options, bump);
+                        let probes = options.probes.clone();
+                        results.extend(unordered(search(probes, &mut HashSet::new()), &mut seen));
+                    }
+                    let sequence = Heap::from(results);
+                    rerank_multi::<_, Op>(
+                        index,
+                        &options,
+                        unprojected,
+                        combine,
+                        sequence,
+                        fetcher,
+                        move |x| {
+                            let maybe_vector = unsafe { opfamily.input_vector(x) };
+                            if let Some(OwnedVector::Vecf16(vector)) = maybe_vector {
+                                vector
+                            } else {
+                                unreachable!()
+                            }
+                        },
+                    )
+                }
+                _ => unreachable!(),
+            };
+        let iter = if let Some(max_scan_tuples) = options.max_scan_tuples {
+            Box::new(iter.take(max_scan_tuples as _))
+        } else {
+            iter
+        };
+        Box::new(iter.map(move |(distance, pointer)| {
+            let (key, _) = pointer_to_kv(pointer);
+            (distance.to_f32(), key, recheck)
+        }))
+    }
+}
+
+// candidates of all queries are reranked by the weighted sum, so every one of
+// them is read before the first is returned
+fn unordered<'b>(
+    results: Vec<Candidate<'b>>,
+    seen: &mut HashSet<NonZero<u64>>,
+) -> Vec<Candidate<'b>> {
+    count_scanned_tuples(results.len());
+    results
+        .into_iter()
+        .filter(|(_, AlwaysEqual(PackedRefMut4((pointer, _, _))))| seen.insert(*pointer))
+        .map(|((_, x), y)| ((Reverse(Distance::NEG_INFINITY), x), y))
+        .collect()
+}
+
+/// Returns the queries around which lists are probed. Documents that are near
+/// the queries with positive weights get the best weighted sums, while those
+/// with other weights push them away, so only the former are probed around.
+/// If no weight is positive, all queries are probed around, and the results
+/// are only found among their neighbors.
+fn probed_around<'a, V>(vectors: &'a [V], weights: &'a [f32]) -> impl Iterator<Item = &'a V> {
+    let positive = weights.iter().any(|&weight| weight > 0.0);
+    std::iter::zip(vectors, weights)
+        .filter(move |&(_, &weight)| !positive || weight > 0.0)
+        .map(|(vector, _)| vector)
+}
+
+/// Reranks the candidates by the weighted sum of their distances to `vectors`,
+/// like `rerank` does for a single vector.
+fn rerank_multi<'b, R, O>(
+    index: &'b R,
+    options: &SearchOptions,
+    vectors: Vec<O::Vector>,
+    combine: impl FnMut(Vec<Distance>) -> Distance + 'b,
+    sequence: impl Sequence<Item = Candidate<'b>> + 'b,
+    mut fetcher: impl Fetcher + 'b,
+    unpack: impl Fn(Datum) -> O::Vector + 'b,
+) -> Box<dyn Iterator<Item = (Distance, NonZero<u64>)> + 'b>
+where
+    R: RelationRead + RelationPrefetch + RelationReadStream,
+    R::Page: Page<Opaque = vchordrq::Opaque>,
+    O: Operator,
+{
+    let rerank_hints = Hints::default().full(false);
+    match (how(index), options.io_rerank) {
+        (RerankMethod::Index, Io::Plain) => {
+            let prefetcher = PlainPrefetcher::new(index, sequence);
+            Box::new(rerank_index_multi::<O, _, _, _>(
+                vectors, combine, prefetcher,
+            ))
+        }
+        (RerankMethod::Index, Io::Simple) => {
+            let prefetcher = SimplePrefetcher::new(index, sequence);
+            Box::new(rerank_index_multi::<O, _, _, _>(
+                vectors, combine, prefetcher,
+            ))
+        }
+        (RerankMethod::Index, Io::Stream) => {
+            let prefetcher = StreamPrefetcher::new(index, sequence, rerank_hints);
+            Box::new(rerank_index_multi::<O, _, _, _>(
+                vectors, combine, prefetcher,
+            ))
+        }
+        (RerankMethod::Heap, _) => {
+            let fetch = move |payload| {
+                let (key, _) = pointer_to_kv(payload);
+                let mut tuple = fetcher.fetch(key)?;
+                let (datums, is_nulls) = tuple.build();
+                let datum = (!is_nulls[0]).then_some(datums[0])?;
+                Some(unpack(datum))
+            };
+            let prefetcher = PlainPrefetcher::new(index, sequence);
+            Box::new(rerank_heap_multi::<O, _, _, _>(
+                vectors, combine, prefetcher, fetch,
+            ))
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// This software is licensed under a dual license model:
+//
+// GNU Affero General Public License v3 (AGPLv3): You may use, modify, and
+// distribute this software under the terms of the AGPLv3.
+//
+// Elastic License v2 (ELv2): You may also use, modify, and distribute this
+// software under the Elastic License v2, which has specific restrictions.
+//
+// We welcome any commercial collaboration or support. For inquiries
+// regarding the licenses, please contact us at:
+// vectorchord-inquiry@tensorchord.ai
+//
+// Copyright (c) 2025-2026 TensorChord Inc.
+
+use crate::index::fetcher::*;
+use crate::index::traverse::{HeapTraverser, Traverser};
+use pgrx::pg_sys::Datum;
+use std::collections::HashMap;
+use std::num::NonZero;
+
+pub struct Problem {
+    pub page: Option<u32>,
+    pub message: String,
+}
+
+/// Checks an index with `verify`, which is given the number of pages of the
+/// index and passes the payload of every index entry to the callback. With
+/// `heapallindexed`, every vector of the heap is also checked to be indexed
+/// exactly once, where `extras` returns the extras of the vectors of a value.
+pub unsafe fn verify(
+    heap_relation: pgrx::pg_sys::Relation,
+    index_relation: pgrx::pg_sys::Relation,
+    heapallindexed: bool,
+    verify: impl FnOnce(u32, &mut dyn FnMut(NonZero<u64>)) -> Vec<Problem>,
+    extras: impl Fn(Datum) -> Vec<u16>,
+) -> Vec<Problem> {
+    // taken before the index is read, so that heap tuples in the snapshot are
+    // indexed even if insertions go on
+    let snapshot = heapallindexed
+        .then(|| unsafe { pgrx::pg_sys::RegisterSnapshot(pgrx::pg_sys::GetTransactionSnapshot()) });
+    let number_of_pages = unsafe {
+        pgrx::pg_sys::RelationGetNumberOfBlocksInFork(
+            index_relation,
+            pgrx::pg_sys::ForkNumber::MAIN_FORKNUM,
+        )
+    };
+    let mut entries = HashMap::<NonZero<u64>, u32>::new();
+    let mut problems = verify(number_of_pages, &mut |payload| {
+        if heapallindexed {
+            *entries.entry(payload).or_default() += 1;
+        }
+    });
+    if let Some(snapshot) = snapshot {
+        let traverser = unsafe {
+            HeapTraverser::with_snapshot(
+                heap_relation,
+                index_relation,
+                pgrx::pg_sys::BuildIndexInfo(index_relation),
+                snapshot,
+            )
+        };
+        traverser.traverse(false, |tuple: &mut dyn crate::index::traverse::Tuple| {
+            let ctid = tuple.id();
+            let (values, is_nulls) = tuple.build();
+            let value = unsafe { (!is_nulls.add(0).read()).then_some(values.add(0).read()) };
+            let extras = value.map(&extras).unwrap_or_default();
+            let multiple = extras.len() > 1;
+            for extra in extras {
+                let key = ctid_to_key(ctid);
+                let payload = kv_to_pointer((key, extra));
+                let [bi_hi, bi_lo, ip_posid] = key;
+                let block = (bi_hi as u32) << 16 | bi_lo as u32;
+                let tuple = if multiple {
+                    format!("the vector {extra} of the heap tuple ({block},{ip_posid})")
+                } else {
+                    format!("the heap tuple ({block},{ip_posid})")
+                };
+                match entries.get(&payload).copied().unwrap_or_default() {
+                    1 => (),
+                    0 => problems.push(Problem {
+                        page: None,
+                        message: format!("{tuple} is not indexed"),
+                    }),
+                    n => problems.push(Problem {
+                        page: None,
+                        message: format!("{tuple} is indexed {n} times"),
+                    }),
+                }
+            }
+        });
+        unsafe {
+            pgrx::pg_sys::UnregisterSnapshot(snapshot);
+        }
+    }
+    problems
+}
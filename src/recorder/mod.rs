@@ -12,8 +12,8 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
-pub use types::{DefaultRecorder, Recorder};
-pub use worker::dump;
+pub use types::{DefaultRecorder, Parameters, Recorder};
+pub use worker::{Record, dump};
 
 mod hook;
 mod types;
@@ -13,8 +13,11 @@
 // Copyright (c) 2025-2026 TensorChord Inc.
 
 use simd::f16;
+use vector::VectorBorrowed;
+use vector::bvect::BVectBorrowed;
 use vector::rabitq4::Rabitq4Borrowed;
 use vector::rabitq8::Rabitq8Borrowed;
+use vector::svect::SVectBorrowed;
 use vector::vect::VectBorrowed;
 
 pub fn vector_out(vector: VectBorrowed<'_, f32>) -> String {
@@ -41,6 +44,27 @@ pub fn halfvec_out(vector: VectBorrowed<'_, f16>) -> String {
     result
 }
 
+pub fn sparsevec_out(vector: SVectBorrowed<'_, f32>) -> String {
+    let mut result = String::from("{");
+    for (i, x) in std::iter::zip(vector.indexes(), vector.values()) {
+        if !result.ends_with('{') {
+            result.push(',');
+        }
+        result.push_str(&format!("{}:{}", i + 1, x));
+    }
+    result.push_str("}/");
+    result.push_str(&vector.dim().to_string());
+    result
+}
+
+pub fn bit_out(vector: BVectBorrowed<'_>) -> String {
+    let mut result = String::with_capacity(vector.dim() as _);
+    for x in vector.iter() {
+        result.push(if x { '1' } else { '0' });
+    }
+    result
+}
+
 pub fn rabitq8_out(vector: Rabitq8Borrowed<'_>) -> String {
     let mut result = String::new();
     result.push('(');
@@ -12,21 +12,89 @@
 //
 // Copyright (c) 2025-2026 TensorChord Inc.
 
-use crate::recorder::worker::push;
+use crate::recorder::worker::{Record, push};
 use rand::RngExt;
-use std::cell::RefMut;
+use std::cell::{RefCell, RefMut};
+use std::rc::Rc;
+use std::time::Duration;
 
 pub trait Recorder {
     fn is_enabled(&self) -> bool;
     fn send(&self, sample: &str);
 }
 
-#[derive(Debug)]
+/// The search parameters of a scan, recorded alongside its sample.
+#[derive(Debug, Clone, Default)]
+pub struct Parameters {
+    pub probes: Option<String>,
+    pub epsilon: Option<f32>,
+    pub max_scan_tuples: Option<u32>,
+    pub prefilter: Option<bool>,
+}
+
+#[derive(Debug, Default)]
+struct Statistics {
+    sample: Option<String>,
+    limit: Option<i64>,
+    returned_rows: u64,
+    elapsed: Duration,
+}
+
+/// A sample is only written when the scan ends, so that it carries the
+/// number of returned rows and the time spent in the index. Whether a scan is
+/// sampled is decided when the recorder is created, so that unsampled scans
+/// are not timed.
+#[derive(Debug, Clone)]
 pub struct DefaultRecorder {
-    pub enable: bool,
-    pub rate: Option<f64>,
-    pub max_records: u32,
-    pub index: u32,
+    sampled: bool,
+    max_records: u32,
+    index: u32,
+    parameters: Parameters,
+    statistics: Rc<RefCell<Statistics>>,
+}
+
+impl DefaultRecorder {
+    pub fn new(
+        enable: bool,
+        rate: Option<f64>,
+        max_records: u32,
+        index: u32,
+        parameters: Parameters,
+    ) -> Self {
+        let sampled = enable && rate.is_some_and(|rate| rand::rng().random_bool(rate));
+        Self {
+            sampled,
+            max_records,
+            index,
+            parameters,
+            statistics: Rc::new(RefCell::new(Statistics::default())),
+        }
+    }
+    pub fn set_limit(&self, limit: Option<i64>) {
+        self.statistics.borrow_mut().limit = limit;
+    }
+    pub fn observe(&self, elapsed: Duration, returned_rows: u64) {
+        let mut statistics = self.statistics.borrow_mut();
+        statistics.elapsed += elapsed;
+        statistics.returned_rows += returned_rows;
+    }
+    /// Writes the sample of the finished scan, if there is one.
+    pub fn flush(&self) {
+        let statistics = std::mem::take(&mut *self.statistics.borrow_mut());
+        if let Some(sample) = statistics.sample {
+            let record = Record {
+                sample,
+                probes: self.parameters.probes.clone(),
+                epsilon: self.parameters.epsilon,
+                max_scan_tuples: self.parameters.max_scan_tuples,
+                prefilter: self.parameters.prefilter,
+                limit: statistics.limit,
+                returned_rows: statistics.returned_rows,
+                elapsed: statistics.elapsed.as_secs_f64(),
+            };
+            push(self.index, &record, self.max_records);
+        }
+    }
 }
 
 pub struct PgRefCell<T>(std::cell::RefCell<T>);
@@ -49,14 +117,11 @@ impl<T> PgRefCell<T> {
 
 impl Recorder for DefaultRecorder {
     fn is_enabled(&self) -> bool {
-        self.enable
+        self.sampled
     }
     fn send(&self, sample: &str) {
-        if let Some(rate) = self.rate {
-            let mut rng = rand::rng();
-            if rng.random_bool(rate) {
-                push(self.index, sample, self.max_records);
-            }
+        if self.sampled {
+            self.statistics.borrow_mut().sample = Some(sample.to_string());
         }
     }
 }
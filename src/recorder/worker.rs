@@ -19,7 +19,20 @@ use std::path::Path;
 
 // The directory name must start with "pgsql_tmp" to be excluded by pg_basebackup
 const RECORDER_DIR: &str = "pgsql_tmp_vchord_sampling";
-const RECORDER_VERSION: u32 = 1;
+const RECORDER_VERSION: u32 = 2;
+
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub sample: String,
+    pub probes: Option<String>,
+    pub epsilon: Option<f32>,
+    pub max_scan_tuples: Option<u32>,
+    pub prefilter: Option<bool>,
+    pub limit: Option<i64>,
+    pub returned_rows: u64,
+    /// Time spent in the index, in seconds.
+    pub elapsed: f64,
+}
 
 static CONNECTION: PgRefCell<Option<rusqlite::Connection>> =
     PgRefCell::<Option<rusqlite::Connection>>::new(None);
@@ -77,19 +90,26 @@ fn get<'a>(create: bool) -> Option<RefMut<'a, rusqlite::Connection>> {
     RefMut::filter_map(connection, |c| c.as_mut()).ok()
 }
 
-pub fn push(index: u32, sample: &str, max_records: u32) {
+pub fn push(index: u32, record: &Record, max_records: u32) {
     let mut connection = match get(true) {
         Some(c) => c,
         None => return,
     };
     let init_statement = format!(
         "
-        CREATE TABLE IF NOT EXISTS index_{index} (sample TEXT, create_at REAL);
+        CREATE TABLE IF NOT EXISTS index_{index} (
+            sample TEXT, probes TEXT, epsilon REAL, max_scan_tuples INTEGER, prefilter INTEGER,
+            limit_count INTEGER, returned_rows INTEGER, elapsed REAL, create_at REAL
+        );
         CREATE INDEX IF NOT EXISTS i ON index_{index} (create_at);
         "
     );
-    let insert_statement =
-        format!("INSERT INTO index_{index} (sample, create_at) VALUES (?1, unixepoch('subsec'))");
+    let insert_statement = format!(
+        "INSERT INTO index_{index} (
+            sample, probes, epsilon, max_scan_tuples, prefilter,
+            limit_count, returned_rows, elapsed, create_at
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, unixepoch('subsec'))"
+    );
     let count_statement = format!("SELECT COUNT(create_at) FROM index_{index}");
     let maintain_statement = format!(
         "DELETE FROM index_{index} WHERE rowid = (
@@ -98,7 +118,16 @@ pub fn push(index: u32, sample: &str, max_records: u32) {
     if let Err(err) = || -> rusqlite::Result<()> {
         let tx = connection.transaction()?;
         tx.execute_batch(&init_statement)?;
-        tx.prepare_cached(&insert_statement)?.execute((sample,))?;
+        tx.prepare_cached(&insert_statement)?.execute((
+            &record.sample,
+            &record.probes,
+            record.epsilon,
+            record.max_scan_tuples,
+            record.prefilter,
+            record.limit,
+            record.returned_rows as i64,
+            record.elapsed,
+        ))?;
         let records = tx.query_one(&count_statement, (), |row| row.get::<usize, u32>(0))?;
         if records > max_records {
             tx.execute(&maintain_statement, (records - max_records,))?;
@@ -127,19 +156,31 @@ pub fn delete_database(database_oid: u32) {
     let _ = fs::remove_file(format!("{RECORDER_DIR}/database_{database_oid}.sqlite-wal"));
 }
 
-pub fn dump(index: u32) -> Vec<String> {
+pub fn dump(index: u32) -> Vec<Record> {
     let connection = match get(false) {
         Some(c) => c,
         None => return Vec::new(),
     };
-    let load_statement = format!("SELECT sample FROM index_{index} ORDER BY create_at DESC");
-    match || -> rusqlite::Result<Vec<String>> {
+    let load_statement = format!(
+        "SELECT sample, probes, epsilon, max_scan_tuples, prefilter, limit_count, returned_rows, elapsed
+        FROM index_{index} ORDER BY create_at DESC"
+    );
+    match || -> rusqlite::Result<Vec<Record>> {
         let mut stmt = connection.prepare(&load_statement)?;
         let mut rows = stmt.query(())?;
         let mut result = Vec::new();
         while let Some(row) = rows.next()? {
             if let Ok(sample) = row.get::<usize, String>(0) {
-                result.push(sample);
+                result.push(Record {
+                    sample,
+                    probes: row.get::<usize, _>(1).unwrap_or_default(),
+                    epsilon: row.get::<usize, _>(2).unwrap_or_default(),
+                    max_scan_tuples: row.get::<usize, _>(3).unwrap_or_default(),
+                    prefilter: row.get::<usize, _>(4).unwrap_or_default(),
+                    limit: row.get::<usize, _>(5).unwrap_or_default(),
+                    returned_rows: row.get::<usize, i64>(6).unwrap_or_default() as u64,
+                    elapsed: row.get::<usize, _>(7).unwrap_or_default(),
+                });
             }
         }
         Ok(result)
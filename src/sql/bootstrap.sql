@@ -6,3 +6,11 @@ CREATE TYPE rabitq8;
 CREATE TYPE sphere_rabitq8;
 CREATE TYPE rabitq4;
 CREATE TYPE sphere_rabitq4;
+CREATE TYPE sphere_sparsevec;
+CREATE TYPE sphere_bit;
+CREATE TYPE maxsim_threshold_vector;
+CREATE TYPE maxsim_threshold_halfvec;
+CREATE TYPE maxsim_threshold_rabitq8;
+CREATE TYPE maxsim_threshold_rabitq4;
+CREATE TYPE multi_query_vector;
+CREATE TYPE multi_query_halfvec;
@@ -38,6 +38,46 @@ CREATE TYPE sphere_rabitq4 AS (
     radius REAL
 );
 
+CREATE TYPE sphere_sparsevec AS (
+    center sparsevec,
+    radius REAL
+);
+
+CREATE TYPE sphere_bit AS (
+    center bit,
+    radius REAL
+);
+
+CREATE TYPE maxsim_threshold_vector AS (
+    query vector[],
+    threshold REAL
+);
+
+CREATE TYPE maxsim_threshold_halfvec AS (
+    query halfvec[],
+    threshold REAL
+);
+
+CREATE TYPE maxsim_threshold_rabitq8 AS (
+    query rabitq8[],
+    threshold REAL
+);
+
+CREATE TYPE maxsim_threshold_rabitq4 AS (
+    query rabitq4[],
+    threshold REAL
+);
+
+CREATE TYPE multi_query_vector AS (
+    queries vector[],
+    weights REAL[]
+);
+
+CREATE TYPE multi_query_halfvec AS (
+    queries halfvec[],
+    weights REAL[]
+);
+
 -- List of internal functions
 
 CREATE FUNCTION _vchord_rabitq8_operator_maxsim(rabitq8[], rabitq8[]) RETURNS real
@@ -46,6 +86,12 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8
 CREATE FUNCTION _vchord_rabitq4_operator_maxsim(rabitq4[], rabitq4[]) RETURNS real
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_operator_maxsim_wrapper';
 
+CREATE FUNCTION _vchord_rabitq8_maxsim_threshold_in(rabitq8[], maxsim_threshold_rabitq8) RETURNS bool
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq8_maxsim_threshold_in_wrapper';
+
+CREATE FUNCTION _vchord_rabitq4_maxsim_threshold_in(rabitq4[], maxsim_threshold_rabitq4) RETURNS bool
+IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4_maxsim_threshold_in_wrapper';
+
 -- List of operators
 
 CREATE OPERATOR <-> (
@@ -114,6 +160,12 @@ CREATE OPERATOR <<->> (
     RIGHTARG = sphere_rabitq4
 );
 
+CREATE OPERATOR <<->> (
+    PROCEDURE = _vchord_sparsevec_sphere_l2_in,
+    LEFTARG = sparsevec,
+    RIGHTARG = sphere_sparsevec
+);
+
 CREATE OPERATOR <<#>> (
     PROCEDURE = _vchord_vector_sphere_ip_in,
     LEFTARG = vector,
@@ -138,6 +190,12 @@ CREATE OPERATOR <<#>> (
     RIGHTARG = sphere_rabitq4
 );
 
+CREATE OPERATOR <<#>> (
+    PROCEDURE = _vchord_sparsevec_sphere_ip_in,
+    LEFTARG = sparsevec,
+    RIGHTARG = sphere_sparsevec
+);
+
 CREATE OPERATOR <<=>> (
     PROCEDURE = _vchord_vector_sphere_cosine_in,
     LEFTARG = vector,
@@ -162,6 +220,24 @@ CREATE OPERATOR <<=>> (
     RIGHTARG = sphere_rabitq4
 );
 
+CREATE OPERATOR <<=>> (
+    PROCEDURE = _vchord_sparsevec_sphere_cosine_in,
+    LEFTARG = sparsevec,
+    RIGHTARG = sphere_sparsevec
+);
+
+CREATE OPERATOR <<~>> (
+    PROCEDURE = _vchord_bit_sphere_hamming_in,
+    LEFTARG = bit,
+    RIGHTARG = sphere_bit
+);
+
+CREATE OPERATOR <<%>> (
+    PROCEDURE = _vchord_bit_sphere_jaccard_in,
+    LEFTARG = bit,
+    RIGHTARG = sphere_bit
+);
+
 CREATE OPERATOR @# (
     PROCEDURE = _vchord_vector_operator_maxsim,
     LEFTARG = vector[],
@@ -186,6 +262,66 @@ CREATE OPERATOR @# (
     RIGHTARG = rabitq4[]
 );
 
+CREATE OPERATOR @#> (
+    PROCEDURE = _vchord_vector_maxsim_threshold_in,
+    LEFTARG = vector[],
+    RIGHTARG = maxsim_threshold_vector
+);
+
+CREATE OPERATOR @#> (
+    PROCEDURE = _vchord_halfvec_maxsim_threshold_in,
+    LEFTARG = halfvec[],
+    RIGHTARG = maxsim_threshold_halfvec
+);
+
+CREATE OPERATOR @#> (
+    PROCEDURE = _vchord_rabitq8_maxsim_threshold_in,
+    LEFTARG = rabitq8[],
+    RIGHTARG = maxsim_threshold_rabitq8
+);
+
+CREATE OPERATOR @#> (
+    PROCEDURE = _vchord_rabitq4_maxsim_threshold_in,
+    LEFTARG = rabitq4[],
+    RIGHTARG = maxsim_threshold_rabitq4
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vchord_vector_multi_query_l2,
+    LEFTARG = vector,
+    RIGHTARG = multi_query_vector
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vchord_vector_multi_query_ip,
+    LEFTARG = vector,
+    RIGHTARG = multi_query_vector
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vchord_vector_multi_query_cosine,
+    LEFTARG = vector,
+    RIGHTARG = multi_query_vector
+);
+
+CREATE OPERATOR <-> (
+    PROCEDURE = _vchord_halfvec_multi_query_l2,
+    LEFTARG = halfvec,
+    RIGHTARG = multi_query_halfvec
+);
+
+CREATE OPERATOR <#> (
+    PROCEDURE = _vchord_halfvec_multi_query_ip,
+    LEFTARG = halfvec,
+    RIGHTARG = multi_query_halfvec
+);
+
+CREATE OPERATOR <=> (
+    PROCEDURE = _vchord_halfvec_multi_query_cosine,
+    LEFTARG = halfvec,
+    RIGHTARG = multi_query_halfvec
+);
+
 -- List of functions
 
 CREATE FUNCTION sphere(vector, real) RETURNS sphere_vector
@@ -200,6 +336,30 @@ IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_rabitq8';
 CREATE FUNCTION sphere(rabitq4, real) RETURNS sphere_rabitq4
 IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_rabitq4';
 
+CREATE FUNCTION sphere(sparsevec, real) RETURNS sphere_sparsevec
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_sparsevec';
+
+CREATE FUNCTION sphere(bit, real) RETURNS sphere_bit
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::sphere_bit';
+
+CREATE FUNCTION maxsim_threshold(vector[], real) RETURNS maxsim_threshold_vector
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::maxsim_threshold_vector';
+
+CREATE FUNCTION maxsim_threshold(halfvec[], real) RETURNS maxsim_threshold_halfvec
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::maxsim_threshold_halfvec';
+
+CREATE FUNCTION maxsim_threshold(rabitq8[], real) RETURNS maxsim_threshold_rabitq8
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::maxsim_threshold_rabitq8';
+
+CREATE FUNCTION maxsim_threshold(rabitq4[], real) RETURNS maxsim_threshold_rabitq4
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::maxsim_threshold_rabitq4';
+
+CREATE FUNCTION multi_query(vector[], real[]) RETURNS multi_query_vector
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::multi_query_vector';
+
+CREATE FUNCTION multi_query(halfvec[], real[]) RETURNS multi_query_halfvec
+IMMUTABLE PARALLEL SAFE LANGUAGE sql AS 'SELECT ROW($1, $2)::multi_query_halfvec';
+
 CREATE FUNCTION quantize_to_rabitq8(vector) RETURNS rabitq8
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_vector_quantize_to_rabitq8_wrapper';
 
@@ -227,6 +387,19 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchord_rabitq4
 CREATE FUNCTION vchordrq_sampled_values(regclass) RETURNS SETOF TEXT
 STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_sampled_values_wrapper';
 
+CREATE FUNCTION vchordrq_sampled_records(regclass)
+RETURNS TABLE(
+    value TEXT,
+    probes TEXT,
+    epsilon REAL,
+    max_scan_tuples INTEGER,
+    prefilter BOOLEAN,
+    limit_count BIGINT,
+    returned_rows BIGINT,
+    elapsed_ms DOUBLE PRECISION
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_sampled_records_wrapper';
+
 CREATE FUNCTION vchordrq_sampled_queries(regclass)
 RETURNS TABLE(
     schema_name NAME,
@@ -234,7 +407,14 @@ RETURNS TABLE(
     table_name NAME,
     column_name NAME,
     operator NAME,
-    value TEXT
+    value TEXT,
+    probes TEXT,
+    epsilon REAL,
+    max_scan_tuples INTEGER,
+    prefilter BOOLEAN,
+    limit_count BIGINT,
+    returned_rows BIGINT,
+    elapsed_ms DOUBLE PRECISION
 )
 STRICT LANGUAGE plpgsql AS $$
 DECLARE
@@ -285,6 +465,92 @@ BEGIN
                 AND X.indnatts = 1
                 AND X.indexrelid = %1$s
         )
+        SELECT
+            im.schema_name,
+            im.index_name,
+            im.table_name,
+            im.column_name,
+            im.operator,
+            s.value,
+            s.probes,
+            s.epsilon,
+            s.max_scan_tuples,
+            s.prefilter,
+            s.limit_count,
+            s.returned_rows,
+            s.elapsed_ms
+        FROM
+            index_metadata im,
+            LATERAL %2$I.vchordrq_sampled_records(%1$s) AS s;
+        $q$,
+        $1::oid,
+        ext_schema
+    );
+    RETURN QUERY EXECUTE query_text;
+END;
+$$;
+
+CREATE FUNCTION vchordg_sampled_values(regclass) RETURNS SETOF TEXT
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_sampled_values_wrapper';
+
+CREATE FUNCTION vchordg_sampled_queries(regclass)
+RETURNS TABLE(
+    schema_name NAME,
+    index_name NAME,
+    table_name NAME,
+    column_name NAME,
+    operator NAME,
+    value TEXT
+)
+STRICT LANGUAGE plpgsql AS $$
+DECLARE
+    ext_schema TEXT;
+    query_text TEXT;
+BEGIN
+    SELECT n.nspname
+    INTO ext_schema
+    FROM pg_catalog.pg_extension e
+    JOIN pg_catalog.pg_namespace n ON n.oid = e.extnamespace
+    WHERE e.extname = 'vchord';
+
+    IF ext_schema IS NULL THEN
+        RAISE EXCEPTION 'vchord is not installed';
+    END IF;
+
+    query_text := format(
+        $q$
+        WITH index_metadata AS (
+            SELECT
+                NS.nspname AS schema_name,
+                I.relname AS index_name,
+                C.relname AS table_name,
+                PA.attname AS column_name,
+                OP.oprname AS operator
+            FROM
+                pg_catalog.pg_index X
+            JOIN
+                pg_catalog.pg_class C ON C.oid = X.indrelid
+            JOIN
+                pg_catalog.pg_namespace NS ON C.relnamespace = NS.oid
+            JOIN
+                pg_catalog.pg_class I ON I.oid = X.indexrelid
+            JOIN
+                pg_catalog.pg_am A ON A.oid = I.relam
+            LEFT JOIN
+                pg_catalog.pg_opclass AS OPC ON OPC.oid = X.indclass[0]
+            LEFT JOIN
+                pg_catalog.pg_amop AO ON OPC.opcfamily = AO.amopfamily
+            LEFT JOIN
+                pg_catalog.pg_operator OP ON OP.oid = AO.amopopr
+            LEFT JOIN
+                pg_catalog.pg_attribute PA ON PA.attrelid = X.indrelid AND PA.attnum = X.indkey[0]
+            WHERE
+                A.amname = 'vchordg'
+                AND AO.amopstrategy = 1
+                AND C.relkind = 'r'
+                AND X.indnatts = 1
+                AND X.indexrelid = %1$s
+        )
         SELECT
             im.schema_name,
             im.index_name,
@@ -294,7 +560,7 @@ BEGIN
             s.value
         FROM
             index_metadata im,
-            LATERAL %2$I.vchordrq_sampled_values(%1$s) AS s(value);
+            LATERAL %2$I.vchordg_sampled_values(%1$s) AS s(value);
         $q$,
         $1::oid,
         ext_schema
@@ -309,6 +575,47 @@ IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_amhan
 CREATE FUNCTION vchordrq_prewarm(regclass, integer default 0) RETURNS TEXT
 STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_prewarm_wrapper';
 
+CREATE FUNCTION vchordrq_index_info(regclass)
+RETURNS TABLE(
+    version BIGINT,
+    dim INTEGER,
+    height_of_root INTEGER,
+    cells INTEGER[],
+    is_residual BOOLEAN,
+    rerank_in_heap BOOLEAN,
+    centroid_pages BIGINT,
+    vector_pages BIGINT,
+    h1_pages BIGINT,
+    jump_pages BIGINT,
+    directory_pages BIGINT,
+    frozen_pages BIGINT,
+    appendable_pages BIGINT,
+    free_pages BIGINT
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_index_info_wrapper';
+
+CREATE FUNCTION vchordrq_lists(regclass)
+RETURNS TABLE(
+    id INTEGER,
+    parent INTEGER,
+    live_tuples BIGINT,
+    dead_tuples BIGINT,
+    directory_pages BIGINT,
+    frozen_pages BIGINT,
+    appendable_pages BIGINT
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_lists_wrapper';
+
+CREATE FUNCTION vchordrq_recluster(regclass, text default '') RETURNS void
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_recluster_wrapper';
+
+CREATE FUNCTION vchordrq_verify(regclass, heapallindexed boolean default false)
+RETURNS TABLE(
+    page BIGINT,
+    problem TEXT
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_verify_wrapper';
+
 CREATE FUNCTION vchordrq_evaluate_query_recall(
     query text,
     exact_search boolean default false,
@@ -382,12 +689,266 @@ BEGIN
 END;
 $$;
 
+CREATE FUNCTION vchordrq_debug_scanned_tuples() RETURNS BIGINT
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordrq_debug_scanned_tuples_wrapper';
+
+CREATE FUNCTION vchordrq_recommend_search_params(regclass, target_recall real)
+RETURNS TABLE(
+    probes TEXT,
+    epsilon REAL,
+    recall REAL,
+    avg_scanned_tuples REAL
+)
+STRICT LANGUAGE plpgsql AS $$
+DECLARE
+    ext_schema text;
+    epsilons real[] := ARRAY[1.0, 1.4, 1.9];
+    index_height integer;
+    index_cells integer[];
+    candidates text[] := ARRAY[]::text[];
+    candidate text;
+    queries text[] := ARRAY[]::text[];
+    truths text[] := ARRAY[]::text[];
+    sampled record;
+    query text;
+    rough tid[];
+    accu tid[];
+    match_count integer;
+    total_recall real;
+    scanned_before bigint;
+    scanned_after bigint;
+    leaf_probes integer;
+    lists integer;
+    eps real;
+    pair_recall real;
+    pair_scanned real;
+    reached boolean := false;
+    old_enable_scan text := current_setting('vchordrq.enable_scan');
+    old_probes text := current_setting('vchordrq.probes');
+    old_epsilon text := current_setting('vchordrq.epsilon');
+    old_parallel text := current_setting('max_parallel_workers_per_gather');
+    old_sampling text := current_setting('vchordrq.query_sampling_enable');
+    old_debug text := current_setting('vchordrq.debug_scanned_tuples');
+BEGIN
+    IF target_recall < 0 OR target_recall > 1 THEN
+        RAISE EXCEPTION 'target recall must be between 0 and 1';
+    END IF;
+    SELECT n.nspname
+    INTO ext_schema
+    FROM pg_catalog.pg_extension e
+    JOIN pg_catalog.pg_namespace n ON n.oid = e.extnamespace
+    WHERE e.extname = 'vchord';
+
+    IF ext_schema IS NULL THEN
+        RAISE EXCEPTION 'vchord is not installed';
+    END IF;
+
+    EXECUTE format('SELECT height_of_root, cells FROM %I.vchordrq_index_info($1)', ext_schema)
+    INTO index_height, index_cells
+    USING $1;
+
+    -- probes grow level by level in the same proportion, doubling at the leaf level
+    IF index_height = 1 THEN
+        candidates := ARRAY[''];
+    ELSE
+        leaf_probes := 1;
+        LOOP
+            candidate := '';
+            FOR level IN 1..index_height - 1 LOOP
+                lists := index_cells[index_height - level];
+                IF level > 1 THEN
+                    candidate := candidate || ',';
+                END IF;
+                candidate := candidate || greatest(1, least(lists, ceil(lists::real * leaf_probes / index_cells[1])::integer));
+            END LOOP;
+            candidates := candidates || candidate;
+            EXIT WHEN leaf_probes >= index_cells[1];
+            leaf_probes := least(leaf_probes * 2, index_cells[1]);
+        END LOOP;
+    END IF;
+
+    -- replayed queries must not be sampled again
+    BEGIN
+        PERFORM set_config('vchordrq.query_sampling_enable', 'off', true);
+    EXCEPTION WHEN insufficient_privilege THEN
+        NULL;
+    END;
+    PERFORM set_config('max_parallel_workers_per_gather', '0', true);
+    PERFORM set_config('vchordrq.debug_scanned_tuples', 'on', true);
+
+    -- only the vector and the limit of a query are sampled, so queries are
+    -- replayed without their filters, and the recommendation assumes unfiltered
+    -- searches
+    FOR sampled IN EXECUTE format('SELECT * FROM %I.vchordrq_sampled_queries($1)', ext_schema) USING $1 LOOP
+        -- queries on expression indexes cannot be replayed
+        CONTINUE WHEN sampled.column_name IS NULL;
+        query := format(
+            'SELECT ctid FROM %I.%I ORDER BY %I OPERATOR(%s) %L LIMIT %s',
+            sampled.schema_name,
+            sampled.table_name,
+            sampled.column_name,
+            sampled.operator,
+            sampled.value,
+            coalesce(sampled.limit_count, 10)
+        );
+        PERFORM set_config('vchordrq.enable_scan', 'off', true);
+        EXECUTE
+            format('SELECT coalesce(array_agg(id), array[]::tid[]) FROM (%s) AS result(id)', query)
+        INTO
+            accu;
+        PERFORM set_config('vchordrq.enable_scan', old_enable_scan, true);
+        CONTINUE WHEN cardinality(accu) = 0;
+        queries := queries || query;
+        truths := truths || accu::text;
+    END LOOP;
+    IF cardinality(queries) = 0 THEN
+        RAISE EXCEPTION 'there are no sampled queries to replay for the index %', $1;
+    END IF;
+
+    -- every pair is evaluated, and the cheapest one reaching the target recall
+    -- is recommended, or the one with the best recall if none reaches it
+    FOREACH candidate IN ARRAY candidates LOOP
+        FOREACH eps IN ARRAY epsilons LOOP
+            PERFORM set_config('vchordrq.probes', candidate, true);
+            PERFORM set_config('vchordrq.epsilon', eps::text, true);
+            total_recall := 0;
+            EXECUTE format('SELECT %I.vchordrq_debug_scanned_tuples()', ext_schema) INTO scanned_before;
+            FOR i IN 1..cardinality(queries) LOOP
+                EXECUTE
+                    format('SELECT coalesce(array_agg(id), array[]::tid[]) FROM (%s) AS result(id)', queries[i])
+                INTO
+                    rough;
+                accu := truths[i]::tid[];
+                SELECT COUNT(*) INTO match_count FROM (SELECT unnest(rough) INTERSECT SELECT unnest(accu)) AS tids;
+                total_recall := total_recall + match_count::real / cardinality(accu)::real;
+            END LOOP;
+            pair_recall := total_recall / cardinality(queries);
+            EXECUTE format('SELECT %I.vchordrq_debug_scanned_tuples()', ext_schema) INTO scanned_after;
+            pair_scanned := (scanned_after - scanned_before)::real / cardinality(queries);
+            IF pair_recall >= target_recall THEN
+                IF NOT reached OR pair_scanned < avg_scanned_tuples THEN
+                    probes := candidate;
+                    epsilon := eps;
+                    recall := pair_recall;
+                    avg_scanned_tuples := pair_scanned;
+                END IF;
+                reached := true;
+            ELSIF NOT reached AND (recall IS NULL OR pair_recall > recall) THEN
+                probes := candidate;
+                epsilon := eps;
+                recall := pair_recall;
+                avg_scanned_tuples := pair_scanned;
+            END IF;
+        END LOOP;
+    END LOOP;
+    IF NOT reached THEN
+        RAISE WARNING 'target recall % is not reached, the best recall is %', target_recall, recall;
+    END IF;
+
+    PERFORM set_config('vchordrq.probes', old_probes, true);
+    PERFORM set_config('vchordrq.epsilon', old_epsilon, true);
+    PERFORM set_config('max_parallel_workers_per_gather', old_parallel, true);
+    PERFORM set_config('vchordrq.debug_scanned_tuples', old_debug, true);
+    BEGIN
+        PERFORM set_config('vchordrq.query_sampling_enable', old_sampling, true);
+    EXCEPTION WHEN insufficient_privilege THEN
+        NULL;
+    END;
+    RETURN NEXT;
+END;
+$$;
+
 CREATE FUNCTION vchordg_amhandler(internal) RETURNS index_am_handler
 IMMUTABLE STRICT PARALLEL SAFE LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_amhandler_wrapper';
 
 CREATE FUNCTION vchordg_prewarm(regclass) RETURNS TEXT
 STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_prewarm_wrapper';
 
+CREATE FUNCTION vchordg_index_info(regclass)
+RETURNS TABLE(
+    dim INTEGER,
+    bits INTEGER,
+    m INTEGER,
+    alpha REAL[],
+    ef_construction INTEGER,
+    beam_construction INTEGER,
+    start_page BIGINT,
+    start_offset INTEGER,
+    vertex_pages BIGINT,
+    vector_pages BIGINT
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_index_info_wrapper';
+
+CREATE FUNCTION vchordg_graph_stats(regclass)
+RETURNS TABLE(
+    vertices BIGINT,
+    deleted_vertices BIGINT,
+    dangling_pointers BIGINT,
+    unreachable_vertices BIGINT,
+    out_degree_histogram BIGINT[]
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_graph_stats_wrapper';
+
+CREATE FUNCTION vchordg_verify(regclass, heapallindexed boolean default false)
+RETURNS TABLE(
+    page BIGINT,
+    problem TEXT
+)
+STRICT LANGUAGE c AS 'MODULE_PATHNAME', '_vchordg_verify_wrapper';
+
+CREATE FUNCTION vchordg_evaluate_query_recall(
+    query text,
+    exact_search boolean default false,
+    accu_ef_search integer default 65535
+)
+RETURNS real
+LANGUAGE plpgsql
+AS $$
+DECLARE
+    rough tid[];
+    accu tid[];
+    match_count integer := 0;
+    accu_k integer;
+    recall real;
+BEGIN
+    IF query IS NULL OR exact_search IS NULL OR accu_ef_search IS NULL THEN
+        RETURN NULL;
+    END IF;
+
+    BEGIN
+        EXECUTE
+            format('SELECT coalesce(array_agg(id), array[]::tid[]) FROM (%s) AS result(id)', query)
+        INTO
+            rough;
+    EXCEPTION WHEN OTHERS THEN
+        RAISE EXCEPTION 'Error executing ANN query "%": %', query, SQLERRM;
+    END;
+
+    BEGIN
+        IF exact_search THEN
+            SET LOCAL vchordg.enable_scan = off;
+        ELSE
+            EXECUTE format('SET LOCAL "vchordg.ef_search" = %L', accu_ef_search);
+            SET LOCAL vchordg.max_scan_tuples = -1;
+        END IF;
+        EXECUTE
+            format('SELECT coalesce(array_agg(id), array[]::tid[]) FROM (%s) AS result(id)', query)
+        INTO
+            accu;
+    EXCEPTION WHEN OTHERS THEN
+         RAISE EXCEPTION 'Error executing Ground Truth query "%": %', query, SQLERRM;
+    END;
+    accu_k := cardinality(accu);
+    IF accu_k = 0 THEN
+        RAISE WARNING  'Query "%": No results found, returning NaN for recall.', query;
+        RETURN 'NaN';
+    END IF;
+    SELECT COUNT(*) INTO match_count FROM (SELECT unnest(rough) INTERSECT SELECT unnest(accu)) AS tids;
+    recall := match_count::real / accu_k::real;
+    RETURN recall;
+END;
+$$;
+
 -- List of access methods
 
 CREATE ACCESS METHOD vchordrq TYPE INDEX HANDLER vchordrq_amhandler;
@@ -407,6 +968,11 @@ CREATE OPERATOR FAMILY rabitq8_cosine_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq4_l2_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq4_ip_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq4_cosine_ops USING vchordrq;
+CREATE OPERATOR FAMILY sparsevec_l2_ops USING vchordrq;
+CREATE OPERATOR FAMILY sparsevec_ip_ops USING vchordrq;
+CREATE OPERATOR FAMILY sparsevec_cosine_ops USING vchordrq;
+CREATE OPERATOR FAMILY bit_hamming_ops USING vchordrq;
+CREATE OPERATOR FAMILY bit_jaccard_ops USING vchordrq;
 CREATE OPERATOR FAMILY vector_maxsim_ops USING vchordrq;
 CREATE OPERATOR FAMILY halfvec_maxsim_ops USING vchordrq;
 CREATE OPERATOR FAMILY rabitq8_maxsim_ops USING vchordrq;
@@ -423,6 +989,12 @@ CREATE OPERATOR FAMILY rabitq8_cosine_ops USING vchordg;
 CREATE OPERATOR FAMILY rabitq4_l2_ops USING vchordg;
 CREATE OPERATOR FAMILY rabitq4_ip_ops USING vchordg;
 CREATE OPERATOR FAMILY rabitq4_cosine_ops USING vchordg;
+CREATE OPERATOR FAMILY bit_hamming_ops USING vchordg;
+CREATE OPERATOR FAMILY bit_jaccard_ops USING vchordg;
+CREATE OPERATOR FAMILY vector_maxsim_ops USING vchordg;
+CREATE OPERATOR FAMILY halfvec_maxsim_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq8_maxsim_ops USING vchordg;
+CREATE OPERATOR FAMILY rabitq4_maxsim_ops USING vchordg;
 
 -- List of operator classes
 
@@ -430,36 +1002,42 @@ CREATE OPERATOR CLASS vector_l2_ops
     FOR TYPE vector USING vchordrq FAMILY vector_l2_ops AS
     OPERATOR 1 <-> (vector, vector) FOR ORDER BY float_ops,
     OPERATOR 2 <<->> (vector, sphere_vector) FOR SEARCH,
+    OPERATOR 4 <-> (vector, multi_query_vector) FOR ORDER BY float_ops,
     FUNCTION 1 _vchordrq_support_vector_l2_ops();
 
 CREATE OPERATOR CLASS vector_ip_ops
     FOR TYPE vector USING vchordrq FAMILY vector_ip_ops AS
     OPERATOR 1 <#> (vector, vector) FOR ORDER BY float_ops,
     OPERATOR 2 <<#>> (vector, sphere_vector) FOR SEARCH,
+    OPERATOR 4 <#> (vector, multi_query_vector) FOR ORDER BY float_ops,
     FUNCTION 1 _vchordrq_support_vector_ip_ops();
 
 CREATE OPERATOR CLASS vector_cosine_ops
     FOR TYPE vector USING vchordrq FAMILY vector_cosine_ops AS
     OPERATOR 1 <=> (vector, vector) FOR ORDER BY float_ops,
     OPERATOR 2 <<=>> (vector, sphere_vector) FOR SEARCH,
+    OPERATOR 4 <=> (vector, multi_query_vector) FOR ORDER BY float_ops,
     FUNCTION 1 _vchordrq_support_vector_cosine_ops();
 
 CREATE OPERATOR CLASS halfvec_l2_ops
     FOR TYPE halfvec USING vchordrq FAMILY halfvec_l2_ops AS
     OPERATOR 1 <-> (halfvec, halfvec) FOR ORDER BY float_ops,
     OPERATOR 2 <<->> (halfvec, sphere_halfvec) FOR SEARCH,
+    OPERATOR 4 <-> (halfvec, multi_query_halfvec) FOR ORDER BY float_ops,
     FUNCTION 1 _vchordrq_support_halfvec_l2_ops();
 
 CREATE OPERATOR CLASS halfvec_ip_ops
     FOR TYPE halfvec USING vchordrq FAMILY halfvec_ip_ops AS
     OPERATOR 1 <#> (halfvec, halfvec) FOR ORDER BY float_ops,
     OPERATOR 2 <<#>> (halfvec, sphere_halfvec) FOR SEARCH,
+    OPERATOR 4 <#> (halfvec, multi_query_halfvec) FOR ORDER BY float_ops,
     FUNCTION 1 _vchordrq_support_halfvec_ip_ops();
 
 CREATE OPERATOR CLASS halfvec_cosine_ops
     FOR TYPE halfvec USING vchordrq FAMILY halfvec_cosine_ops AS
     OPERATOR 1 <=> (halfvec, halfvec) FOR ORDER BY float_ops,
     OPERATOR 2 <<=>> (halfvec, sphere_halfvec) FOR SEARCH,
+    OPERATOR 4 <=> (halfvec, multi_query_halfvec) FOR ORDER BY float_ops,
     FUNCTION 1 _vchordrq_support_halfvec_cosine_ops();
 
 CREATE OPERATOR CLASS rabitq8_l2_ops
@@ -498,24 +1076,58 @@ CREATE OPERATOR CLASS rabitq4_cosine_ops
     OPERATOR 2 <<=>> (rabitq4, sphere_rabitq4) FOR SEARCH,
     FUNCTION 1 _vchordrq_support_rabitq4_cosine_ops();
 
+CREATE OPERATOR CLASS sparsevec_l2_ops
+    FOR TYPE sparsevec USING vchordrq FAMILY sparsevec_l2_ops AS
+    OPERATOR 1 <-> (sparsevec, sparsevec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<->> (sparsevec, sphere_sparsevec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_sparsevec_l2_ops();
+
+CREATE OPERATOR CLASS sparsevec_ip_ops
+    FOR TYPE sparsevec USING vchordrq FAMILY sparsevec_ip_ops AS
+    OPERATOR 1 <#> (sparsevec, sparsevec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<#>> (sparsevec, sphere_sparsevec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_sparsevec_ip_ops();
+
+CREATE OPERATOR CLASS sparsevec_cosine_ops
+    FOR TYPE sparsevec USING vchordrq FAMILY sparsevec_cosine_ops AS
+    OPERATOR 1 <=> (sparsevec, sparsevec) FOR ORDER BY float_ops,
+    OPERATOR 2 <<=>> (sparsevec, sphere_sparsevec) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_sparsevec_cosine_ops();
+
+CREATE OPERATOR CLASS bit_hamming_ops
+    FOR TYPE bit USING vchordrq FAMILY bit_hamming_ops AS
+    OPERATOR 1 <~> (bit, bit) FOR ORDER BY float_ops,
+    OPERATOR 2 <<~>> (bit, sphere_bit) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_bit_hamming_ops();
+
+CREATE OPERATOR CLASS bit_jaccard_ops
+    FOR TYPE bit USING vchordrq FAMILY bit_jaccard_ops AS
+    OPERATOR 1 <%> (bit, bit) FOR ORDER BY float_ops,
+    OPERATOR 2 <<%>> (bit, sphere_bit) FOR SEARCH,
+    FUNCTION 1 _vchordrq_support_bit_jaccard_ops();
+
 CREATE OPERATOR CLASS vector_maxsim_ops
     FOR TYPE vector[] USING vchordrq FAMILY vector_maxsim_ops AS
     OPERATOR 3 @# (vector[], vector[]) FOR ORDER BY float_ops,
+    OPERATOR 2 @#> (vector[], maxsim_threshold_vector) FOR SEARCH,
     FUNCTION 1 _vchordrq_support_vector_maxsim_ops();
 
 CREATE OPERATOR CLASS halfvec_maxsim_ops
     FOR TYPE halfvec[] USING vchordrq FAMILY halfvec_maxsim_ops AS
     OPERATOR 3 @# (halfvec[], halfvec[]) FOR ORDER BY float_ops,
+    OPERATOR 2 @#> (halfvec[], maxsim_threshold_halfvec) FOR SEARCH,
     FUNCTION 1 _vchordrq_support_halfvec_maxsim_ops();
 
 CREATE OPERATOR CLASS rabitq8_maxsim_ops
     FOR TYPE rabitq8[] USING vchordrq FAMILY rabitq8_maxsim_ops AS
     OPERATOR 3 @# (rabitq8[], rabitq8[]) FOR ORDER BY float_ops,
+    OPERATOR 2 @#> (rabitq8[], maxsim_threshold_rabitq8) FOR SEARCH,
     FUNCTION 1 _vchordrq_support_rabitq8_maxsim_ops();
 
 CREATE OPERATOR CLASS rabitq4_maxsim_ops
     FOR TYPE rabitq4[] USING vchordrq FAMILY rabitq4_maxsim_ops AS
     OPERATOR 3 @# (rabitq4[], rabitq4[]) FOR ORDER BY float_ops,
+    OPERATOR 2 @#> (rabitq4[], maxsim_threshold_rabitq4) FOR SEARCH,
     FUNCTION 1 _vchordrq_support_rabitq4_maxsim_ops();
 
 CREATE OPERATOR CLASS vector_l2_ops
@@ -590,6 +1202,38 @@ CREATE OPERATOR CLASS rabitq4_cosine_ops
     OPERATOR 2 <<=>> (rabitq4, sphere_rabitq4) FOR SEARCH,
     FUNCTION 1 _vchordg_support_rabitq4_cosine_ops();
 
+CREATE OPERATOR CLASS bit_hamming_ops
+    FOR TYPE bit USING vchordg FAMILY bit_hamming_ops AS
+    OPERATOR 1 <~> (bit, bit) FOR ORDER BY float_ops,
+    OPERATOR 2 <<~>> (bit, sphere_bit) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_bit_hamming_ops();
+
+CREATE OPERATOR CLASS bit_jaccard_ops
+    FOR TYPE bit USING vchordg FAMILY bit_jaccard_ops AS
+    OPERATOR 1 <%> (bit, bit) FOR ORDER BY float_ops,
+    OPERATOR 2 <<%>> (bit, sphere_bit) FOR SEARCH,
+    FUNCTION 1 _vchordg_support_bit_jaccard_ops();
+
+CREATE OPERATOR CLASS vector_maxsim_ops
+    FOR TYPE vector[] USING vchordg FAMILY vector_maxsim_ops AS
+    OPERATOR 3 @# (vector[], vector[]) FOR ORDER BY float_ops,
+    FUNCTION 1 _vchordg_support_vector_maxsim_ops();
+
+CREATE OPERATOR CLASS halfvec_maxsim_ops
+    FOR TYPE halfvec[] USING vchordg FAMILY halfvec_maxsim_ops AS
+    OPERATOR 3 @# (halfvec[], halfvec[]) FOR ORDER BY float_ops,
+    FUNCTION 1 _vchordg_support_halfvec_maxsim_ops();
+
+CREATE OPERATOR CLASS rabitq8_maxsim_ops
+    FOR TYPE rabitq8[] USING vchordg FAMILY rabitq8_maxsim_ops AS
+    OPERATOR 3 @# (rabitq8[], rabitq8[]) FOR ORDER BY float_ops,
+    FUNCTION 1 _vchordg_support_rabitq8_maxsim_ops();
+
+CREATE OPERATOR CLASS rabitq4_maxsim_ops
+    FOR TYPE rabitq4[] USING vchordg FAMILY rabitq4_maxsim_ops AS
+    OPERATOR 3 @# (rabitq4[], rabitq4[]) FOR ORDER BY float_ops,
+    FUNCTION 1 _vchordg_support_rabitq4_maxsim_ops();
+
 -- List of views
 
 CREATE VIEW vchordrq_sampled_queries AS
@@ -599,7 +1243,14 @@ SELECT
     record.table_name,
     record.column_name,
     record.operator,
-    record.value
+    record.value,
+    record.probes,
+    record.epsilon,
+    record.max_scan_tuples,
+    record.prefilter,
+    record.limit_count,
+    record.returned_rows,
+    record.elapsed_ms
 FROM
     (
         SELECT i.oid
@@ -610,3 +1261,22 @@ FROM
         WHERE am.amname = 'vchordrq'
     ) AS index_oids
 CROSS JOIN LATERAL vchordrq_sampled_queries(index_oids.oid::regclass) AS record;
+
+CREATE VIEW vchordg_sampled_queries AS
+SELECT
+    record.schema_name,
+    record.index_name,
+    record.table_name,
+    record.column_name,
+    record.operator,
+    record.value
+FROM
+    (
+        SELECT i.oid
+        FROM pg_catalog.pg_class AS i
+        JOIN pg_catalog.pg_index AS ix ON i.oid = ix.indexrelid
+        JOIN pg_catalog.pg_opclass AS opc ON ix.indclass[0] = opc.oid
+        JOIN pg_catalog.pg_am AS am ON opc.opcmethod = am.oid
+        WHERE am.amname = 'vchordg'
+    ) AS index_oids
+CROSS JOIN LATERAL vchordg_sampled_queries(index_oids.oid::regclass) AS record;